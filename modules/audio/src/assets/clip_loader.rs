@@ -1,11 +1,16 @@
+use std::future::Future;
 use std::io::Cursor;
+use std::pin::Pin;
 use std::sync::Arc;
+use std::task::{Context, Poll};
 
 use crayon::errors::Result;
-use crayon::res::utils::prelude::ResourceLoader;
+use crayon::res::utils::prelude::{poll_resource, ResourceHandle, ResourceLoader, ResourceState};
+use crayon::uuid::Uuid;
 use lewton::inside_ogg::OggStreamReader;
 
 use super::clip::*;
+use super::wav;
 
 pub const MAGIC: [u8; 8] = [b'C', b'S', b'F', b'X', b' ', 0, 0, 1];
 
@@ -28,18 +33,24 @@ impl ResourceLoader for AudioClipLoader {
             bail!("[AudioClipLoader] MAGIC number not match.");
         }
 
-        let cursor = Cursor::new(&bytes[8..]);
-        let mut stream_reader = OggStreamReader::new(cursor).unwrap();
+        let clip = if wav::is_wav(&bytes[8..]) {
+            wav::decode(&bytes[8..])?
+        } else {
+            let cursor = Cursor::new(&bytes[8..]);
+            let mut stream_reader = OggStreamReader::new(cursor)?;
 
-        let mut clip = AudioClip {
-            channels: stream_reader.ident_hdr.audio_channels,
-            sample_rate: stream_reader.ident_hdr.audio_sample_rate,
-            pcm: Vec::new(),
-        };
+            let mut clip = AudioClip {
+                channels: stream_reader.ident_hdr.audio_channels,
+                sample_rate: stream_reader.ident_hdr.audio_sample_rate,
+                pcm: Vec::new(),
+            };
 
-        while let Some(v) = stream_reader.read_dec_packet_itl()? {
-            clip.pcm.extend(&v);
-        }
+            while let Some(v) = stream_reader.read_dec_packet_itl()? {
+                clip.pcm.extend(&v);
+            }
+
+            clip
+        };
 
         info!(
             "[AudioClipLoader] loads clip {:?} (channels {:?} sample_rate {:?} pcm: {:?}).",
@@ -57,4 +68,33 @@ impl ResourceLoader for AudioClipLoader {
     }
 
     fn delete(&self, _: Self::Handle, _: Self::Resource) {}
+
+    fn size(&self, clip: &Self::Resource) -> usize {
+        clip.pcm.len() * std::mem::size_of::<i16>()
+    }
+}
+
+impl ResourceHandle for AudioClipHandle {
+    #[inline]
+    fn create_from_uuid(uuid: Uuid) -> Result<Self> {
+        crate::create_clip_from_uuid(uuid)
+    }
+
+    #[inline]
+    fn state(self) -> ResourceState {
+        crate::clip_state(self)
+    }
+
+    #[inline]
+    fn delete(self) {
+        crate::delete_clip(self)
+    }
+}
+
+impl Future for AudioClipHandle {
+    type Output = Result<Self>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        poll_resource(*self, cx)
+    }
 }
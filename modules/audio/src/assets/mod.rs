@@ -1,5 +1,6 @@
 pub mod clip;
 pub mod clip_loader;
+pub mod wav;
 
 pub mod prelude {
     pub use super::clip::{AudioClip, AudioClipHandle};
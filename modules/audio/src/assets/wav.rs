@@ -0,0 +1,79 @@
+//! A minimal decoder of uncompressed PCM WAVE files.
+
+use crayon::errors::Result;
+
+use super::clip::AudioClip;
+
+/// Checks if the bytes looks like a WAVE file.
+#[inline]
+pub fn is_wav(bytes: &[u8]) -> bool {
+    bytes.len() >= 12 && &bytes[0..4] == b"RIFF" && &bytes[8..12] == b"WAVE"
+}
+
+/// Decodes 8-bit or 16-bit PCM WAVE file into `AudioClip`.
+pub fn decode(bytes: &[u8]) -> Result<AudioClip> {
+    if !is_wav(bytes) {
+        bail!("[WAV] RIFF header not match.");
+    }
+
+    let mut fmt = None;
+    let mut data = None;
+    let mut iter = 12;
+
+    while iter + 8 <= bytes.len() {
+        let id = &bytes[iter..iter + 4];
+        let len = read_u32(&bytes[iter + 4..]) as usize;
+        let start = iter + 8;
+        let end = (start + len).min(bytes.len());
+
+        if id == b"fmt " {
+            // The chunk might be truncated at the end of file.
+            let chunk = &bytes[start..end];
+            if chunk.len() < 16 {
+                bail!("[WAV] fmt chunk is too small.");
+            }
+
+            fmt = Some((
+                read_u16(&chunk[0..]),
+                read_u16(&chunk[2..]),
+                read_u32(&chunk[4..]),
+                read_u16(&chunk[14..]),
+            ));
+        } else if id == b"data" {
+            data = Some(&bytes[start..end]);
+        }
+
+        // Chunks are aligned to 2 bytes.
+        iter = start + len + (len & 1);
+    }
+
+    let (format, channels, sample_rate, bits) =
+        fmt.ok_or_else(|| format_err!("[WAV] fmt chunk is missing."))?;
+    let data = data.ok_or_else(|| format_err!("[WAV] data chunk is missing."))?;
+
+    if format != 1 {
+        bail!("[WAV] only uncompressed PCM is supported (format {}).", format);
+    }
+
+    let pcm = match bits {
+        8 => data.iter().map(|&v| (i16::from(v) - 128) << 8).collect(),
+        16 => data.chunks(2).filter(|v| v.len() == 2).map(|v| read_u16(v) as i16).collect(),
+        _ => bail!("[WAV] {}-bits samples are not supported.", bits),
+    };
+
+    Ok(AudioClip {
+        pcm,
+        channels: channels as u8,
+        sample_rate,
+    })
+}
+
+#[inline]
+fn read_u16(v: &[u8]) -> u16 {
+    u16::from(v[0]) | (u16::from(v[1]) << 8)
+}
+
+#[inline]
+fn read_u32(v: &[u8]) -> u32 {
+    u32::from(v[0]) | (u32::from(v[1]) << 8) | (u32::from(v[2]) << 16) | (u32::from(v[3]) << 24)
+}
@@ -13,6 +13,8 @@ extern crate crayon;
 #[macro_use]
 extern crate failure;
 extern crate lewton;
+#[macro_use]
+extern crate serde_json;
 
 pub mod assets;
 pub mod source;
@@ -29,7 +31,7 @@ pub use self::inside::{discard, setup};
 
 use crayon::errors::Result;
 use crayon::math::prelude::Vector3;
-use crayon::res::prelude::ResourceState;
+use crayon::res::utils::prelude::{ResourcePoolStats, ResourceState};
 use crayon::uuid::Uuid;
 
 use self::assets::prelude::AudioClipHandle;
@@ -68,6 +70,25 @@ pub fn delete_clip(handle: AudioClipHandle) {
     ctx().delete_clip(handle);
 }
 
+/// Marks the clip as cached, which allows it to be evicted when the memory usage of
+/// clips exceeds the budget. It will be reloaded transparently when accessed again.
+#[inline]
+pub fn set_clip_cached(handle: AudioClipHandle, cached: bool) -> Result<()> {
+    ctx().set_clip_cached(handle, cached)
+}
+
+/// Sets the memory budget in bytes of clips.
+#[inline]
+pub fn set_clip_budget(budget: Option<usize>) {
+    ctx().set_clip_budget(budget);
+}
+
+/// Gets the statistics of clips.
+#[inline]
+pub fn clip_stats() -> ResourcePoolStats {
+    ctx().clip_stats()
+}
+
 /// Plays a audio source, returning a `AudioSourceHandle` for it.
 #[inline]
 pub fn play<T>(params: T) -> Result<AudioSourceHandle>
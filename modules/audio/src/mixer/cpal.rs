@@ -1,3 +1,4 @@
+use std::sync::atomic::AtomicUsize;
 use std::sync::{Arc, RwLock};
 use std::thread::Builder;
 
@@ -7,7 +8,7 @@ use crayon::errors::Result;
 use super::sampler::Sampler;
 use super::Command;
 
-pub fn run(rx: Arc<RwLock<Vec<Command>>>) -> Result<()> {
+pub fn run(rx: Arc<RwLock<Vec<Command>>>, active: Arc<AtomicUsize>) -> Result<()> {
     let device = cpal::default_output_device()
         .ok_or_else(|| format_err!("No avaiable audio output device"))?;
 
@@ -24,7 +25,7 @@ pub fn run(rx: Arc<RwLock<Vec<Command>>>) -> Result<()> {
         format
     );
 
-    let mut sampler = Sampler::new(format.channels as u8, format.sample_rate.0 as u32);
+    let mut sampler = Sampler::new(format.channels as u8, format.sample_rate.0 as u32, active);
     Builder::new()
         .name("Audio".into())
         .spawn(move || {
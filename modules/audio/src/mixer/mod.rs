@@ -6,6 +6,7 @@ mod webaudio;
 mod headless;
 mod sampler;
 
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::{Arc, RwLock};
 
 use crayon::errors::Result;
@@ -20,22 +21,25 @@ pub struct Mixer {
     sources: RwLock<HandlePool<AudioSourceHandle>>,
     tx: Arc<RwLock<Vec<Command>>>,
     clips: Arc<RwLock<ResourcePool<AudioClipHandle, AudioClipLoader>>>,
+    active: Arc<AtomicUsize>,
 }
 
 impl Mixer {
     pub fn new(clips: Arc<RwLock<ResourcePool<AudioClipHandle, AudioClipLoader>>>) -> Result<Self> {
         let tx = Arc::new(RwLock::new(Vec::new()));
+        let active = Arc::new(AtomicUsize::new(0));
 
         #[cfg(not(target_arch = "wasm32"))]
-        cpal::run(tx.clone())?;
+        cpal::run(tx.clone(), active.clone())?;
 
         #[cfg(target_arch = "wasm32")]
-        webaudio::run(tx.clone())?;
+        webaudio::run(tx.clone(), active.clone())?;
 
         Ok(Mixer {
             sources: RwLock::new(HandlePool::new()),
             tx: tx,
             clips: clips,
+            active: active,
         })
     }
 
@@ -49,6 +53,7 @@ impl Mixer {
             sources: RwLock::new(HandlePool::new()),
             tx: tx,
             clips: clips,
+            active: Arc::new(AtomicUsize::new(0)),
         })
     }
 }
@@ -73,6 +78,12 @@ impl Mixer {
         }
     }
 
+    /// Gets the number of playing sources.
+    #[inline]
+    pub fn active_sources(&self) -> usize {
+        self.active.load(Ordering::Relaxed)
+    }
+
     #[inline]
     pub fn set_listener(&self, position: Vector3<f32>) {
         let cmd = Command::SetListener(position);
@@ -102,6 +113,12 @@ impl Mixer {
         let cmd = Command::SetPosition(handle, position);
         self.tx.write().unwrap().push(cmd);
     }
+
+    #[inline]
+    pub fn set_paused(&self, paused: bool) {
+        let cmd = Command::SetPaused(paused);
+        self.tx.write().unwrap().push(cmd);
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -112,5 +129,6 @@ pub enum Command {
     SetVolume(AudioSourceHandle, f32),
     SetPitch(AudioSourceHandle, f32),
     SetPosition(AudioSourceHandle, Vector3<f32>),
+    SetPaused(bool),
     Discard,
 }
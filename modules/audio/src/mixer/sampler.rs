@@ -1,3 +1,4 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 
 use crayon::math::prelude::Vector3;
@@ -12,17 +13,22 @@ pub struct Sampler {
     sample_rate: u32,
     listener: Vector3<f32>,
     channels_iter: u8,
+    paused: bool,
     samplers: Vec<Option<AudioSourceSampler>>,
+    // The number of playing sources, which is shared with the mixer.
+    active: Arc<AtomicUsize>,
 }
 
 impl Sampler {
-    pub fn new(channels: u8, sample_rate: u32) -> Self {
+    pub fn new(channels: u8, sample_rate: u32, active: Arc<AtomicUsize>) -> Self {
         Sampler {
             channels: channels,
             sample_rate: sample_rate,
             listener: Vector3::new(0.0, 0.0, 0.0),
             channels_iter: 0,
+            paused: false,
             samplers: Vec::new(),
+            active: active,
         }
     }
 
@@ -39,6 +45,10 @@ impl Sampler {
     }
 
     pub fn sample(&mut self) -> f32 {
+        if self.paused {
+            return 0.0;
+        }
+
         let mut sum = 0.0;
         for v in &mut self.samplers {
             if let Some(ref source) = v {
@@ -50,6 +60,8 @@ impl Sampler {
 
         if self.channels_iter == 0 {
             let sample_rate = self.sample_rate;
+            let mut freed = false;
+
             for v in &mut self.samplers {
                 let free = v
                     .as_mut()
@@ -58,8 +70,13 @@ impl Sampler {
 
                 if free {
                     *v = None;
+                    freed = true;
                 }
             }
+
+            if freed {
+                self.publish();
+            }
         }
 
         sum
@@ -74,15 +91,23 @@ impl Sampler {
                 Command::SetPitch(handle, pitch) => self.set_pitch(handle, pitch),
                 Command::SetVolume(handle, volume) => self.set_volume(handle, volume),
                 Command::SetPosition(handle, emitter) => self.set_position(handle, emitter),
+                Command::SetPaused(paused) => self.paused = paused,
                 Command::Discard => {
                     return false;
                 }
             }
         }
 
+        self.publish();
         true
     }
 
+    #[inline]
+    fn publish(&self) {
+        let num = self.samplers.iter().filter(|v| v.is_some()).count();
+        self.active.store(num, Ordering::Relaxed);
+    }
+
     pub fn create_source(
         &mut self,
         handle: AudioSourceHandle,
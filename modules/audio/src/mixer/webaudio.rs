@@ -1,5 +1,6 @@
 use std::cell::RefCell;
 use std::rc::Rc;
+use std::sync::atomic::AtomicUsize;
 use std::sync::{Arc, RwLock};
 
 use crayon::errors::Result;
@@ -12,14 +13,14 @@ use super::Command;
 
 const CHANNELS: u8 = 2;
 
-pub fn run(rx: Arc<RwLock<Vec<Command>>>) -> Result<()> {
+pub fn run(rx: Arc<RwLock<Vec<Command>>>, active: Arc<AtomicUsize>) -> Result<()> {
     info!("Create web audio mixer.",);
 
     let ctx = AudioContext::new().unwrap();
 
     let closure = Rc::new(RefCell::new(None));
     let clone = closure.clone();
-    let mut sampler = Sampler::new(CHANNELS, ctx.sample_rate() as u32);
+    let mut sampler = Sampler::new(CHANNELS, ctx.sample_rate() as u32, active);
 
     let mut bufs = Vec::new();
     for _ in 0..CHANNELS {
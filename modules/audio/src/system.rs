@@ -1,9 +1,10 @@
 use std::sync::{Arc, RwLock};
 
+use crayon::application::ins::Inspectable;
 use crayon::application::prelude::{LifecycleListener, LifecycleListenerHandle};
 use crayon::errors::Result;
 use crayon::math::prelude::Vector3;
-use crayon::res::utils::prelude::{ResourcePool, ResourceState};
+use crayon::res::utils::prelude::{ResourcePool, ResourcePoolStats, ResourceState};
 use crayon::uuid::Uuid;
 
 use super::assets::prelude::{AudioClipHandle, AudioClipLoader};
@@ -14,22 +15,51 @@ use super::source::{AudioSource, AudioSourceHandle};
 pub struct AudioSystem {
     lis: LifecycleListenerHandle,
     clips: Arc<RwLock<ResourcePool<AudioClipHandle, AudioClipLoader>>>,
-    mixer: Mixer,
+    mixer: Arc<Mixer>,
 }
 
 struct AudioState {
     clips: Arc<RwLock<ResourcePool<AudioClipHandle, AudioClipLoader>>>,
+    mixer: Arc<Mixer>,
+    paused: bool,
 }
 
 impl LifecycleListener for AudioState {
     fn on_pre_update(&mut self) -> Result<()> {
         self.clips.write().unwrap().advance()?;
+
+        // Follows the pausing of game time.
+        let paused = crayon::application::paused();
+        if self.paused != paused {
+            self.paused = paused;
+            self.mixer.set_paused(paused);
+        }
+
         Ok(())
     }
 }
 
+struct AudioInspector {
+    clips: Arc<RwLock<ResourcePool<AudioClipHandle, AudioClipLoader>>>,
+    mixer: Arc<Mixer>,
+}
+
+impl Inspectable for AudioInspector {
+    fn inspect(&self) -> ::serde_json::Value {
+        let clips = self.clips.read().unwrap();
+        json!({
+            "clips": {
+                "stats": clips.stats(),
+                "resources": clips.infos(),
+            },
+            "active_sources": self.mixer.active_sources(),
+        })
+    }
+}
+
 impl Drop for AudioSystem {
     fn drop(&mut self) {
+        crayon::application::detach_inspectable("audio");
         crayon::application::detach(self.lis);
     }
 }
@@ -37,16 +67,26 @@ impl Drop for AudioSystem {
 impl AudioSystem {
     pub fn new() -> Result<Self> {
         let clips = Arc::new(RwLock::new(ResourcePool::new(AudioClipLoader::new())));
-        let mixer = if crayon::application::headless() {
+        let mixer = Arc::new(if crayon::application::headless() {
             Mixer::headless(clips.clone())?
         } else {
             Mixer::new(clips.clone())?
-        };
+        });
 
         let state = AudioState {
             clips: clips.clone(),
+            mixer: mixer.clone(),
+            paused: false,
         };
 
+        crayon::application::attach_inspectable(
+            "audio",
+            Arc::new(AudioInspector {
+                clips: clips.clone(),
+                mixer: mixer.clone(),
+            }),
+        );
+
         Ok(AudioSystem {
             lis: crayon::application::attach(state),
             clips: clips,
@@ -86,6 +126,25 @@ impl AudioSystem {
         self.clips.write().unwrap().delete(handle);
     }
 
+    /// Marks the clip as cached, which allows it to be evicted when the memory usage of
+    /// clips exceeds the budget. It will be reloaded transparently when accessed again.
+    #[inline]
+    pub fn set_clip_cached(&self, handle: AudioClipHandle, cached: bool) -> Result<()> {
+        self.clips.write().unwrap().set_cached(handle, cached)
+    }
+
+    /// Sets the memory budget in bytes of clips.
+    #[inline]
+    pub fn set_clip_budget(&self, budget: Option<usize>) {
+        self.clips.write().unwrap().set_budget(budget);
+    }
+
+    /// Gets the statistics of clips.
+    #[inline]
+    pub fn clip_stats(&self) -> ResourcePoolStats {
+        self.clips.read().unwrap().stats()
+    }
+
     /// Plays a audio source, returning a `AudioSourceHandle` for it.
     #[inline]
     pub fn play<T>(&self, params: T) -> Result<AudioSourceHandle>
@@ -0,0 +1,822 @@
+//! Imports glTF 2.0 scenes (.gltf and .glb) into `Prefab`.
+//!
+//! The node hierarchy of the default scene is converted into `PrefabNode`s under a synthetic
+//! root node. Every primitive is converted into a standalone mesh, with a `VertexLayout` made
+//! of the attributes it provides (positions, normals, tangents, texture coordinates, colors,
+//! joints and weights). If a mesh has more than one primitive, a child node is created for each
+//! of them.
+//!
+//! glTF uses a right-handed coordinate system, so the Z axis is mirrored when importing into our
+//! left-handed world, and the winding order of triangles is reversed accordingly. Triangle strips
+//! are converted into lists for that. Notes that non-uniform scales are not supported by
+//! `Transform` and will be averaged.
+
+use std::collections::BTreeMap;
+
+use crayon::errors::Result;
+use crayon::math::prelude::{Aabb3, InnerSpace, Matrix3, Quaternion, Vector3};
+use crayon::video::assets::shader::Attribute;
+use crayon::video::prelude::{
+    IndexFormat, MeshData, MeshParams, MeshPrimitive, VertexFormat, VertexLayout,
+};
+
+use serde_json;
+
+use spatial::prelude::Transform;
+
+use super::prefab::{Prefab, PrefabMaterial, PrefabNode};
+
+const GLB_MAGIC: &[u8] = b"glTF";
+const GLB_CHUNK_JSON: u32 = 0x4E4F_534A;
+const GLB_CHUNK_BIN: u32 = 0x004E_4942;
+// The maximum number of values of an accessor without buffer view.
+const MAX_ZEROED_VALUES: usize = 1 << 24;
+
+/// The result of importing a glTF scene.
+pub struct GltfImport {
+    /// The prefab. `PrefabNode::mesh_renderer` indexes into `meshes`, and
+    /// `PrefabMaterial::base_color_texture` indexes into `images`.
+    pub prefab: Prefab,
+    /// The meshes converted from primitives.
+    pub meshes: Vec<(MeshParams, MeshData)>,
+    /// The encoded (PNG or JPEG) images used by materials. Its `None` if the image could
+    /// not be resolved.
+    pub images: Vec<Option<Vec<u8>>>,
+}
+
+/// Checks if the bytes looks like a glTF (JSON or binary) file.
+pub fn is_gltf(bytes: &[u8]) -> bool {
+    if bytes.starts_with(GLB_MAGIC) {
+        return true;
+    }
+
+    bytes
+        .iter()
+        .find(|v| !v.is_ascii_whitespace())
+        .map(|&v| v == b'{')
+        .unwrap_or(false)
+}
+
+/// Imports the glTF scene from `bytes`. External resources (e.g. "scene.bin") are loaded with
+/// `resolve`, which receives the URI as it is.
+pub fn import<F>(bytes: &[u8], mut resolve: F) -> Result<GltfImport>
+where
+    F: FnMut(&str) -> Result<Vec<u8>>,
+{
+    let (json, bin) = if bytes.starts_with(GLB_MAGIC) {
+        parse_glb(bytes)?
+    } else {
+        (bytes, None)
+    };
+
+    let doc: Document = serde_json::from_slice(json)?;
+
+    let mut buffers = Vec::with_capacity(doc.buffers.len());
+    for (i, v) in doc.buffers.iter().enumerate() {
+        let buf = match v.uri.as_ref() {
+            Some(uri) => load_uri(uri, &mut resolve)?,
+            None if i == 0 && bin.is_some() => bin.unwrap().to_vec(),
+            None => bail!("[glTF] The uri of buffer {} is missing.", i),
+        };
+
+        if buf.len() < v.byte_length {
+            bail!("[glTF] Buffer {} is too small.", i);
+        }
+
+        buffers.push(buf);
+    }
+
+    let mut importer = Importer {
+        doc: &doc,
+        buffers: &buffers,
+        prefab: Prefab::default(),
+        meshes: Vec::new(),
+        images: Vec::new(),
+        primitives: BTreeMap::new(),
+        textures: BTreeMap::new(),
+    };
+
+    for i in 0..doc.materials.len() {
+        let material = importer.material(i, &mut resolve)?;
+        importer.prefab.materials.push(material);
+    }
+
+    let roots = match doc
+        .scene
+        .or(if doc.scenes.is_empty() { None } else { Some(0) })
+    {
+        Some(scene) => doc
+            .scenes
+            .get(scene)
+            .ok_or_else(|| format_err!("[glTF] Scene {} is missing.", scene))?
+            .nodes
+            .clone(),
+        None => (0..doc.nodes.len()).collect(),
+    };
+
+    importer.prefab.nodes.push(PrefabNode {
+        name: "root".to_owned(),
+        local_transform: Transform::default(),
+        first_child: None,
+        next_sib: None,
+        mesh_renderer: None,
+        material: None,
+    });
+
+    let mut children = Vec::new();
+    for v in roots {
+        children.push(importer.node(v, 0)?);
+    }
+
+    importer.link(0, &children);
+
+    Ok(GltfImport {
+        prefab: importer.prefab,
+        meshes: importer.meshes,
+        images: importer.images,
+    })
+}
+
+fn parse_glb(bytes: &[u8]) -> Result<(&[u8], Option<&[u8]>)> {
+    if bytes.len() < 20 || read_u32(bytes, 4) != 2 {
+        bail!("[glTF] Only GLB version 2 is supported.");
+    }
+
+    let len = (read_u32(bytes, 8) as usize).min(bytes.len());
+    let mut iter = 12;
+    let mut json = None;
+    let mut bin = None;
+
+    while iter + 8 <= len {
+        let chunk_len = read_u32(bytes, iter) as usize;
+        let chunk_type = read_u32(bytes, iter + 4);
+        let start = iter + 8;
+
+        if start + chunk_len > len {
+            bail!("[glTF] Unexpected end of GLB.");
+        }
+
+        match chunk_type {
+            GLB_CHUNK_JSON => json = Some(&bytes[start..start + chunk_len]),
+            GLB_CHUNK_BIN => bin = Some(&bytes[start..start + chunk_len]),
+            _ => {}
+        }
+
+        iter = start + chunk_len;
+    }
+
+    let json = json.ok_or_else(|| format_err!("[glTF] JSON chunk of GLB is missing."))?;
+    Ok((json, bin))
+}
+
+fn load_uri<F>(uri: &str, resolve: &mut F) -> Result<Vec<u8>>
+where
+    F: FnMut(&str) -> Result<Vec<u8>>,
+{
+    if uri.starts_with("data:") {
+        let index = uri
+            .find(";base64,")
+            .ok_or_else(|| format_err!("[glTF] Only base64 data uri is supported."))?;
+
+        decode_base64(&uri[index + 8..])
+    } else {
+        resolve(uri)
+    }
+}
+
+struct Importer<'a> {
+    doc: &'a Document,
+    buffers: &'a [Vec<u8>],
+    prefab: Prefab,
+    meshes: Vec<(MeshParams, MeshData)>,
+    images: Vec<Option<Vec<u8>>>,
+    primitives: BTreeMap<(usize, usize), usize>,
+    textures: BTreeMap<usize, usize>,
+}
+
+impl<'a> Importer<'a> {
+    fn node(&mut self, index: usize, depth: usize) -> Result<usize> {
+        if depth > 256 {
+            bail!("[glTF] The node hierarchy is too deep (or has cycles).");
+        }
+
+        let doc = self.doc;
+        let node = doc
+            .nodes
+            .get(index)
+            .ok_or_else(|| format_err!("[glTF] Node {} is missing.", index))?;
+
+        let name = node
+            .name
+            .clone()
+            .unwrap_or_else(|| format!("node{}", index));
+
+        let current = self.prefab.nodes.len();
+        self.prefab.nodes.push(PrefabNode {
+            name: name.clone(),
+            local_transform: node.transform(),
+            first_child: None,
+            next_sib: None,
+            mesh_renderer: None,
+            material: None,
+        });
+
+        let mut children = Vec::new();
+
+        if let Some(mesh) = node.mesh {
+            let primitives = doc
+                .meshes
+                .get(mesh)
+                .ok_or_else(|| format_err!("[glTF] Mesh {} is missing.", mesh))?
+                .primitives
+                .len();
+
+            for i in 0..primitives {
+                let (mesh_index, material) = self.primitive(mesh, i)?;
+
+                if primitives == 1 {
+                    self.prefab.nodes[current].mesh_renderer = Some(mesh_index);
+                    self.prefab.nodes[current].material = material;
+                } else {
+                    children.push(self.prefab.nodes.len());
+                    self.prefab.nodes.push(PrefabNode {
+                        name: format!("{}#{}", name, i),
+                        local_transform: Transform::default(),
+                        first_child: None,
+                        next_sib: None,
+                        mesh_renderer: Some(mesh_index),
+                        material,
+                    });
+                }
+            }
+        }
+
+        for &v in &node.children {
+            children.push(self.node(v, depth + 1)?);
+        }
+
+        self.link(current, &children);
+        Ok(current)
+    }
+
+    fn link(&mut self, parent: usize, children: &[usize]) {
+        self.prefab.nodes[parent].first_child = children.first().cloned();
+        for v in children.windows(2) {
+            self.prefab.nodes[v[0]].next_sib = Some(v[1]);
+        }
+    }
+
+    fn material<F>(&mut self, index: usize, resolve: &mut F) -> Result<PrefabMaterial>
+    where
+        F: FnMut(&str) -> Result<Vec<u8>>,
+    {
+        let doc = self.doc;
+        let v = &doc.materials[index];
+        let mut material = PrefabMaterial::default();
+
+        if let Some(pbr) = v.pbr_metallic_roughness.as_ref() {
+            if let Some(color) = pbr.base_color_factor {
+                material.base_color = color;
+            }
+
+            material.metallic = pbr.metallic_factor.unwrap_or(1.0);
+            material.roughness = pbr.roughness_factor.unwrap_or(1.0);
+
+            if let Some(texture) = pbr.base_color_texture.as_ref() {
+                material.base_color_texture = self.texture(texture.index, resolve)?;
+            }
+        }
+
+        if let Some(emissive) = v.emissive_factor {
+            material.emissive = emissive;
+        }
+
+        Ok(material)
+    }
+
+    fn texture<F>(&mut self, index: usize, resolve: &mut F) -> Result<Option<usize>>
+    where
+        F: FnMut(&str) -> Result<Vec<u8>>,
+    {
+        let doc = self.doc;
+        let source = match doc.textures.get(index).and_then(|v| v.source) {
+            Some(source) => source,
+            None => return Ok(None),
+        };
+
+        if let Some(&v) = self.textures.get(&source) {
+            return Ok(Some(v));
+        }
+
+        let image = doc
+            .images
+            .get(source)
+            .ok_or_else(|| format_err!("[glTF] Image {} is missing.", source))?;
+
+        let bytes = if let Some(view) = image.buffer_view {
+            Some(self.view(view)?.to_vec())
+        } else if let Some(uri) = image.uri.as_ref() {
+            match load_uri(uri, resolve) {
+                Ok(bytes) => Some(bytes),
+                Err(err) => {
+                    warn!("[glTF] Failed to load image {}. {}", uri, err);
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
+        let slot = self.images.len();
+        self.images.push(bytes);
+        self.textures.insert(source, slot);
+        Ok(Some(slot))
+    }
+
+    fn primitive(&mut self, mesh: usize, index: usize) -> Result<(usize, Option<usize>)> {
+        let doc = self.doc;
+        let primitive = &doc.meshes[mesh].primitives[index];
+        if let Some(&v) = self.primitives.get(&(mesh, index)) {
+            return Ok((v, primitive.material));
+        }
+
+        let position = *primitive
+            .attributes
+            .get("POSITION")
+            .ok_or_else(|| format_err!("[glTF] POSITION of mesh {} is missing.", mesh))?;
+
+        let num_verts = self.accessor(position)?.count;
+
+        // (attribute, format, size, normalized, values)
+        let mut streams: Vec<(Attribute, VertexFormat, u8, bool, Vec<f32>)> = Vec::new();
+        let semantics = [
+            ("POSITION", Attribute::Position, 3),
+            ("NORMAL", Attribute::Normal, 3),
+            ("TANGENT", Attribute::Tangent, 4),
+            ("TEXCOORD_0", Attribute::Texcoord0, 2),
+            ("TEXCOORD_1", Attribute::Texcoord1, 2),
+            ("COLOR_0", Attribute::Color0, 4),
+            ("JOINTS_0", Attribute::Indices, 4),
+            ("WEIGHTS_0", Attribute::Weight, 4),
+        ];
+
+        for &(semantic, attribute, size) in &semantics {
+            if let Some(&accessor) = primitive.attributes.get(semantic) {
+                let (mut values, components) = self.read(accessor)?;
+                if self.accessor(accessor)?.count != num_verts {
+                    bail!("[glTF] The count of {} mismatched.", semantic);
+                }
+
+                // Pads RGB colors with alpha.
+                if components == 3 && size == 4 {
+                    values = values
+                        .chunks(3)
+                        .flat_map(|v| vec![v[0], v[1], v[2], 1.0])
+                        .collect();
+                } else if components != size {
+                    bail!("[glTF] The type of {} is not supported.", semantic);
+                }
+
+                // Mirrors the Z axis.
+                if attribute == Attribute::Position || attribute == Attribute::Normal {
+                    for v in values.chunks_mut(3) {
+                        v[2] = -v[2];
+                    }
+                } else if attribute == Attribute::Tangent {
+                    // The handedness of bitangent is flipped by mirroring too.
+                    for v in values.chunks_mut(4) {
+                        v[2] = -v[2];
+                        v[3] = -v[3];
+                    }
+                }
+
+                let format = if attribute == Attribute::Indices {
+                    VertexFormat::UShort
+                } else {
+                    VertexFormat::Float
+                };
+
+                streams.push((attribute, format, size as u8, false, values));
+            }
+        }
+
+        let mut layout = VertexLayout::build();
+        for v in &streams {
+            layout = layout.with(v.0, v.1, v.2, v.3);
+        }
+
+        let mut params = MeshParams::default();
+        params.layout = layout.finish();
+        params.num_verts = num_verts;
+        params.primitive = match primitive.mode {
+            0 => MeshPrimitive::Points,
+            1 => MeshPrimitive::Lines,
+            3 => MeshPrimitive::LineStrip,
+            4 => MeshPrimitive::Triangles,
+            5 => MeshPrimitive::TriangleStrip,
+            v => bail!("[glTF] Primitive mode {} is not supported.", v),
+        };
+
+        let mut vptr = Vec::with_capacity(params.vertex_buffer_len());
+        for i in 0..num_verts {
+            for v in &streams {
+                let size = v.2 as usize;
+                let values = &v.4[i * size..(i + 1) * size];
+
+                if v.1 == VertexFormat::UShort {
+                    let joints: Vec<u16> = values.iter().map(|&v| v as u16).collect();
+                    vptr.extend_from_slice(IndexFormat::encode(&joints));
+                } else {
+                    vptr.extend_from_slice(IndexFormat::encode(values));
+                }
+            }
+        }
+
+        let mut idxes: Vec<u32> = match primitive.indices {
+            Some(accessor) => self.read(accessor)?.0.iter().map(|&v| v as u32).collect(),
+            None => (0..num_verts as u32).collect(),
+        };
+
+        if idxes.iter().any(|&v| v as usize >= num_verts) {
+            bail!("[glTF] Index out of bounds in mesh {}.", mesh);
+        }
+
+        // Converts strips into lists, so the winding order could be reversed uniformly.
+        if params.primitive == MeshPrimitive::TriangleStrip {
+            idxes = (2..idxes.len())
+                .flat_map(|i| {
+                    if i % 2 == 0 {
+                        vec![idxes[i - 2], idxes[i - 1], idxes[i]]
+                    } else {
+                        vec![idxes[i - 1], idxes[i - 2], idxes[i]]
+                    }
+                })
+                .collect();
+
+            params.primitive = MeshPrimitive::Triangles;
+        }
+
+        // Reverses the winding order since the Z axis is mirrored.
+        if params.primitive == MeshPrimitive::Triangles {
+            for v in idxes.chunks_mut(3) {
+                if v.len() == 3 {
+                    v.swap(1, 2);
+                }
+            }
+        }
+
+        params.num_idxes = idxes.len();
+        let iptr: Box<[u8]> = if num_verts <= ::std::u16::MAX as usize {
+            params.index_format = IndexFormat::U16;
+            let idxes: Vec<u16> = idxes.iter().map(|&v| v as u16).collect();
+            IndexFormat::encode(&idxes).into()
+        } else {
+            params.index_format = IndexFormat::U32;
+            IndexFormat::encode(&idxes).into()
+        };
+
+        params.aabb = aabb(&streams[0].4);
+
+        let data = MeshData {
+            vptr: vptr.into_boxed_slice(),
+            iptr,
+        };
+
+        params.validate(Some(&data))?;
+
+        let slot = self.meshes.len();
+        self.meshes.push((params, data));
+        self.primitives.insert((mesh, index), slot);
+        Ok((slot, primitive.material))
+    }
+
+    fn accessor(&self, index: usize) -> Result<&'a Accessor> {
+        self.doc
+            .accessors
+            .get(index)
+            .ok_or_else(|| format_err!("[glTF] Accessor {} is missing.", index))
+    }
+
+    fn view(&self, index: usize) -> Result<&'a [u8]> {
+        let view = self
+            .doc
+            .buffer_views
+            .get(index)
+            .ok_or_else(|| format_err!("[glTF] BufferView {} is missing.", index))?;
+
+        let buffer = self
+            .buffers
+            .get(view.buffer)
+            .ok_or_else(|| format_err!("[glTF] Buffer {} is missing.", view.buffer))?;
+
+        view.byte_offset
+            .checked_add(view.byte_length)
+            .and_then(|end| buffer.get(view.byte_offset..end))
+            .ok_or_else(|| format_err!("[glTF] BufferView {} is out of bounds.", index))
+    }
+
+    /// Reads the accessor into floats, returns the values and the number of components.
+    fn read(&self, index: usize) -> Result<(Vec<f32>, usize)> {
+        let accessor = self.accessor(index)?;
+
+        let components = match accessor.ty.as_str() {
+            "SCALAR" => 1,
+            "VEC2" => 2,
+            "VEC3" => 3,
+            "VEC4" => 4,
+            "MAT2" => 4,
+            "MAT3" => 9,
+            "MAT4" => 16,
+            v => bail!("[glTF] Accessor type {} is not supported.", v),
+        };
+
+        let size = match accessor.component_type {
+            5120 | 5121 => 1,
+            5122 | 5123 => 2,
+            5125 | 5126 => 4,
+            v => bail!("[glTF] Component type {} is not supported.", v),
+        };
+
+        let len = accessor
+            .count
+            .checked_mul(components)
+            .ok_or_else(|| format_err!("[glTF] Accessor {} is too large.", index))?;
+
+        let view_index = match accessor.buffer_view {
+            Some(v) => v,
+            None => {
+                // The values are zeros without buffer view, which should be small enough.
+                if len > MAX_ZEROED_VALUES {
+                    bail!("[glTF] Accessor {} is too large.", index);
+                }
+
+                return Ok((vec![0.0; len], components));
+            }
+        };
+
+        let view = self.view(view_index)?;
+        let stride = self.doc.buffer_views[view_index]
+            .byte_stride
+            .unwrap_or(size * components);
+
+        // Makes sure that the last element fits in the view before allocating, the offsets
+        // of elements could not overflow after that.
+        if accessor.count > 0 {
+            let end = (accessor.count - 1)
+                .checked_mul(stride)
+                .and_then(|v| v.checked_add(accessor.byte_offset))
+                .and_then(|v| v.checked_add(size * components));
+
+            if end.map_or(true, |v| v > view.len()) {
+                bail!("[glTF] Accessor {} is out of bounds.", index);
+            }
+        }
+
+        let mut values = Vec::with_capacity(len);
+        for i in 0..accessor.count {
+            for j in 0..components {
+                let offset = accessor.byte_offset + i * stride + j * size;
+                let bytes = view
+                    .get(offset..offset + size)
+                    .ok_or_else(|| format_err!("[glTF] Accessor {} is out of bounds.", index))?;
+
+                let n = accessor.normalized;
+                let v = match accessor.component_type {
+                    5120 if n => (f32::from(bytes[0] as i8) / 127.0).max(-1.0),
+                    5120 => f32::from(bytes[0] as i8),
+                    5121 if n => f32::from(bytes[0]) / 255.0,
+                    5121 => f32::from(bytes[0]),
+                    5122 if n => (f32::from(read_u16(bytes, 0) as i16) / 32767.0).max(-1.0),
+                    5122 => f32::from(read_u16(bytes, 0) as i16),
+                    5123 if n => f32::from(read_u16(bytes, 0)) / 65535.0,
+                    5123 => f32::from(read_u16(bytes, 0)),
+                    5125 => read_u32(bytes, 0) as f32,
+                    _ => f32::from_bits(read_u32(bytes, 0)),
+                };
+
+                values.push(v);
+            }
+        }
+
+        Ok((values, components))
+    }
+}
+
+fn aabb(positions: &[f32]) -> Aabb3<f32> {
+    let mut aabb = Aabb3::zero();
+
+    for (i, v) in positions.chunks(3).enumerate() {
+        if i == 0 {
+            aabb.min.x = v[0];
+            aabb.min.y = v[1];
+            aabb.min.z = v[2];
+            aabb.max = aabb.min;
+        } else {
+            aabb.min.x = aabb.min.x.min(v[0]);
+            aabb.min.y = aabb.min.y.min(v[1]);
+            aabb.min.z = aabb.min.z.min(v[2]);
+            aabb.max.x = aabb.max.x.max(v[0]);
+            aabb.max.y = aabb.max.y.max(v[1]);
+            aabb.max.z = aabb.max.z.max(v[2]);
+        }
+    }
+
+    aabb
+}
+
+#[inline]
+fn read_u16(bytes: &[u8], offset: usize) -> u16 {
+    u16::from(bytes[offset]) | (u16::from(bytes[offset + 1]) << 8)
+}
+
+#[inline]
+fn read_u32(bytes: &[u8], offset: usize) -> u32 {
+    u32::from(read_u16(bytes, offset)) | (u32::from(read_u16(bytes, offset + 2)) << 16)
+}
+
+fn decode_base64(input: &str) -> Result<Vec<u8>> {
+    let mut bytes = Vec::with_capacity(input.len() * 3 / 4);
+    let mut acc = 0u32;
+    let mut bits = 0;
+
+    for c in input.bytes() {
+        let v = match c {
+            b'A'..=b'Z' => c - b'A',
+            b'a'..=b'z' => c - b'a' + 26,
+            b'0'..=b'9' => c - b'0' + 52,
+            b'+' | b'-' => 62,
+            b'/' | b'_' => 63,
+            b'=' => break,
+            c if c.is_ascii_whitespace() => continue,
+            _ => bail!("[glTF] Invalid base64 data uri."),
+        };
+
+        acc = (acc << 6) | u32::from(v);
+        bits += 6;
+
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((acc >> bits) as u8);
+        }
+    }
+
+    Ok(bytes)
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Document {
+    scene: Option<usize>,
+    #[serde(default)]
+    scenes: Vec<Scene>,
+    #[serde(default)]
+    nodes: Vec<Node>,
+    #[serde(default)]
+    meshes: Vec<Mesh>,
+    #[serde(default)]
+    accessors: Vec<Accessor>,
+    #[serde(default)]
+    buffer_views: Vec<BufferView>,
+    #[serde(default)]
+    buffers: Vec<Buffer>,
+    #[serde(default)]
+    materials: Vec<Material>,
+    #[serde(default)]
+    textures: Vec<Texture>,
+    #[serde(default)]
+    images: Vec<Image>,
+}
+
+#[derive(Deserialize)]
+struct Scene {
+    #[serde(default)]
+    nodes: Vec<usize>,
+}
+
+#[derive(Deserialize)]
+struct Node {
+    name: Option<String>,
+    #[serde(default)]
+    children: Vec<usize>,
+    mesh: Option<usize>,
+    matrix: Option<[f32; 16]>,
+    translation: Option<[f32; 3]>,
+    rotation: Option<[f32; 4]>,
+    scale: Option<[f32; 3]>,
+}
+
+impl Node {
+    fn transform(&self) -> Transform {
+        let (position, rotation, scale) = if let Some(m) = self.matrix {
+            // Column-major, decomposes into TRS.
+            let sx = Vector3::new(m[0], m[1], m[2]);
+            let sy = Vector3::new(m[4], m[5], m[6]);
+            let sz = Vector3::new(m[8], m[9], m[10]);
+
+            let scale = [sx.magnitude(), sy.magnitude(), sz.magnitude()];
+            let rotation = Quaternion::from(Matrix3::from_cols(
+                sx / scale[0].max(::std::f32::EPSILON),
+                sy / scale[1].max(::std::f32::EPSILON),
+                sz / scale[2].max(::std::f32::EPSILON),
+            ));
+
+            ([m[12], m[13], m[14]], rotation, scale)
+        } else {
+            let r = self.rotation.unwrap_or([0.0, 0.0, 0.0, 1.0]);
+            (
+                self.translation.unwrap_or([0.0, 0.0, 0.0]),
+                Quaternion::new(r[3], r[0], r[1], r[2]),
+                self.scale.unwrap_or([1.0, 1.0, 1.0]),
+            )
+        };
+
+        // Mirrors the Z axis.
+        Transform {
+            position: Vector3::new(position[0], position[1], -position[2]),
+            rotation: Quaternion::new(rotation.s, -rotation.v.x, -rotation.v.y, rotation.v.z),
+            scale: (scale[0] + scale[1] + scale[2]) / 3.0,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct Mesh {
+    primitives: Vec<Primitive>,
+}
+
+fn default_mode() -> u32 {
+    4
+}
+
+#[derive(Deserialize)]
+struct Primitive {
+    attributes: BTreeMap<String, usize>,
+    indices: Option<usize>,
+    material: Option<usize>,
+    #[serde(default = "default_mode")]
+    mode: u32,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Accessor {
+    buffer_view: Option<usize>,
+    #[serde(default)]
+    byte_offset: usize,
+    component_type: u32,
+    #[serde(default)]
+    normalized: bool,
+    count: usize,
+    #[serde(rename = "type")]
+    ty: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BufferView {
+    buffer: usize,
+    #[serde(default)]
+    byte_offset: usize,
+    byte_length: usize,
+    byte_stride: Option<usize>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Buffer {
+    uri: Option<String>,
+    byte_length: usize,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Material {
+    pbr_metallic_roughness: Option<PbrMetallicRoughness>,
+    emissive_factor: Option<[f32; 3]>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PbrMetallicRoughness {
+    base_color_factor: Option<[f32; 4]>,
+    base_color_texture: Option<TextureInfo>,
+    metallic_factor: Option<f32>,
+    roughness_factor: Option<f32>,
+}
+
+#[derive(Deserialize)]
+struct TextureInfo {
+    index: usize,
+}
+
+#[derive(Deserialize)]
+struct Texture {
+    source: Option<usize>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Image {
+    uri: Option<String>,
+    buffer_view: Option<usize>,
+}
@@ -1,3 +1,4 @@
+pub mod gltf;
 pub mod prefab;
 pub mod prefab_loader;
 
@@ -5,6 +6,6 @@ pub mod mesh_builder;
 pub mod texture_builder;
 
 pub mod prelude {
-    pub use super::prefab::{Prefab, PrefabHandle};
+    pub use super::prefab::{Prefab, PrefabHandle, PrefabMaterial};
     pub use super::prefab_loader::PrefabLoader;
 }
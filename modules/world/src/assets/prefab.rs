@@ -1,8 +1,13 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
 use crayon::errors::*;
-use crayon::res::utils::prelude::ResourceState;
+use crayon::res::utils::prelude::{poll_resource, ResourceHandle, ResourceState};
 use crayon::sched::prelude::LatchProbe;
 use crayon::uuid::Uuid;
 use crayon::video::assets::mesh::MeshHandle;
+use crayon::video::assets::texture::TextureHandle;
 
 use spatial::prelude::Transform;
 
@@ -11,14 +16,18 @@ impl_handle!(PrefabHandle);
 /// A prefab asset acts as a template from which you can create new
 /// entity instances in the world. It stores a entity and its children
 /// complete with components and properties internally.
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
 pub struct Prefab {
     ///
     pub nodes: Vec<PrefabNode>,
+    pub materials: Vec<PrefabMaterial>,
     pub universe_meshes: Vec<Uuid>,
+    pub universe_textures: Vec<Uuid>,
 
     #[serde(skip)]
     pub meshes: Vec<MeshHandle>,
+    #[serde(skip)]
+    pub textures: Vec<TextureHandle>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -33,6 +42,36 @@ pub struct PrefabNode {
     pub next_sib: Option<usize>,
     /// The optional mesh renderer.
     pub mesh_renderer: Option<usize>,
+    /// The optional material index.
+    pub material: Option<usize>,
+}
+
+/// The metallic-roughness material which is converted into renderer specific material
+/// when instantiating.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+pub struct PrefabMaterial {
+    /// The base color in RGBA.
+    pub base_color: [f32; 4],
+    /// The base color texture index.
+    pub base_color_texture: Option<usize>,
+    /// The metalness of the material.
+    pub metallic: f32,
+    /// The roughness of the material.
+    pub roughness: f32,
+    /// The emissive color in RGB.
+    pub emissive: [f32; 3],
+}
+
+impl Default for PrefabMaterial {
+    fn default() -> Self {
+        PrefabMaterial {
+            base_color: [1.0, 1.0, 1.0, 1.0],
+            base_color_texture: None,
+            metallic: 1.0,
+            roughness: 1.0,
+            emissive: [0.0, 0.0, 0.0],
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Copy)]
@@ -58,3 +97,28 @@ impl LatchProbe for PrefabHandle {
         ResourceState::NotReady != crate::prefab_state(*self)
     }
 }
+
+impl ResourceHandle for PrefabHandle {
+    #[inline]
+    fn create_from_uuid(uuid: Uuid) -> Result<Self> {
+        crate::create_prefab_from_uuid(uuid)
+    }
+
+    #[inline]
+    fn state(self) -> ResourceState {
+        crate::prefab_state(self)
+    }
+
+    #[inline]
+    fn delete(self) {
+        crate::delete_prefab(self)
+    }
+}
+
+impl Future for PrefabHandle {
+    type Output = Result<Self>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        poll_resource(*self, cx)
+    }
+}
@@ -5,12 +5,18 @@ use crayon::errors::Result;
 use crayon::res::utils::prelude::ResourceLoader;
 use crayon::{bincode, video};
 
+use super::gltf;
 use super::prefab::*;
 
 pub const MAGIC: [u8; 8] = [
-    'P' as u8, 'R' as u8, 'E' as u8, 'B' as u8, ' ' as u8, 0, 0, 1,
+    'P' as u8, 'R' as u8, 'E' as u8, 'B' as u8, ' ' as u8, 0, 0, 2,
 ];
 
+/// Loads prefabs serialized by `crayon-tools`, or glTF scenes directly.
+///
+/// Notes that the glTF scenes loaded at runtime have no textures, since the images could
+/// not be decoded without the offline importer. Materials fall back to their base colors,
+/// so import the scenes with `crayon-tools` if textures are required.
 #[derive(Clone)]
 pub struct PrefabLoader {}
 
@@ -26,23 +32,18 @@ impl ResourceLoader for PrefabLoader {
     type Resource = Arc<Prefab>;
 
     fn load(&self, handle: Self::Handle, bytes: &[u8]) -> Result<Self::Intermediate> {
-        if &bytes[0..8] != &MAGIC[..] {
-            bail!("[PrefabLoader] MAGIC number not match.");
-        }
-
-        let mut file = Cursor::new(&bytes[8..]);
-        let mut prefab: Prefab = bincode::deserialize_from(&mut file)?;
-
-        for &v in &prefab.universe_meshes {
-            let mesh = video::create_mesh_from_uuid(v)?;
-            prefab.meshes.push(mesh);
-        }
+        let prefab = if gltf::is_gltf(bytes) {
+            load_gltf(bytes)?
+        } else {
+            load_prefab(bytes)?
+        };
 
         info!(
-            "[PrefabLoader] load {:?}. (Nodes: {}, Meshes: {})",
+            "[PrefabLoader] load {:?}. (Nodes: {}, Meshes: {}, Textures: {})",
             handle,
             prefab.nodes.len(),
-            prefab.meshes.len()
+            prefab.meshes.len(),
+            prefab.textures.len()
         );
 
         Ok(prefab)
@@ -58,5 +59,50 @@ impl ResourceLoader for PrefabLoader {
         for &v in &prefab.meshes {
             video::delete_mesh(v);
         }
+
+        for &v in &prefab.textures {
+            video::delete_texture(v);
+        }
+    }
+}
+
+fn load_prefab(bytes: &[u8]) -> Result<Prefab> {
+    if bytes.len() < 8 || &bytes[0..8] != &MAGIC[..] {
+        bail!("[PrefabLoader] MAGIC number not match.");
+    }
+
+    let mut file = Cursor::new(&bytes[8..]);
+    let mut prefab: Prefab = bincode::deserialize_from(&mut file)?;
+
+    for &v in &prefab.universe_meshes {
+        let mesh = video::create_mesh_from_uuid(v)?;
+        prefab.meshes.push(mesh);
+    }
+
+    for &v in &prefab.universe_textures {
+        let texture = video::create_texture_from_uuid(v)?;
+        prefab.textures.push(texture);
     }
+
+    Ok(prefab)
+}
+
+// Loads the glTF scene at runtime. Only self-contained files (.glb or .gltf with embedded
+// data uri) are supported, and images are ignored since they can't be decoded at runtime.
+fn load_gltf(bytes: &[u8]) -> Result<Prefab> {
+    let scene = gltf::import(bytes, |uri| {
+        bail!("[PrefabLoader] External resource {} is not supported at runtime.", uri)
+    })?;
+
+    if !scene.images.is_empty() {
+        warn!("[PrefabLoader] Images of glTF are ignored at runtime.");
+    }
+
+    let mut prefab = scene.prefab;
+    for (params, data) in scene.meshes {
+        let mesh = video::create_mesh(params, Some(data))?;
+        prefab.meshes.push(mesh);
+    }
+
+    Ok(prefab)
 }
@@ -6,6 +6,8 @@ extern crate failure;
 extern crate serde;
 
 extern crate inlinable_string;
+#[macro_use]
+extern crate serde_json;
 
 pub mod assets;
 pub mod renderable;
@@ -17,7 +19,7 @@ pub mod utils;
 pub mod prelude {
     pub use super::assets::prelude::*;
     pub use super::renderable::prelude::*;
-    pub use super::scene::Scene;
+    pub use super::scene::{Scene, SceneStats};
     pub use super::spatial::prelude::*;
     pub use super::Entity;
 }
@@ -28,6 +30,7 @@ pub use self::inside::{discard, setup};
 pub use self::system::WorldDefaultResources;
 
 use crayon::res::utils::prelude::ResourceState;
+use crayon::uuid::Uuid;
 use std::sync::Arc;
 
 use self::assets::prelude::{Prefab, PrefabHandle};
@@ -57,6 +60,12 @@ pub fn create_prefab_from<T: AsRef<str>>(url: T) -> Result<PrefabHandle> {
     ctx().create_prefab_from(url)
 }
 
+/// Create a prefab object from file asynchronously.
+#[inline]
+pub fn create_prefab_from_uuid(uuid: Uuid) -> Result<PrefabHandle> {
+    ctx().create_prefab_from_uuid(uuid)
+}
+
 /// Return the prefab obejct if exists.
 #[inline]
 pub fn prefab(handle: PrefabHandle) -> Option<Arc<Prefab>> {
@@ -98,6 +107,11 @@ mod inside {
         }
     }
 
+    #[inline]
+    pub fn valid() -> bool {
+        unsafe { !CTX.is_null() }
+    }
+
     /// Setup the world system.
     pub fn setup() -> Result<(), failure::Error> {
         unsafe {
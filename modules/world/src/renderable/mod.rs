@@ -13,6 +13,7 @@ pub mod prelude {
     pub use super::{Renderable, Renderer};
 }
 
+use assets::prelude::{Prefab, PrefabMaterial};
 use spatial::prelude::SceneGraph;
 use utils::prelude::Component;
 use Entity;
@@ -29,6 +30,10 @@ pub trait Renderer {
     fn mtl_mut(&mut self, ent: Entity) -> Option<&mut Self::Mtl>;
     fn remove_mtl(&mut self, ent: Entity);
 
+    /// Adds the material which converted from `PrefabMaterial` when instantiating prefabs.
+    /// Textures referenced by the material are stored in `Prefab::textures`.
+    fn add_prefab_mtl(&mut self, _: Entity, _: &PrefabMaterial, _: &Prefab) {}
+
     fn submit(&mut self, camera: &Camera, lits: &[Lit], meshes: &[MeshRenderer]);
 }
 
@@ -109,21 +114,52 @@ impl Renderable {
 }
 
 impl Renderable {
+    /// Gets the number of cameras.
+    #[inline]
+    pub fn num_cameras(&self) -> usize {
+        self.cameras.data.len()
+    }
+
+    /// Gets the number of lits.
+    #[inline]
+    pub fn num_lits(&self) -> usize {
+        self.lits.data.len()
+    }
+
+    /// Gets the number of mesh renderers.
+    #[inline]
+    pub fn num_meshes(&self) -> usize {
+        self.meshes.data.len()
+    }
+
     pub fn draw<R: Renderer>(&mut self, renderer: &mut R, sg: &SceneGraph) {
-        for (i, v) in self.cameras.data.iter_mut().enumerate() {
-            if let Some(transform) = sg.transform(self.cameras.entities[i]) {
+        let ents: Vec<_> = self
+            .cameras
+            .entities
+            .iter()
+            .chain(&self.lits.entities)
+            .chain(&self.meshes.entities)
+            .cloned()
+            .collect();
+
+        let transforms = sg.transforms(&ents);
+        let (cameras, transforms) = transforms.split_at(self.cameras.entities.len());
+        let (lits, meshes) = transforms.split_at(self.lits.entities.len());
+
+        for (v, transform) in self.cameras.data.iter_mut().zip(cameras) {
+            if let Some(transform) = *transform {
                 v.transform = transform;
             }
         }
 
-        for (i, v) in self.lits.data.iter_mut().enumerate() {
-            if let Some(transform) = sg.transform(self.lits.entities[i]) {
+        for (v, transform) in self.lits.data.iter_mut().zip(lits) {
+            if let Some(transform) = *transform {
                 v.transform = transform;
             }
         }
 
         for (i, v) in self.meshes.data.iter_mut().enumerate() {
-            if let Some(transform) = sg.transform(self.meshes.entities[i]) {
+            if let Some(transform) = meshes[i] {
                 v.transform = transform;
                 v.ent = self.meshes.entities[i];
             }
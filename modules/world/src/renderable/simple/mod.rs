@@ -4,6 +4,7 @@ pub use self::material::SimpleMaterial;
 use crayon::prelude::*;
 use failure::Error;
 
+use assets::prelude::{Prefab, PrefabMaterial};
 use utils::prelude::Component;
 use Entity;
 
@@ -184,6 +185,20 @@ impl super::Renderer for SimpleRenderer {
         self.remove(ent);
     }
 
+    fn add_prefab_mtl(&mut self, ent: Entity, mtl: &PrefabMaterial, prefab: &Prefab) {
+        let c = mtl.base_color;
+        let smoothness = (1.0 - mtl.roughness).max(0.0).min(1.0);
+
+        let mut m = SimpleMaterial::default();
+        m.diffuse = Color::new(c[0], c[1], c[2], c[3]);
+        m.diffuse_texture = mtl
+            .base_color_texture
+            .and_then(|v| prefab.textures.get(v).cloned());
+        m.specular = Color::new(smoothness, smoothness, smoothness, 1.0);
+        m.shininess = smoothness * 128.0;
+        self.add(ent, m);
+    }
+
     fn submit(&mut self, camera: &Camera, lits: &[Lit], meshes: &[MeshRenderer]) {
         use crayon::math::prelude::{InnerSpace, Matrix, MetricSpace, SquareMatrix};
 
@@ -191,12 +206,15 @@ impl super::Renderer for SimpleRenderer {
         let projection_matrix = camera.frustum().to_matrix();
         let mut lits = Vec::from(lits);
 
-        for mesh in meshes {
+        let matrices = crayon::sched::par::par_map(meshes, |mesh| {
             let model_matrix = mesh.transform.matrix();
             let mv = view_matrix * model_matrix;
             let mvp = projection_matrix * mv;
             let vn = mv.invert().and_then(|v| Some(v.transpose())).unwrap_or(mv);
+            (mv, mvp, vn)
+        });
 
+        for (mesh, &(mv, mvp, vn)) in meshes.iter().zip(&matrices) {
             let mut dc = Draw::new(self.shader, mesh.mesh);
             dc.set_uniform_variable("u_ModelViewMatrix", mv);
             dc.set_uniform_variable("u_MVPMatrix", mvp);
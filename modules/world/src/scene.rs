@@ -1,5 +1,7 @@
 //! Scenes contain the environments and menus of your game.
 
+use std::sync::{Arc, RwLock};
+
 use crayon::errors::Result;
 use crayon::math::prelude::{Quaternion, Vector3};
 use crayon::utils::prelude::HandlePool;
@@ -10,12 +12,22 @@ use spatial::prelude::{SceneGraph, Transform};
 use tags::Tags;
 use Entity;
 
+/// The statistics of a `Scene`, which are published through the inspector of world system.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SceneStats {
+    pub entities: usize,
+    pub cameras: usize,
+    pub lits: usize,
+    pub meshes: usize,
+}
+
 /// Scenes contain the environments and menus of your game. Think of each unique
 /// Scene as a unique level. In each Scene, you place your environments, obstacles,
 /// and decorations, essentially designing and building your game in pieces.
 pub struct Scene<R: Renderer> {
     entities: HandlePool<Entity>,
     tags: Tags,
+    stats: Arc<RwLock<SceneStats>>,
 
     pub nodes: SceneGraph,
     pub renderables: Renderable,
@@ -24,9 +36,15 @@ pub struct Scene<R: Renderer> {
 
 impl<R: Renderer> Scene<R> {
     pub fn new(renderer: R) -> Self {
+        let stats = Arc::new(RwLock::new(SceneStats::default()));
+        if ::inside::valid() {
+            ::inside::ctx().add_scene(&stats);
+        }
+
         Scene {
             entities: HandlePool::new(),
             tags: Tags::new(),
+            stats: stats,
             nodes: SceneGraph::new(),
             renderables: Renderable::new(),
             renderer: renderer,
@@ -39,6 +57,23 @@ impl<R: Renderer> Scene<R> {
         self.entities.len()
     }
 
+    /// Gets the statistics of this scene.
+    pub fn stats(&self) -> SceneStats {
+        SceneStats {
+            entities: self.entities.len(),
+            cameras: self.renderables.num_cameras(),
+            lits: self.renderables.num_lits(),
+            meshes: self.renderables.num_meshes(),
+        }
+    }
+
+    // Publishes the statistics to inspector, it's done when entities are created or deleted,
+    // and every time the scene is drawn.
+    #[inline]
+    fn publish(&self) {
+        *self.stats.write().unwrap() = self.stats();
+    }
+
     /// Checks if specified `Entity` was created by this scene, and has not been
     /// deleted yet.
     #[inline]
@@ -52,6 +87,7 @@ impl<R: Renderer> Scene<R> {
         let e = self.entities.create().into();
         self.nodes.add(e);
         self.tags.add(e, name.as_ref());
+        self.publish();
         e
     }
 
@@ -78,6 +114,7 @@ impl<R: Renderer> Scene<R> {
                 self.renderables.remove_camera(v);
             }
 
+            self.publish();
             Some(deletions)
         } else {
             None
@@ -196,6 +233,10 @@ impl<R: Renderer> Scene<R> {
                     self.renderables.add_mesh(e, mr);
                 }
 
+                if let Some(mtl) = n.material.and_then(|v| prefab.materials.get(v)) {
+                    self.renderer.add_prefab_mtl(e, mtl, &prefab);
+                }
+
                 if let Some(sib) = n.next_sib {
                     nodes.push((parent, sib));
                 }
@@ -219,6 +260,7 @@ impl<R: Renderer> Scene<R> {
     #[inline]
     pub fn draw(&mut self) {
         self.renderables.draw(&mut self.renderer, &self.nodes);
+        self.publish();
     }
 }
 
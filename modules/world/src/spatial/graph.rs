@@ -349,6 +349,53 @@ impl SceneGraph {
         })
     }
 
+    /// Gets the transforms of entities in world space. The world transforms of all nodes
+    /// are propagated once, so its much cheaper than querying `transform` one by one.
+    pub fn transforms(&self, ents: &[Entity]) -> Vec<Option<Transform>> {
+        let world = self.world_transforms();
+        ents.iter()
+            .map(|v| self.remap.get(v).map(|&index| world[index]))
+            .collect()
+    }
+
+    /// Computes the world transforms of all nodes. They are propagated level by level from
+    /// the roots, and the nodes in the same level are computed in parallel.
+    fn world_transforms(&self) -> Vec<Transform> {
+        let mut world = self.local_transforms.clone();
+        let mut level: Vec<_> = self
+            .roots
+            .iter()
+            .map(|&v| unsafe { self.index_unchecked(v) })
+            .collect();
+
+        while !level.is_empty() {
+            let mut children = Vec::new();
+            for &parent in &level {
+                let mut cursor = self.nodes[parent].first_child;
+                while let Some(child) = cursor {
+                    let child = unsafe { self.index_unchecked(child) };
+                    children.push((parent, child));
+                    cursor = self.nodes[child].next_sib;
+                }
+            }
+
+            let transforms = {
+                let world = &world;
+                crayon::sched::par::par_map(&children, |&(parent, child)| {
+                    world[parent] * self.local_transforms[child]
+                })
+            };
+
+            for (&(_, child), transform) in children.iter().zip(transforms) {
+                world[child] = transform;
+            }
+
+            level = children.into_iter().map(|(_, child)| child).collect();
+        }
+
+        world
+    }
+
     /// Gets the transform in local space.
     #[inline]
     pub fn local_transform(&self, ent: Entity) -> Option<Transform> {
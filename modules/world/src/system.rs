@@ -1,12 +1,15 @@
-use std::sync::{Arc, RwLock};
+use std::sync::{Arc, Mutex, RwLock, Weak};
 
+use crayon::application::ins::Inspectable;
 use crayon::application::prelude::*;
 use crayon::res::utils::prelude::*;
+use crayon::uuid::Uuid;
 use crayon::video::assets::prelude::*;
 use failure::Error;
 
 use assets::prelude::*;
 use assets::{mesh_builder, texture_builder};
+use scene::SceneStats;
 
 #[derive(Debug, Clone, Copy)]
 pub struct WorldDefaultResources {
@@ -16,8 +19,11 @@ pub struct WorldDefaultResources {
     pub quad: MeshHandle,
 }
 
+type Scenes = Arc<Mutex<Vec<Weak<RwLock<SceneStats>>>>>;
+
 pub struct WorldSystem {
     prefabs: Arc<RwLock<ResourcePool<PrefabHandle, PrefabLoader>>>,
+    scenes: Scenes,
     lis: LifecycleListenerHandle,
 
     pub default: WorldDefaultResources,
@@ -34,8 +40,41 @@ impl LifecycleListener for WorldState {
     }
 }
 
+struct WorldInspector {
+    prefabs: Arc<RwLock<ResourcePool<PrefabHandle, PrefabLoader>>>,
+    scenes: Scenes,
+}
+
+impl Inspectable for WorldInspector {
+    fn inspect(&self) -> ::serde_json::Value {
+        let (stats, infos) = {
+            let prefabs = self.prefabs.read().unwrap();
+            (prefabs.stats(), prefabs.infos())
+        };
+
+        // Scenes that have been dropped are removed here.
+        let mut scenes = self.scenes.lock().unwrap();
+        scenes.retain(|v| v.upgrade().is_some());
+
+        let scenes: Vec<_> = scenes
+            .iter()
+            .filter_map(|v| v.upgrade())
+            .map(|v| *v.read().unwrap())
+            .collect();
+
+        json!({
+            "prefabs": {
+                "stats": stats,
+                "resources": infos,
+            },
+            "scenes": scenes,
+        })
+    }
+}
+
 impl Drop for WorldSystem {
     fn drop(&mut self) {
+        crayon::application::detach_inspectable("world");
         crayon::application::detach(self.lis);
     }
 }
@@ -50,9 +89,19 @@ impl WorldSystem {
         };
 
         let prefabs = Arc::new(RwLock::new(ResourcePool::new(PrefabLoader::new())));
+        let scenes = Arc::new(Mutex::new(Vec::new()));
+
+        crayon::application::attach_inspectable(
+            "world",
+            Arc::new(WorldInspector {
+                prefabs: prefabs.clone(),
+                scenes: scenes.clone(),
+            }),
+        );
 
         let shared = WorldSystem {
             prefabs: prefabs.clone(),
+            scenes: scenes,
             lis: crayon::application::attach(WorldState { prefabs }),
             default: default,
         };
@@ -69,6 +118,13 @@ impl WorldSystem {
         Ok(handle)
     }
 
+    /// Create a prefab object from file asynchronously.
+    #[inline]
+    pub fn create_prefab_from_uuid(&self, uuid: Uuid) -> Result<PrefabHandle, Error> {
+        let handle = self.prefabs.write().unwrap().create_from_uuid(uuid)?;
+        Ok(handle)
+    }
+
     /// Creates a prefab object.
     #[inline]
     pub fn create_prefab(&self, prefab: Prefab) -> Result<PrefabHandle, Error> {
@@ -76,6 +132,11 @@ impl WorldSystem {
         Ok(handle)
     }
 
+    /// Registers the statistics of a scene, which will be published through inspector.
+    pub fn add_scene(&self, stats: &Arc<RwLock<SceneStats>>) {
+        self.scenes.lock().unwrap().push(Arc::downgrade(stats));
+    }
+
     /// Return the prefab obejct if exists.
     #[inline]
     pub fn prefab(&self, handle: PrefabHandle) -> Option<Arc<Prefab>> {
@@ -0,0 +1,175 @@
+extern crate crayon;
+extern crate crayon_world;
+
+use crayon::math::prelude::Vector3;
+use crayon::video::prelude::{Attribute, IndexFormat};
+use crayon_world::assets::gltf;
+
+const JSON: &str = r#"{
+    "asset": { "version": "2.0" },
+    "scene": 0,
+    "scenes": [{ "nodes": [0] }],
+    "nodes": [
+        { "name": "parent", "translation": [1.0, 2.0, 3.0], "children": [1] },
+        { "name": "child", "mesh": 0 }
+    ],
+    "meshes": [{
+        "primitives": [{ "attributes": { "POSITION": 0, "COLOR_0": 2 }, "indices": 1, "material": 0 }]
+    }],
+    "materials": [{
+        "pbrMetallicRoughness": { "baseColorFactor": [1.0, 0.0, 0.0, 1.0], "roughnessFactor": 0.5 }
+    }],
+    "accessors": [
+        { "bufferView": 0, "componentType": 5126, "count": 3, "type": "VEC3" },
+        { "bufferView": 1, "componentType": 5123, "count": 3, "type": "SCALAR" },
+        { "bufferView": 2, "componentType": 5121, "normalized": true, "count": 3, "type": "VEC3" }
+    ],
+    "bufferViews": [
+        { "buffer": 0, "byteOffset": 0, "byteLength": 36 },
+        { "buffer": 0, "byteOffset": 36, "byteLength": 6 },
+        { "buffer": 0, "byteOffset": 44, "byteLength": 9 }
+    ],
+    "buffers": [{ BUFFER "byteLength": 56 }]
+}"#;
+
+fn bin() -> Vec<u8> {
+    let mut bytes = Vec::new();
+    for v in &[0.0f32, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 1.0] {
+        bytes.extend_from_slice(&v.to_bits().to_le_bytes());
+    }
+
+    for v in &[0u16, 1, 2, 0] {
+        bytes.extend_from_slice(&v.to_le_bytes());
+    }
+
+    bytes.extend_from_slice(&[255, 0, 0, 0, 255, 0, 0, 0, 255, 0, 0, 0]);
+    bytes
+}
+
+fn encode_glb() -> Vec<u8> {
+    let mut json = JSON.replace("BUFFER", "").into_bytes();
+    while json.len() % 4 != 0 {
+        json.push(b' ');
+    }
+
+    let bin = bin();
+    let len = 12 + 8 + json.len() + 8 + bin.len();
+
+    let mut bytes = Vec::new();
+    bytes.extend_from_slice(b"glTF");
+    bytes.extend_from_slice(&2u32.to_le_bytes());
+    bytes.extend_from_slice(&(len as u32).to_le_bytes());
+    bytes.extend_from_slice(&(json.len() as u32).to_le_bytes());
+    bytes.extend_from_slice(b"JSON");
+    bytes.extend_from_slice(&json);
+    bytes.extend_from_slice(&(bin.len() as u32).to_le_bytes());
+    bytes.extend_from_slice(b"BIN\0");
+    bytes.extend_from_slice(&bin);
+    bytes
+}
+
+fn check(scene: &gltf::GltfImport) {
+    let prefab = &scene.prefab;
+    assert_eq!(prefab.nodes.len(), 3);
+
+    assert_eq!(prefab.nodes[0].first_child, Some(1));
+    assert_eq!(prefab.nodes[1].name, "parent");
+    assert_eq!(prefab.nodes[1].first_child, Some(2));
+    assert_eq!(
+        prefab.nodes[1].local_transform.position,
+        Vector3::new(1.0, 2.0, -3.0)
+    );
+
+    assert_eq!(prefab.nodes[2].name, "child");
+    assert_eq!(prefab.nodes[2].mesh_renderer, Some(0));
+    assert_eq!(prefab.nodes[2].material, Some(0));
+
+    assert_eq!(prefab.materials.len(), 1);
+    assert_eq!(prefab.materials[0].base_color, [1.0, 0.0, 0.0, 1.0]);
+    assert_eq!(prefab.materials[0].roughness, 0.5);
+    assert_eq!(prefab.materials[0].base_color_texture, None);
+
+    assert_eq!(scene.meshes.len(), 1);
+    let (ref params, ref data) = scene.meshes[0];
+    assert_eq!(params.num_verts, 3);
+    assert_eq!(params.num_idxes, 3);
+    assert_eq!(params.index_format, IndexFormat::U16);
+    assert_eq!(params.layout.offset(Attribute::Position), Some(0));
+    assert_eq!(params.layout.offset(Attribute::Color0), Some(12));
+    assert_eq!(params.layout.stride(), 28);
+
+    // The Z axis is mirrored, and the winding order is reversed.
+    assert_eq!(params.aabb.min.z, -1.0);
+    assert_eq!(&data.iptr[..], &[0, 0, 2, 0, 1, 0]);
+
+    // RGB colors are normalized and padded with alpha.
+    let color: Vec<f32> = data.vptr[12..28]
+        .chunks(4)
+        .map(|v| f32::from_bits(u32::from_le_bytes([v[0], v[1], v[2], v[3]])))
+        .collect();
+    assert_eq!(color, vec![1.0, 0.0, 0.0, 1.0]);
+}
+
+#[test]
+fn glb() {
+    let bytes = encode_glb();
+    assert!(gltf::is_gltf(&bytes));
+
+    let scene = gltf::import(&bytes, |uri| panic!("unexpected uri {}.", uri)).unwrap();
+    check(&scene);
+}
+
+#[test]
+fn external() {
+    let json = JSON.replace("BUFFER", r#""uri": "triangle.bin","#);
+    assert!(gltf::is_gltf(json.as_bytes()));
+
+    let scene = gltf::import(json.as_bytes(), |uri| {
+        assert_eq!(uri, "triangle.bin");
+        Ok(bin())
+    })
+    .unwrap();
+    check(&scene);
+
+    assert!(gltf::import(json.as_bytes(), |_| Ok(vec![0; 8])).is_err());
+}
+
+#[test]
+fn invalid() {
+    assert!(!gltf::is_gltf(b"PREB"));
+    assert!(gltf::import(b"{ \"nodes\": [{ \"mesh\": 0 }] }", |_| Ok(Vec::new())).is_err());
+    assert!(gltf::import(b"glTF\x01\0\0\0", |_| Ok(Vec::new())).is_err());
+}
+
+#[test]
+fn strip() {
+    let json = JSON
+        .replace("BUFFER", r#""uri": "triangle.bin","#)
+        .replace(r#""indices": 1,"#, r#""indices": 1, "mode": 5,"#)
+        .replace(
+            r#""count": 3, "type": "SCALAR""#,
+            r#""count": 4, "type": "SCALAR""#,
+        )
+        .replace(r#""byteLength": 6"#, r#""byteLength": 8"#);
+
+    let scene = gltf::import(json.as_bytes(), |_| Ok(bin())).unwrap();
+    let (ref params, ref data) = scene.meshes[0];
+
+    // Strips are converted into lists, with the winding order reversed.
+    assert_eq!(params.num_idxes, 6);
+    assert_eq!(&data.iptr[..], &[0, 0, 2, 0, 1, 0, 2, 0, 0, 0, 1, 0]);
+}
+
+#[test]
+fn out_of_bounds() {
+    let json = JSON.replace("BUFFER", r#""uri": "triangle.bin","#);
+    for count in &["4", "18446744073709551615"] {
+        let json = json.replacen(
+            r#""count": 3, "type": "VEC3""#,
+            &format!(r#""count": {}, "type": "VEC3""#, count),
+            1,
+        );
+
+        assert!(gltf::import(json.as_bytes(), |_| Ok(bin())).is_err());
+    }
+}
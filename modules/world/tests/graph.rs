@@ -108,6 +108,45 @@ fn transform() {
     assert_ulps_eq!(transform.transform_point(v), [1.0, 2.0, 2.0].into());
 }
 
+#[test]
+fn transforms() {
+    let mut scene = Scene::new(HeadlessRenderer::new());
+    let e1 = scene.create("e1");
+    let e2 = scene.create("e2");
+    let e3 = scene.create("e3");
+    let e4 = scene.create("e4");
+    let e5 = scene.create("e5");
+
+    // Children are created before their parents, so the storage order is not the
+    // hierachy order.
+    scene.set_parent(e1, e2, false).unwrap();
+    scene.set_parent(e2, e3, false).unwrap();
+    scene.set_parent(e4, e3, false).unwrap();
+    // e3 <- (e2 <- e1, e4), e5
+
+    scene.set_local_position(e1, [1.0, 0.0, 0.0]);
+    scene.set_local_position(e2, [0.0, 1.0, 0.0]);
+    scene.set_local_position(e3, [0.0, 0.0, 1.0]);
+    scene.set_local_position(e4, [2.0, 0.0, 0.0]);
+    scene.set_local_position(e5, [0.0, 2.0, 0.0]);
+    scene.set_scale(e3, 2.0);
+
+    let e6 = scene.create("e6");
+    scene.delete(e6);
+
+    let ents = [e1, e2, e3, e4, e5, e6];
+    let transforms = scene.nodes.transforms(&ents);
+    assert!(transforms[5].is_none());
+
+    for (ent, transform) in ents[0..5].iter().zip(&transforms) {
+        let expected = scene.transform(*ent).unwrap();
+        assert_ulps_eq!(transform.unwrap().position, expected.position);
+        assert_ulps_eq!(transform.unwrap().scale, expected.scale);
+    }
+
+    assert_ulps_eq!(transforms[0].unwrap().position, [2.0, 2.0, 1.0].into());
+}
+
 #[test]
 fn keep_world_pose() {
     // Hierachy changes might have affects on node's transform.
@@ -37,11 +37,7 @@ fn instantiate() {
     crayon::application::oneshot().unwrap();
     crayon_world::setup().unwrap();
 
-    let mut prefab = Prefab {
-        nodes: Vec::new(),
-        universe_meshes: Vec::new(),
-        meshes: Vec::new(),
-    };
+    let mut prefab = Prefab::default();
 
     prefab.nodes.push(PrefabNode {
         name: "room.obj".into(),
@@ -49,6 +45,7 @@ fn instantiate() {
         first_child: Some(1),
         next_sib: None,
         mesh_renderer: None,
+        material: None,
     });
 
     prefab.nodes.push(PrefabNode {
@@ -57,6 +54,7 @@ fn instantiate() {
         first_child: Some(2),
         next_sib: None,
         mesh_renderer: None,
+        material: None,
     });
 
     prefab.nodes.push(PrefabNode {
@@ -65,6 +63,7 @@ fn instantiate() {
         first_child: None,
         next_sib: Some(3),
         mesh_renderer: None,
+        material: None,
     });
 
     prefab.nodes.push(PrefabNode {
@@ -73,6 +72,7 @@ fn instantiate() {
         first_child: None,
         next_sib: None,
         mesh_renderer: None,
+        material: None,
     });
 
     let template = crayon_world::create_prefab(prefab).unwrap();
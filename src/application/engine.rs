@@ -1,9 +1,11 @@
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
+use crate::prof::ProfileScope;
 use crate::sched::prelude::LatchProbe;
 use crate::window::prelude::{Event, EventListener, EventListenerHandle, WindowEvent};
 
+use super::ins::InspectSystem;
 use super::lifecycle::LifecycleListener;
 use super::Params;
 
@@ -16,6 +18,8 @@ pub struct EngineSystem {
     events: EventListenerHandle,
     state: Arc<EngineState>,
     headless: bool,
+    seed: u64,
+    ins: InspectSystem,
 }
 
 struct EngineState {
@@ -37,11 +41,14 @@ impl Drop for EngineSystem {
         crate::window::detach(self.events);
 
         unsafe {
+            crate::console::inside::discard();
             crate::res::inside::discard();
             crate::input::inside::discard();
             crate::video::inside::discard();
             crate::window::inside::discard();
             crate::sched::inside::discard();
+            crate::prof::inside::discard();
+            crate::crash::inside::discard();
         }
     }
 }
@@ -49,10 +56,11 @@ impl Drop for EngineSystem {
 impl EngineSystem {
     /// Setup engine with specified settings.
     pub unsafe fn new(params: Params) -> Result<Self> {
-        #[cfg(not(target_arch = "wasm32"))]
-        crate::sched::inside::setup(4, None, None);
-        #[cfg(target_arch = "wasm32")]
-        crate::sched::inside::setup(0, None, None);
+        crate::crash::inside::setup(&params);
+        crate::prof::inside::setup();
+        console(&params)?;
+
+        crate::sched::inside::setup(&params.sched, None);
 
         crate::window::inside::setup(params.window)?;
         crate::video::inside::setup()?;
@@ -67,16 +75,21 @@ impl EngineSystem {
             events: crate::window::attach(state.clone()),
             state,
             headless: false,
+            seed: params.seed,
+            ins: inspector(params.inspector)?,
         };
 
         Ok(sys)
     }
 
-    pub unsafe fn new_headless(params: Params) -> Result<Self> {
-        #[cfg(not(target_arch = "wasm32"))]
-        crate::sched::inside::setup(4, None, None);
-        #[cfg(target_arch = "wasm32")]
-        crate::sched::inside::setup(0, None, None);
+    pub unsafe fn new_headless(mut params: Params) -> Result<Self> {
+        // Keeps away from the real devices, virtual gamepads could still be attached.
+        params.input.gamepad.scan_devices = false;
+        crate::crash::inside::setup(&params);
+        crate::prof::inside::setup();
+        console(&params)?;
+
+        crate::sched::inside::setup(&params.sched, None);
 
         crate::window::inside::headless();
         crate::video::inside::headless();
@@ -91,6 +104,8 @@ impl EngineSystem {
             events: crate::window::attach(state.clone()),
             state,
             headless: true,
+            seed: params.seed,
+            ins: inspector(params.inspector)?,
         };
 
         Ok(sys)
@@ -106,11 +121,22 @@ impl EngineSystem {
         self.headless
     }
 
-    pub fn run_oneshot(&self) -> Result<()> {
-        super::foreach(|v| v.on_pre_update())?;
-        super::foreach(|v| v.on_update())?;
-        super::foreach(|v| v.on_render())?;
-        super::foreach_rev(|v| v.on_post_update())?;
+    #[inline]
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    #[inline]
+    pub fn ins(&self) -> &InspectSystem {
+        &self.ins
+    }
+
+    /// Runs `num` frames on the calling thread.
+    pub fn run_frames(&self, num: usize) -> Result<()> {
+        for _ in 0..num {
+            advance()?;
+        }
+
         Ok(())
     }
 
@@ -138,11 +164,7 @@ impl EngineSystem {
 
                 super::sys::run_forever(
                     move || {
-                        super::foreach(|v| v.on_pre_update())?;
-                        super::foreach(|v| v.on_update())?;
-                        super::foreach(|v| v.on_render())?;
-                        super::foreach_rev(|v| v.on_post_update())?;
-
+                        advance()?;
                         Ok(state.alive.load(Ordering::Relaxed))
                     },
                     move || {
@@ -160,3 +182,93 @@ impl EngineSystem {
         Ok(())
     }
 }
+
+// Setups the console system with the built-in cvars of engine.
+unsafe fn console(params: &Params) -> Result<()> {
+    use crate::console::{self, CVarValue};
+
+    fn u32_of(v: &CVarValue) -> u32 {
+        v.as_i64().unwrap_or(0).max(0) as u32
+    }
+
+    crate::console::inside::setup(params.console.clone())?;
+
+    let help = "The minimum frames per second, time will appear to slow if fps goes lower.";
+    console::register_cvar_with_callback("min_fps", help, params.min_fps, |v| {
+        super::set_min_fps(u32_of(v))
+    });
+
+    let help = "The maximum frames per second, the engine will sleep if fps is higher.";
+    console::register_cvar_with_callback("max_fps", help, params.max_fps, |v| {
+        super::set_max_fps(u32_of(v))
+    });
+
+    let help = "The maximum frames per second when the application does not have focus.";
+    console::register_cvar_with_callback("max_inactive_fps", help, params.max_inactive_fps, |v| {
+        super::set_max_inactive_fps(u32_of(v))
+    });
+
+    let help = "The scale of game time, e.g. 0.5 for slow motion.";
+    console::register_cvar_with_callback("time_scale", help, 1.0, |v| {
+        super::set_time_scale(v.as_f64().unwrap_or(1.0) as f32)
+    });
+
+    Ok(())
+}
+
+// Creates the inspect system with the built-in inspectables of core systems.
+fn inspector(port: Option<u16>) -> Result<InspectSystem> {
+    let mut ins = InspectSystem::new();
+    ins.attach("time", super::time_ctx().inspectable());
+    ins.attach("sched", crate::sched::inside::ctx().inspectable());
+    ins.attach("video", crate::video::inside::ctx().inspectable());
+    ins.attach("input", crate::input::inside::ctx().inspectable());
+    ins.attach("res", crate::res::inside::ctx().inspectable());
+    ins.attach("console", crate::console::inside::ctx().inspectable());
+
+    if let Some(port) = port {
+        ins.listen(port)?;
+    }
+
+    Ok(ins)
+}
+
+// Runs all the lifecycle phases of one frame, with profiling spans of each phase.
+fn advance() -> Result<()> {
+    crate::crash::inside::ctx().advance();
+    let _frame = ProfileScope::new("lifecycle", "frame");
+
+    {
+        let _s = phase("on_pre_update");
+        super::foreach(|v| v.on_pre_update())?;
+    }
+
+    for _ in 0..super::time_ctx().fixed_steps() {
+        let _s = phase("on_fixed_update");
+        super::foreach(|v| v.on_fixed_update())?;
+    }
+
+    {
+        let _s = phase("on_update");
+        super::foreach(|v| v.on_update())?;
+        super::run_tasks();
+    }
+
+    {
+        let _s = phase("on_render");
+        super::foreach(|v| v.on_render())?;
+    }
+
+    {
+        let _s = phase("on_post_update");
+        super::foreach_rev(|v| v.on_post_update())?;
+    }
+
+    Ok(())
+}
+
+// Records the lifecycle phase for crash reports, and profiles it.
+fn phase(name: &'static str) -> ProfileScope {
+    crate::crash::inside::ctx().enter(name);
+    ProfileScope::new("lifecycle", name)
+}
@@ -1,89 +1,381 @@
+//! A bidirectional inspector protocol, which makes live tuning possible without recompiling.
+//!
+//! Systems register themselves as named `Inspectable`s. Clients connected to the inspector
+//! server could list the registered inspectables, subscribe their snapshots at chosen
+//! intervals and send commands back (e.g. setting a value or toggling a flag).
+//!
+//! Messages are JSON documents prefixed with their lengths in 4 bytes (little endian). The
+//! client sends `Request`s, and the server replies with `Response`s.
+//!
+//! ```sh
+//! crayon-inspector list
+//! crayon-inspector watch video 500
+//! crayon-inspector command console set '{"name": "max_fps", "value": 60}'
+//! ```
+
 use std::collections::HashMap;
-use std::io::Write;
+use std::io::{self, Read, Write};
 use std::net::{SocketAddr, TcpListener, TcpStream};
-use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, RwLock};
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, Instant};
+
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use serde_json::Value;
 
 use crate::errors::*;
-use byteorder::{LittleEndian, WriteBytesExt};
 
-pub const PORT: u32 = 9338;
+/// The default port of inspector server.
+pub const PORT: u16 = 9338;
+
+/// The maximum size of a message.
+pub const MAX_MESSAGE_SIZE: usize = 16 * 1024 * 1024;
 
 pub trait Inspectable: Send + Sync {
-    fn inspect(&self, buf: &mut Vec<u8>);
+    /// Takes a snapshot of the current states.
+    fn inspect(&self) -> Value;
+
+    /// Gets the names of supported commands.
+    fn commands(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// Executes a command sent from clients. Notes that it's called on the thread of
+    /// inspector server.
+    fn command(&self, name: &str, _: &Value) -> Result<Value> {
+        bail!("Command {} is not supported.", name)
+    }
+}
+
+/// The messages sent from clients.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Request {
+    /// Lists the registered inspectables.
+    List,
+    /// Takes a snapshot of inspectable.
+    Inspect {
+        name: String,
+    },
+    /// Sends the snapshots of inspectable periodically.
+    Subscribe {
+        name: String,
+        interval_ms: u64,
+    },
+    Unsubscribe {
+        name: String,
+    },
+    /// Executes a command of inspectable.
+    Command {
+        name: String,
+        command: String,
+        #[serde(default)]
+        args: Value,
+    },
+}
+
+/// The descriptions of registered inspectable.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InspectableInfo {
+    pub name: String,
+    pub commands: Vec<String>,
+}
+
+/// The messages sent from inspector server.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Response {
+    List {
+        inspectables: Vec<InspectableInfo>,
+    },
+    Snapshot {
+        name: String,
+        value: Value,
+    },
+    Unsubscribed {
+        name: String,
+    },
+    CommandResult {
+        name: String,
+        command: String,
+        result: ::std::result::Result<Value, String>,
+    },
+    Error {
+        message: String,
+    },
+}
+
+/// Writes a length-prefixed message.
+pub fn write_message<W: Write, T: serde::Serialize>(w: &mut W, message: &T) -> Result<()> {
+    let bytes = serde_json::to_vec(message)?;
+    w.write_u32::<LittleEndian>(bytes.len() as u32)?;
+    w.write_all(&bytes)?;
+    w.flush()?;
+    Ok(())
 }
 
+/// Reads a length-prefixed message, it blocks until the whole message is received.
+pub fn read_message<R: Read, T: serde::de::DeserializeOwned>(r: &mut R) -> Result<T> {
+    let len = r.read_u32::<LittleEndian>()? as usize;
+    if len > MAX_MESSAGE_SIZE {
+        bail!("Message with {} bytes is too large.", len);
+    }
+
+    let mut bytes = vec![0; len];
+    r.read_exact(&mut bytes)?;
+    Ok(serde_json::from_slice(&bytes)?)
+}
+
+type Registry = Arc<RwLock<HashMap<String, Arc<dyn Inspectable>>>>;
+
+/// The registry of inspectables, which serves the clients if the server is started.
 pub struct InspectSystem {
-    listener: TcpListener,
-    ins: HashMap<String, Arc<Inspectable>>,
+    ins: Registry,
+    alive: Arc<AtomicBool>,
+    addr: Option<SocketAddr>,
 }
 
-impl InspectSystem {
-    pub fn new(port: u32) -> Self {
-        let listener = TcpListener::bind(format!("127.0.0.1:{}", port)).unwrap();
+impl Drop for InspectSystem {
+    fn drop(&mut self) {
+        self.alive.store(false, Ordering::Relaxed);
+    }
+}
 
+impl InspectSystem {
+    pub fn new() -> Self {
         InspectSystem {
-            listener,
-            ins: HashMap::new(),
+            ins: Arc::new(RwLock::new(HashMap::new())),
+            alive: Arc::new(AtomicBool::new(true)),
+            addr: None,
         }
     }
 
-    pub fn attach<T: Inspectable + 'static>(&mut self, id: &str, sys: Arc<T>) {
-        self.ins.insert(id.to_string(), sys);
+    /// Registers a inspectable, the old one with the same name is replaced.
+    pub fn attach<T: Inspectable + 'static>(&self, name: &str, ins: Arc<T>) {
+        self.ins.write().unwrap().insert(name.to_owned(), ins);
+    }
+
+    /// Unregisters a inspectable.
+    pub fn detach(&self, name: &str) {
+        self.ins.write().unwrap().remove(name);
+    }
+
+    /// Gets the address of inspector server.
+    #[inline]
+    pub fn addr(&self) -> Option<SocketAddr> {
+        self.addr
     }
 
-    pub fn listen(mut self) {
-        info!(
-            "[InspectSystem] Starting inspector at {}:{}",
-            "127.0.0.1", PORT
-        );
+    /// Starts the inspector server at 127.0.0.1, the port is chosen by OS if its zero.
+    pub fn listen(&mut self, port: u16) -> Result<()> {
+        let listener = TcpListener::bind(("127.0.0.1", port))?;
+        listener.set_nonblocking(true)?;
 
+        let addr = listener.local_addr()?;
+        self.addr = Some(addr);
+        info!("[InspectSystem] Starting inspector at {}.", addr);
+
+        let ins = self.ins.clone();
+        let alive = self.alive.clone();
         thread::Builder::new()
             .name("Inspector".to_string())
-            .spawn(move || loop {
-                match self.connect() {
-                    Ok((stream, addr)) => {
-                        if let Err(err) = self.worker(stream) {
-                            warn!("[InspectSystem] Connection with {} broken! {}.", addr, err);
+            .spawn(move || {
+                while alive.load(Ordering::Relaxed) {
+                    match listener.accept() {
+                        Ok((stream, addr)) => {
+                            info!("[InspectSystem] Accepting client from {}.", addr);
+                            serve(stream, addr, ins.clone(), alive.clone());
+                        }
+                        Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => {
+                            thread::sleep(Duration::from_millis(50));
+                        }
+                        Err(err) => {
+                            warn!("[InspectSystem] Failed to accept connection! {}", err);
+                            thread::sleep(Duration::from_millis(50));
                         }
-                    }
-                    Err(err) => {
-                        warn!("[InspectSystem] Failed to accept connection! {}", err);
                     }
                 }
+            })?;
 
-                ::std::thread::sleep(Duration::from_secs(1));
-            })
-            .unwrap();
+        Ok(())
     }
+}
+
+fn serve(stream: TcpStream, addr: SocketAddr, ins: Registry, alive: Arc<AtomicBool>) {
+    let spawned = thread::Builder::new()
+        .name(format!("Inspector-{}", addr))
+        .spawn(move || {
+            let mut client = Client {
+                stream,
+                ins,
+                buf: Vec::new(),
+                subscriptions: HashMap::new(),
+            };
 
-    fn connect(&mut self) -> Result<(TcpStream, SocketAddr)> {
-        let (stream, addr) = self.listener.accept()?;
-        info!("[InspectSystem] Accepting watcher from {}.", addr);
-        Ok((stream, addr))
+            if let Err(err) = client.run(&alive) {
+                warn!("[InspectSystem] Connection with {} broken! {}.", addr, err);
+            }
+        });
+
+    if let Err(err) = spawned {
+        warn!("[InspectSystem] Failed to serve {}! {}", addr, err);
     }
+}
 
-    fn worker(&mut self, mut stream: TcpStream) -> Result<()> {
-        let mut buf = Vec::new();
-        let mut element = Vec::new();
+struct Client {
+    stream: TcpStream,
+    ins: Registry,
+    // The received bytes of incomplete messages.
+    buf: Vec<u8>,
+    subscriptions: HashMap<String, (Duration, Instant)>,
+}
 
-        loop {
-            buf.clear();
-            element.clear();
+impl Client {
+    fn run(&mut self, alive: &AtomicBool) -> Result<()> {
+        // Wakes up periodically to send the snapshots of subscriptions.
+        self.stream.set_nonblocking(false)?;
+        self.stream
+            .set_read_timeout(Some(Duration::from_millis(10)))?;
 
-            for (k, v) in &self.ins {
-                buf.write_u8(k.len() as u8)?;
-                buf.extend_from_slice(k.as_bytes());
+        let mut chunk = [0; 4096];
+        while alive.load(Ordering::Relaxed) {
+            match self.stream.read(&mut chunk) {
+                Ok(0) => return Ok(()),
+                Ok(n) => self.buf.extend_from_slice(&chunk[..n]),
+                Err(ref err)
+                    if err.kind() == io::ErrorKind::WouldBlock
+                        || err.kind() == io::ErrorKind::TimedOut => {}
+                Err(err) => return Err(err.into()),
+            }
+
+            while let Some(request) = self.next_request()? {
+                let response = self.handle(request);
+                write_message(&mut self.stream, &response)?;
+            }
+
+            self.publish()?;
+        }
+
+        Ok(())
+    }
+
+    fn next_request(&mut self) -> Result<Option<Request>> {
+        if self.buf.len() < 4 {
+            return Ok(None);
+        }
+
+        let len = (&self.buf[..4]).read_u32::<LittleEndian>()? as usize;
+        if len > MAX_MESSAGE_SIZE {
+            bail!("Message with {} bytes is too large.", len);
+        }
+
+        if self.buf.len() < len + 4 {
+            return Ok(None);
+        }
+
+        let request = serde_json::from_slice(&self.buf[4..len + 4]);
+        self.buf.drain(..len + 4);
+
+        match request {
+            Ok(v) => Ok(Some(v)),
+            Err(err) => {
+                let message = format!("Malformed request. {}", err);
+                write_message(&mut self.stream, &Response::Error { message })?;
+                self.next_request()
+            }
+        }
+    }
+
+    fn handle(&mut self, request: Request) -> Response {
+        let ins = self.ins.read().unwrap();
+        let find = |name: &str| {
+            ins.get(name).ok_or_else(|| Response::Error {
+                message: format!("Inspectable {} is undefined.", name),
+            })
+        };
+
+        match request {
+            Request::List => {
+                let mut inspectables: Vec<_> = ins
+                    .iter()
+                    .map(|(k, v)| InspectableInfo {
+                        name: k.clone(),
+                        commands: v.commands(),
+                    })
+                    .collect();
+
+                inspectables.sort_by(|lhs, rhs| lhs.name.cmp(&rhs.name));
+                Response::List { inspectables }
+            }
+
+            Request::Inspect { name } => match find(&name) {
+                Ok(v) => Response::Snapshot {
+                    value: v.inspect(),
+                    name,
+                },
+                Err(err) => err,
+            },
 
-                v.inspect(&mut element);
+            Request::Subscribe { name, interval_ms } => match find(&name) {
+                Ok(v) => {
+                    let interval = Duration::from_millis(interval_ms);
+                    let value = v.inspect();
+                    self.subscriptions
+                        .insert(name.clone(), (interval, Instant::now()));
+                    Response::Snapshot { name, value }
+                }
+                Err(err) => err,
+            },
 
-                buf.write_u32::<LittleEndian>(element.len() as u32)?;
-                buf.append(&mut element);
+            Request::Unsubscribe { name } => {
+                if self.subscriptions.remove(&name).is_some() {
+                    Response::Unsubscribed { name }
+                } else {
+                    Response::Error {
+                        message: format!("Inspectable {} is not subscribed.", name),
+                    }
+                }
             }
 
-            stream.write_all(&buf)?;
-            ::std::thread::sleep(Duration::from_secs(1));
+            Request::Command {
+                name,
+                command,
+                args,
+            } => match find(&name) {
+                Ok(v) => Response::CommandResult {
+                    result: v.command(&command, &args).map_err(|err| err.to_string()),
+                    name,
+                    command,
+                },
+                Err(err) => err,
+            },
         }
     }
+
+    fn publish(&mut self) -> Result<()> {
+        let now = Instant::now();
+        let mut snapshots = Vec::new();
+
+        {
+            let ins = self.ins.read().unwrap();
+            for (name, v) in &mut self.subscriptions {
+                if now.duration_since(v.1) < v.0 {
+                    continue;
+                }
+
+                v.1 = now;
+                if let Some(v) = ins.get(name) {
+                    snapshots.push(Response::Snapshot {
+                        name: name.clone(),
+                        value: v.inspect(),
+                    });
+                }
+            }
+        }
+
+        for v in snapshots {
+            write_message(&mut self.stream, &v)?;
+        }
+
+        Ok(())
+    }
 }
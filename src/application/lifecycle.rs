@@ -1,5 +1,6 @@
 use std::sync::{Arc, Mutex};
 
+use crate::sched::prelude::TaskGraph;
 use crate::utils::object_pool::ObjectPool;
 
 impl_handle!(LifecycleListenerHandle);
@@ -9,10 +10,21 @@ pub trait LifecycleListener {
         Ok(())
     }
 
+    /// Called zero or more times per frame with the fixed timestep, right after
+    /// `on_pre_update`.
+    fn on_fixed_update(&mut self) -> Result<(), failure::Error> {
+        Ok(())
+    }
+
     fn on_update(&mut self) -> Result<(), failure::Error> {
         Ok(())
     }
 
+    /// Adds the tasks of this frame into `graph`, right after `on_update`. The tasks of all
+    /// the listeners are executed in parallel as long as their declared accesses do not
+    /// conflict.
+    fn on_update_tasks<'a>(&'a mut self, _: &mut TaskGraph<'a>) {}
+
     fn on_render(&mut self) -> Result<(), failure::Error> {
         Ok(())
     }
@@ -80,6 +92,19 @@ impl LifecycleSystem {
         Ok(())
     }
 
+    /// Collects the tasks of all the listeners into a graph, and runs it.
+    pub fn run_tasks(&self) {
+        let lifecycles: Vec<_> = self.lifecycles.lock().unwrap().values().cloned().collect();
+        let mut guards: Vec<_> = lifecycles.iter().map(|v| v.lock().unwrap()).collect();
+
+        let mut graph = TaskGraph::new();
+        for v in &mut guards {
+            v.on_update_tasks(&mut graph);
+        }
+
+        graph.run();
+    }
+
     #[inline]
     pub fn foreach_rev<T>(&self, func: T) -> Result<(), failure::Error>
     where
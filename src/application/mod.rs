@@ -32,24 +32,35 @@ mod engine;
 mod launcher;
 mod lifecycle;
 mod time;
+mod timer;
 
 pub mod prelude {
     pub use super::launcher::Launcher;
     pub use super::lifecycle::{LifecycleListener, LifecycleListenerHandle};
+    pub use super::timer::{Clock, Timer};
     pub use super::Params;
 }
 
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+
 use crate::errors::*;
 
+use self::ins::Inspectable;
 use self::lifecycle::{LifecycleListener, LifecycleListenerHandle};
 
 use self::engine::EngineSystem;
 use self::inside::{ctx, lifecycle_ctx, time_ctx, CTX, LIFECYCLE_CTX, TIME_CTX};
 use self::lifecycle::LifecycleSystem;
 use self::time::TimeSystem;
+use self::timer::{Clock, Timer};
 
+use crate::console::ConsoleParams;
+use crate::crash::CrashParams;
 use crate::input::InputParams;
 use crate::res::ResourceParams;
+use crate::sched::SchedulerParams;
 use crate::window::WindowParams;
 
 /// A structure containing configuration data for the game engine, which are
@@ -69,12 +80,33 @@ pub struct Params {
     pub max_inactive_fps: u32,
     /// Set how many frames to average for timestep smoothing.
     pub time_smooth_step: u32,
+    /// The timestep of `LifecycleListener::on_fixed_update`. The fixed update is disabled
+    /// if its zero.
+    pub fixed_timestep: Duration,
+    /// The maximum number of fixed steps per frame. The time we could not catch up with is
+    /// dropped, so a slow frame will not make the next one slower. Zero means unlimited.
+    pub max_fixed_steps: u32,
+    /// Advances the clock by this fixed delta every frame instead of reading the system
+    /// clock, which makes the runs reproducible. It's usually used with `setup_headless`
+    /// and `run_frames` in integration tests.
+    pub fake_clock: Option<Duration>,
+    /// The seed of pseudorandom number generators, see `application::seed`.
+    pub seed: u64,
+    /// The port of inspector server at 127.0.0.1, see `application::ins` for details. The
+    /// port is chosen by OS if its zero, and the server is disabled if `None`.
+    pub inspector: Option<u16>,
     /// The setup parameters for window sub-system.
     pub window: WindowParams,
     /// The setup parameters for input sub-system.
     pub input: InputParams,
     /// The setup params for resource sub-system.
     pub res: ResourceParams,
+    /// The setup params for sched sub-system.
+    pub sched: SchedulerParams,
+    /// The setup params for console sub-system.
+    pub console: ConsoleParams,
+    /// The setup params for crash sub-system.
+    pub crash: CrashParams,
 }
 
 impl Default for Params {
@@ -84,9 +116,17 @@ impl Default for Params {
             max_fps: 30,
             max_inactive_fps: 0,
             time_smooth_step: 0,
+            fixed_timestep: Duration::from_nanos(16_666_667),
+            max_fixed_steps: 5,
+            fake_clock: None,
+            seed: 0,
+            inspector: None,
             window: WindowParams::default(),
             input: InputParams::default(),
             res: ResourceParams::default(),
+            sched: SchedulerParams::default(),
+            console: ConsoleParams::default(),
+            crash: CrashParams::default(),
         }
     }
 }
@@ -100,6 +140,21 @@ impl Params {
                 self.max_fps = 0;
                 warn!("The max FPS could not be controlled in web environment.");
             }
+
+            if self.inspector.is_some() {
+                self.inspector = None;
+                warn!("The inspector server is not supported in web environment.");
+            }
+
+            if self.console.config.is_some() {
+                self.console.config = None;
+                warn!("The config file of console is not supported in web environment.");
+            }
+
+            if self.crash.dir.is_some() {
+                self.crash.dir = None;
+                warn!("The crash reports could not be written in web environment.");
+            }
         }
     }
 }
@@ -127,26 +182,91 @@ where
         };
 
         let latch = crate::res::inside::load_manifests(dirs)?;
-        ctx().run(latch, closure)
+        ctx().run(latch, move || {
+            crate::console::inside::ctx().exec_startup_script()?;
+            closure()
+        })
     }
 }
 
 #[doc(hidden)]
 pub fn oneshot() -> Result<()> {
+    setup_headless(Params::default())?;
+    run_frames(1)
+}
+
+/// Setup the core system in headless mode without running the main loop, the frames are
+/// driven manually with `run_frames`. Notes that it blocks until the manifests in
+/// `params.res.dirs` are loaded.
+///
+/// ```rust,ignore
+/// let mut params = Params::default();
+/// params.fake_clock = Some(Duration::from_millis(10));
+/// params.seed = 42;
+///
+/// application::setup_headless(params)?;
+/// application::run_frames(100)?;
+/// assert_eq!(application::game_time(), Duration::from_secs(1));
+/// ```
+pub fn setup_headless(mut params: Params) -> Result<()> {
     unsafe {
         debug_assert!(LIFECYCLE_CTX.is_null(), "duplicated setup of crayon.");
 
-        let params = Params::default();
-
         sys::init();
+        params.validate();
+
+        let dirs = params.res.dirs.clone();
         LIFECYCLE_CTX = Box::into_raw(Box::new(LifecycleSystem::new()));
         TIME_CTX = Box::into_raw(Box::new(TimeSystem::new(&params)));
         CTX = Box::into_raw(Box::new(EngineSystem::new_headless(params)?));
 
-        ctx().run_oneshot()
+        let latch = crate::res::inside::load_manifests(dirs)?;
+        crate::sched::wait_until(&latch);
+
+        Ok(())
     }
 }
 
+/// Runs `num` frames on the calling thread. It's used to drive the engine which is setup
+/// with `setup_headless`.
+#[inline]
+pub fn run_frames(num: usize) -> Result<()> {
+    ctx().run_frames(num)
+}
+
+/// Gets the seed of pseudorandom number generators in `Params`. Systems should create
+/// their generators with it to have seeded behaviors.
+///
+/// ```rust,ignore
+/// let mut rand = crayon::utils::random::Random::new(application::seed());
+/// ```
+#[inline]
+pub fn seed() -> u64 {
+    ctx().seed()
+}
+
+/// Registers a inspectable with name, which could be queried and commanded by the clients
+/// of inspector server.
+#[inline]
+pub fn attach_inspectable<T>(name: &str, ins: Arc<T>)
+where
+    T: Inspectable + 'static,
+{
+    ctx().ins().attach(name, ins)
+}
+
+/// Unregisters a inspectable.
+#[inline]
+pub fn detach_inspectable(name: &str) {
+    ctx().ins().detach(name)
+}
+
+/// Gets the address of inspector server, returns `None` if the server is disabled.
+#[inline]
+pub fn inspector_addr() -> Option<SocketAddr> {
+    ctx().ins().addr()
+}
+
 /// Discard the core system.
 #[inline]
 pub fn discard() {
@@ -224,6 +344,113 @@ pub fn set_time_smoothing_step(step: u32) {
     time_ctx().set_time_smoothing_step(step);
 }
 
+/// Sets the timestep of `on_fixed_update`. The fixed update is disabled if its zero.
+#[inline]
+pub fn set_fixed_timestep(timestep: Duration) {
+    time_ctx().set_fixed_timestep(timestep);
+}
+
+/// Gets the timestep of `on_fixed_update`.
+#[inline]
+pub fn fixed_timestep() -> Duration {
+    time_ctx().fixed_timestep()
+}
+
+/// Sets the maximum number of fixed steps per frame. Zero means unlimited.
+#[inline]
+pub fn set_max_fixed_steps(steps: u32) {
+    time_ctx().set_max_fixed_steps(steps);
+}
+
+/// Gets the number of times `on_fixed_update` is called in current frame.
+#[inline]
+pub fn fixed_steps() -> u32 {
+    time_ctx().fixed_steps()
+}
+
+/// Gets the interpolation alpha in [0, 1) between the last two fixed steps, which could
+/// be used to interpolate the states of fixed update when rendering.
+#[inline]
+pub fn fixed_alpha() -> f32 {
+    time_ctx().fixed_alpha()
+}
+
+/// Sets the scale of game time, e.g. 0.5 for slow motion. It affects the game clock,
+/// fixed updates and the timers driven by game time.
+#[inline]
+pub fn set_time_scale(scale: f32) {
+    time_ctx().set_time_scale(scale);
+}
+
+/// Gets the scale of game time.
+#[inline]
+pub fn time_scale() -> f32 {
+    time_ctx().time_scale()
+}
+
+/// Pauses the game time. The fixed updates, game timers and audio are paused too.
+#[inline]
+pub fn pause() {
+    time_ctx().set_paused(true);
+}
+
+/// Resumes the game time.
+#[inline]
+pub fn resume() {
+    time_ctx().set_paused(false);
+}
+
+/// Checks if the game time is paused.
+#[inline]
+pub fn paused() -> bool {
+    time_ctx().paused()
+}
+
+/// Gets the wall-clock time since the engine starts.
+#[inline]
+pub fn real_time() -> Duration {
+    time_ctx().time(Clock::Real)
+}
+
+/// Gets the game time since the engine starts, which is scaled and paused.
+#[inline]
+pub fn game_time() -> Duration {
+    time_ctx().time(Clock::Game)
+}
+
+/// Gets the duration of last frame in game time.
+#[inline]
+pub fn game_frame_duration() -> Duration {
+    time_ctx().game_frame_duration()
+}
+
+/// Adds a named timer, which is fired at the beginning of frame after it expires. The timer
+/// with the same name is replaced.
+///
+/// ```rust,ignore
+/// application::add_timer("spawn", Timer::repeat(Duration::from_secs(3)), || { ... });
+/// ```
+#[inline]
+pub fn add_timer<T1, T2>(name: T1, timer: Timer, func: T2)
+where
+    T1: Into<String>,
+    T2: FnMut() + Send + 'static,
+{
+    time_ctx().add_timer(name.into(), timer, func);
+}
+
+/// Removes a named timer, returns true if it exists.
+#[inline]
+pub fn remove_timer(name: &str) -> bool {
+    time_ctx().remove_timer(name)
+}
+
+/// Gets the remaining time before the named timer fires, which could be used as countdown.
+#[inline]
+pub fn timer_remaining(name: &str) -> Option<Duration> {
+    time_ctx().timer_remaining(name)
+}
+
 /// Gets current fps.
 #[inline]
 pub fn fps() -> u32 {
@@ -232,7 +459,7 @@ pub fn fps() -> u32 {
 
 /// Gets the duration duraing last frame.
 #[inline]
-pub fn frame_duration() -> ::std::time::Duration {
+pub fn frame_duration() -> Duration {
     time_ctx().frame_duration()
 }
 
@@ -244,6 +471,11 @@ where
     lifecycle_ctx().foreach(func)
 }
 
+#[inline]
+fn run_tasks() {
+    lifecycle_ctx().run_tasks()
+}
+
 #[inline]
 fn foreach_rev<T>(func: T) -> Result<()>
 where
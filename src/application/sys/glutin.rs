@@ -1,3 +1,6 @@
+use std::sync::Once;
+use std::time::Instant;
+
 use crate::utils::time::Timestamp;
 
 pub fn timestamp() -> Timestamp {
@@ -9,7 +12,27 @@ pub fn timestamp() -> Timestamp {
     Timestamp::from_millis(ms)
 }
 
-pub(crate) fn init() {}
+static EPOCH_INIT: Once = Once::new();
+static mut EPOCH: Option<Instant> = None;
+
+fn epoch() -> Instant {
+    unsafe {
+        EPOCH_INIT.call_once(|| EPOCH = Some(Instant::now()));
+        EPOCH.unwrap()
+    }
+}
+
+/// Returns the microseconds since an unspecified epoch. It's monotonic, and never goes
+/// backwards when the system clock is adjusted.
+pub fn micros() -> u64 {
+    let duration = Instant::now().duration_since(epoch());
+    u64::from(duration.subsec_micros()) + duration.as_secs() * 1_000_000
+}
+
+pub(crate) fn init() {
+    // Fixes the epoch at the start of process.
+    epoch();
+}
 
 pub(crate) fn run_forever<F, F2>(mut advance: F, mut finished: F2) -> Result<(), failure::Error>
 where
@@ -2,8 +2,57 @@
 mod glutin;
 #[cfg(not(target_arch = "wasm32"))]
 pub use self::glutin::*;
+#[cfg(not(target_arch = "wasm32"))]
+use self::glutin as platform;
 
 #[cfg(target_arch = "wasm32")]
 mod web;
 #[cfg(target_arch = "wasm32")]
 pub use self::web::*;
+#[cfg(target_arch = "wasm32")]
+use self::web as platform;
+
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::Duration;
+
+use crate::utils::time::Timestamp;
+
+// The fake clock in microseconds, which replaces the system clock when enabled.
+static FAKE_CLOCK_ENABLED: AtomicBool = AtomicBool::new(false);
+static FAKE_CLOCK: AtomicU64 = AtomicU64::new(0);
+
+/// Returns the current timestamp, it comes from the fake clock if enabled.
+pub fn timestamp() -> Timestamp {
+    if FAKE_CLOCK_ENABLED.load(Ordering::Acquire) {
+        Timestamp::from_millis(FAKE_CLOCK.load(Ordering::Acquire) / 1000)
+    } else {
+        platform::timestamp()
+    }
+}
+
+/// Returns the microseconds since an unspecified epoch, it comes from the fake clock if
+/// enabled.
+pub fn precise_timestamp() -> u64 {
+    if FAKE_CLOCK_ENABLED.load(Ordering::Acquire) {
+        FAKE_CLOCK.load(Ordering::Acquire)
+    } else {
+        platform::micros()
+    }
+}
+
+/// Replaces the system clock with a fake clock which only advances manually.
+pub(crate) fn enable_fake_clock() {
+    FAKE_CLOCK.store(platform::timestamp().as_millis() * 1000, Ordering::Release);
+    FAKE_CLOCK_ENABLED.store(true, Ordering::Release);
+}
+
+/// Restores the system clock.
+pub(crate) fn disable_fake_clock() {
+    FAKE_CLOCK_ENABLED.store(false, Ordering::Release);
+}
+
+/// Advances the fake clock by `delta`.
+pub(crate) fn advance_fake_clock(delta: Duration) {
+    let micros = delta.as_secs() * 1_000_000 + u64::from(delta.subsec_micros());
+    FAKE_CLOCK.fetch_add(micros, Ordering::AcqRel);
+}
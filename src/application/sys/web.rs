@@ -16,9 +16,21 @@ pub fn timestamp() -> Timestamp {
     Timestamp::from_millis(ms as u64)
 }
 
+/// Returns the microseconds since an unspecified epoch.
+pub fn micros() -> u64 {
+    let ms = web_sys::window()
+        .expect("should have a window in this context")
+        .performance()
+        .expect("performance should be available")
+        .now();
+
+    (ms * 1000.0) as u64
+}
+
 pub(crate) fn init() {
     std::panic::set_hook(Box::new(console_error_panic_hook::hook));
-    log::set_boxed_logger(Box::new(WebBrowserLogger {})).unwrap();
+    let logger = crate::crash::CrashLogger::new(WebBrowserLogger {});
+    log::set_boxed_logger(Box::new(logger)).unwrap();
     log::set_max_level(log::LevelFilter::Info);
 }
 
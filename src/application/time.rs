@@ -8,6 +8,8 @@ use std::time::Duration;
 use crate::application::{LifecycleListener, LifecycleListenerHandle};
 use crate::utils::time::Timestamp;
 
+use super::ins::Inspectable;
+use super::timer::{Clock, Timer, TimerSystem};
 use super::Params;
 
 pub struct TimeSystem {
@@ -21,6 +23,114 @@ struct TimeStateShared {
     max_inactive_fps: RwLock<u32>,
     smoothing_step: RwLock<usize>,
     timestep: RwLock<Duration>,
+    fixed_timestep: RwLock<Duration>,
+    max_fixed_steps: RwLock<u32>,
+    fixed: RwLock<FixedSteps>,
+    time_scale: RwLock<f32>,
+    paused: RwLock<bool>,
+    clocks: RwLock<Clocks>,
+    timers: TimerSystem,
+    // The elapsed time of recent frames, the latest one comes first.
+    history: RwLock<VecDeque<Duration>>,
+}
+
+// The number of recent frames kept for inspection.
+const HISTORY_FRAMES: usize = 60;
+
+impl Inspectable for TimeStateShared {
+    fn inspect(&self) -> serde_json::Value {
+        let clocks = *self.clocks.read().unwrap();
+        let history: Vec<_> = self
+            .history
+            .read()
+            .unwrap()
+            .iter()
+            .map(|v| millis(*v))
+            .collect();
+
+        json!({
+            "frame_time_ms": millis(*self.timestep.read().unwrap()),
+            "frame_times_ms": history,
+            "real_time_ms": millis(clocks.real),
+            "game_time_ms": millis(clocks.game),
+            "time_scale": *self.time_scale.read().unwrap(),
+            "paused": *self.paused.read().unwrap(),
+            "min_fps": *self.min_fps.read().unwrap(),
+            "max_fps": *self.max_fps.read().unwrap(),
+            "fixed_timestep_ms": millis(*self.fixed_timestep.read().unwrap()),
+            "fixed_steps": self.fixed.read().unwrap().steps,
+            "timers": self.timers.names(),
+        })
+    }
+
+    fn commands(&self) -> Vec<String> {
+        ["set_time_scale", "pause", "resume", "set_max_fps"]
+            .iter()
+            .map(|v| v.to_string())
+            .collect()
+    }
+
+    fn command(
+        &self,
+        name: &str,
+        args: &serde_json::Value,
+    ) -> crate::errors::Result<serde_json::Value> {
+        match name {
+            "set_time_scale" => {
+                let scale = args
+                    .as_f64()
+                    .ok_or_else(|| format_err!("The time scale should be a number."))?;
+                let scale = (scale as f32).max(0.0);
+                if !set_cvar("time_scale", scale) {
+                    *self.time_scale.write().unwrap() = scale;
+                }
+            }
+            "pause" => *self.paused.write().unwrap() = true,
+            "resume" => *self.paused.write().unwrap() = false,
+            "set_max_fps" => {
+                let fps = args
+                    .as_u64()
+                    .ok_or_else(|| format_err!("The max FPS should be a unsigned integer."))?;
+                if !set_cvar("max_fps", fps as u32) {
+                    *self.max_fps.write().unwrap() = fps as u32;
+                }
+            }
+            _ => bail!("Command {} is not supported.", name),
+        }
+
+        Ok(serde_json::Value::Null)
+    }
+}
+
+// Sets the built-in cvar, which applies the value with its callback, so the console keeps
+// showing the current one. Returns false if the cvar is not registered.
+fn set_cvar<T: Into<crate::console::CVarValue>>(name: &str, value: T) -> bool {
+    crate::console::valid()
+        && crate::console::cvar(name).is_some()
+        && crate::console::set_cvar(name, value).is_ok()
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Clocks {
+    real: Duration,
+    game: Duration,
+    game_timestep: Duration,
+}
+
+impl Clocks {
+    #[inline]
+    fn get(&self, clock: Clock) -> Duration {
+        match clock {
+            Clock::Real => self.real,
+            Clock::Game => self.game,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct FixedSteps {
+    steps: u32,
+    alpha: f32,
 }
 
 struct TimeState {
@@ -29,8 +139,10 @@ struct TimeState {
     max_inactive_fps: u32,
     smoothing_step: usize,
     timestep: Duration,
+    accumulator: Duration,
     previous_timesteps: VecDeque<Duration>,
     last_frame_timepoint: Timestamp,
+    fake_clock: Option<Duration>,
     shared: Arc<TimeStateShared>,
 }
 
@@ -42,6 +154,13 @@ impl LifecycleListener for TimeState {
         self.max_inactive_fps = *self.shared.max_inactive_fps.read().unwrap();
         self.smoothing_step = *self.shared.smoothing_step.read().unwrap();
 
+        // The fake clock advances by a fixed delta per frame, and never waits.
+        if let Some(delta) = self.fake_clock {
+            super::sys::advance_fake_clock(delta);
+            self.last_frame_timepoint = Timestamp::now();
+            return self.step(delta);
+        }
+
         // Perform waiting loop if maximum fps set, cooperatively gives up
         // a timeslice to the OS scheduler.
         if self.max_fps > 0 {
@@ -66,6 +185,13 @@ impl LifecycleListener for TimeState {
             );
         }
 
+        self.step(elapsed)
+    }
+}
+
+impl TimeState {
+    // Steps the timestep, clocks, fixed updates and timers with the elapsed time of last frame.
+    fn step(&mut self, elapsed: Duration) -> crate::errors::Result<()> {
         // Perform timestep smoothing.
         if self.smoothing_step > 0 {
             self.previous_timesteps.push_front(elapsed);
@@ -85,24 +211,104 @@ impl LifecycleListener for TimeState {
         }
 
         *self.shared.timestep.write().unwrap() = self.timestep;
+
+        {
+            let mut history = self.shared.history.write().unwrap();
+            history.push_front(elapsed);
+            history.truncate(HISTORY_FRAMES);
+        }
+
+        // Advances the clocks, the game time is scaled and stops when paused.
+        let scale = if *self.shared.paused.read().unwrap() {
+            0.0
+        } else {
+            *self.shared.time_scale.read().unwrap()
+        };
+
+        let game_elapsed = scaled(elapsed, scale);
+        let clocks = {
+            let mut clocks = self.shared.clocks.write().unwrap();
+            clocks.real += elapsed;
+            clocks.game += game_elapsed;
+            clocks.game_timestep = scaled(self.timestep, scale);
+            *clocks
+        };
+
+        self.accumulate(game_elapsed);
+        self.shared.timers.advance(|v| clocks.get(v));
         Ok(())
     }
+
+    // Accumulates the elapsed time, and calculates how many fixed steps should be taken
+    // in this frame.
+    fn accumulate(&mut self, elapsed: Duration) {
+        let step = nanos(*self.shared.fixed_timestep.read().unwrap());
+        let max_steps = *self.shared.max_fixed_steps.read().unwrap();
+
+        let mut fixed = FixedSteps::default();
+        if step > 0 {
+            let mut accumulator = nanos(self.accumulator) + nanos(elapsed);
+            let steps = accumulator / step;
+
+            if max_steps > 0 && steps > u64::from(max_steps) {
+                // Drops the time we could not catch up with to avoid the spiral of death.
+                fixed.steps = max_steps;
+                accumulator %= step;
+            } else {
+                fixed.steps = steps as u32;
+                accumulator -= steps * step;
+            }
+
+            fixed.alpha = accumulator as f32 / step as f32;
+            self.accumulator = Duration::from_nanos(accumulator);
+        }
+
+        *self.shared.fixed.write().unwrap() = fixed;
+    }
+}
+
+#[inline]
+fn nanos(v: Duration) -> u64 {
+    v.as_secs() * 1_000_000_000 + u64::from(v.subsec_nanos())
+}
+
+#[inline]
+fn millis(v: Duration) -> f64 {
+    nanos(v) as f64 / 1_000_000.0
+}
+
+#[inline]
+fn scaled(v: Duration, scale: f32) -> Duration {
+    Duration::from_nanos((nanos(v) as f64 * f64::from(scale.max(0.0))) as u64)
 }
 
 impl Drop for TimeSystem {
     fn drop(&mut self) {
         crate::application::detach(self.lis);
+        super::sys::disable_fake_clock();
     }
 }
 
 impl TimeSystem {
     pub fn new(setup: &Params) -> Self {
+        if setup.fake_clock.is_some() {
+            super::sys::enable_fake_clock();
+        }
+
         let shared = Arc::new(TimeStateShared {
             min_fps: RwLock::new(setup.min_fps),
             max_fps: RwLock::new(setup.max_fps),
             max_inactive_fps: RwLock::new(setup.max_inactive_fps),
             smoothing_step: RwLock::new(setup.time_smooth_step as usize),
             timestep: RwLock::new(Duration::new(0, 0)),
+            fixed_timestep: RwLock::new(setup.fixed_timestep),
+            max_fixed_steps: RwLock::new(setup.max_fixed_steps),
+            fixed: RwLock::new(FixedSteps::default()),
+            time_scale: RwLock::new(1.0),
+            paused: RwLock::new(false),
+            clocks: RwLock::new(Clocks::default()),
+            timers: TimerSystem::new(),
+            history: RwLock::new(VecDeque::with_capacity(HISTORY_FRAMES)),
         });
 
         let state = TimeState {
@@ -112,7 +318,9 @@ impl TimeSystem {
             smoothing_step: setup.time_smooth_step as usize,
             previous_timesteps: VecDeque::new(),
             timestep: Duration::new(0, 0),
+            accumulator: Duration::new(0, 0),
             last_frame_timepoint: Timestamp::now(),
+            fake_clock: setup.fake_clock,
             shared: shared.clone(),
         };
 
@@ -122,6 +330,10 @@ impl TimeSystem {
         }
     }
 
+    pub(crate) fn inspectable(&self) -> Arc<impl Inspectable> {
+        self.shared.clone()
+    }
+
     /// Set minimum frames per second. If fps goes lower than this, time will
     /// appear to slow. This is useful for some subsystems required strict minimum
     /// time step per frame, such like Collision checks.
@@ -150,6 +362,94 @@ impl TimeSystem {
         *self.shared.smoothing_step.write().unwrap() = step as usize;
     }
 
+    /// Sets the timestep of `on_fixed_update`, it's disabled if the timestep is zero.
+    #[inline]
+    pub fn set_fixed_timestep(&self, timestep: Duration) {
+        *self.shared.fixed_timestep.write().unwrap() = timestep;
+    }
+
+    /// Gets the timestep of `on_fixed_update`.
+    #[inline]
+    pub fn fixed_timestep(&self) -> Duration {
+        *self.shared.fixed_timestep.read().unwrap()
+    }
+
+    /// Sets the maximum number of fixed steps per frame.
+    #[inline]
+    pub fn set_max_fixed_steps(&self, steps: u32) {
+        *self.shared.max_fixed_steps.write().unwrap() = steps;
+    }
+
+    /// Gets the number of fixed steps taken in current frame.
+    #[inline]
+    pub fn fixed_steps(&self) -> u32 {
+        self.shared.fixed.read().unwrap().steps
+    }
+
+    /// Gets the interpolation alpha between the last two fixed steps.
+    #[inline]
+    pub fn fixed_alpha(&self) -> f32 {
+        self.shared.fixed.read().unwrap().alpha
+    }
+
+    /// Sets the scale of game time, e.g. 0.5 for slow motion.
+    #[inline]
+    pub fn set_time_scale(&self, scale: f32) {
+        *self.shared.time_scale.write().unwrap() = scale.max(0.0);
+    }
+
+    /// Gets the scale of game time.
+    #[inline]
+    pub fn time_scale(&self) -> f32 {
+        *self.shared.time_scale.read().unwrap()
+    }
+
+    /// Pauses or resumes the game time.
+    #[inline]
+    pub fn set_paused(&self, paused: bool) {
+        *self.shared.paused.write().unwrap() = paused;
+    }
+
+    /// Checks if the game time is paused.
+    #[inline]
+    pub fn paused(&self) -> bool {
+        *self.shared.paused.read().unwrap()
+    }
+
+    /// Gets the time of `clock` since the engine starts.
+    #[inline]
+    pub fn time(&self, clock: Clock) -> Duration {
+        self.shared.clocks.read().unwrap().get(clock)
+    }
+
+    /// Gets the scaled duration of last frame in game time.
+    #[inline]
+    pub fn game_frame_duration(&self) -> Duration {
+        self.shared.clocks.read().unwrap().game_timestep
+    }
+
+    /// Adds a named timer, the timer with the same name is replaced.
+    pub fn add_timer<T>(&self, name: String, timer: Timer, func: T)
+    where
+        T: FnMut() + Send + 'static,
+    {
+        let now = self.time(timer.clock);
+        self.shared.timers.add(name, timer, now, Box::new(func));
+    }
+
+    /// Removes a named timer, returns true if it exists.
+    #[inline]
+    pub fn remove_timer(&self, name: &str) -> bool {
+        self.shared.timers.remove(name)
+    }
+
+    /// Gets the remaining time before the named timer fires.
+    #[inline]
+    pub fn timer_remaining(&self, name: &str) -> Option<Duration> {
+        let clocks = *self.shared.clocks.read().unwrap();
+        self.shared.timers.remaining(name, |v| clocks.get(v))
+    }
+
     /// Gets current fps.
     #[inline]
     pub fn fps(&self) -> u32 {
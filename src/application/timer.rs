@@ -0,0 +1,174 @@
+//! Named timers and countdowns which are fired from the lifecycle.
+
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// The clock which drives timers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Clock {
+    /// The wall-clock time since the engine starts. It's not affected by time scale
+    /// or pausing.
+    Real,
+    /// The game time, which is scaled by the time scale and stops when paused.
+    Game,
+}
+
+/// The settings of a timer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timer {
+    /// The duration before the timer fires.
+    pub duration: Duration,
+    /// Restarts the timer after it fires.
+    pub repeat: bool,
+    /// The clock which drives this timer.
+    pub clock: Clock,
+}
+
+impl Timer {
+    /// Creates a countdown which fires once after `duration` of game time.
+    pub fn once(duration: Duration) -> Self {
+        Timer {
+            duration,
+            repeat: false,
+            clock: Clock::Game,
+        }
+    }
+
+    /// Creates a timer which fires every `duration` of game time.
+    pub fn repeat(duration: Duration) -> Self {
+        Timer {
+            duration,
+            repeat: true,
+            clock: Clock::Game,
+        }
+    }
+
+    /// Drives this timer with `clock`.
+    #[inline]
+    pub fn with_clock(mut self, clock: Clock) -> Self {
+        self.clock = clock;
+        self
+    }
+}
+
+type TimerFn = Box<dyn FnMut() + Send>;
+
+struct TimerState {
+    id: usize,
+    name: String,
+    timer: Timer,
+    deadline: Duration,
+    // Taken out while it's being fired.
+    func: Option<TimerFn>,
+}
+
+#[derive(Default)]
+struct Timers {
+    counter: usize,
+    timers: Vec<TimerState>,
+}
+
+#[derive(Default)]
+pub struct TimerSystem {
+    timers: Mutex<Timers>,
+}
+
+impl TimerSystem {
+    pub fn new() -> Self {
+        TimerSystem::default()
+    }
+
+    /// Adds a named timer, it replaces the timer with the same name if exists.
+    pub fn add(&self, name: String, timer: Timer, now: Duration, func: TimerFn) {
+        let mut timers = self.timers.lock().unwrap();
+        timers.timers.retain(|v| v.name != name);
+        timers.counter += 1;
+
+        let id = timers.counter;
+        timers.timers.push(TimerState {
+            id,
+            name,
+            timer,
+            deadline: now + timer.duration,
+            func: Some(func),
+        });
+    }
+
+    /// Removes a named timer, returns true if it exists.
+    pub fn remove(&self, name: &str) -> bool {
+        let mut timers = self.timers.lock().unwrap();
+        let len = timers.timers.len();
+        timers.timers.retain(|v| v.name != name);
+        timers.timers.len() != len
+    }
+
+    /// Gets the names of timers.
+    pub fn names(&self) -> Vec<String> {
+        let timers = self.timers.lock().unwrap();
+        timers.timers.iter().map(|v| v.name.clone()).collect()
+    }
+
+    /// Gets the remaining time before the named timer fires.
+    pub fn remaining<T>(&self, name: &str, now: T) -> Option<Duration>
+    where
+        T: Fn(Clock) -> Duration,
+    {
+        let timers = self.timers.lock().unwrap();
+        timers
+            .timers
+            .iter()
+            .find(|v| v.name == name)
+            .map(|v| checked_sub(v.deadline, now(v.timer.clock)))
+    }
+
+    /// Fires all the timers that have expired. Every timer fires at most once per call.
+    pub fn advance<T>(&self, now: T)
+    where
+        T: Fn(Clock) -> Duration,
+    {
+        let mut fired = Vec::new();
+
+        {
+            let mut timers = self.timers.lock().unwrap();
+            let mut i = 0;
+
+            while i < timers.timers.len() {
+                let v = &mut timers.timers[i];
+                if now(v.timer.clock) < v.deadline {
+                    i += 1;
+                    continue;
+                }
+
+                if v.timer.repeat {
+                    v.deadline += v.timer.duration;
+                    fired.push((v.id, v.func.take()));
+                    i += 1;
+                } else {
+                    let v = timers.timers.remove(i);
+                    fired.push((v.id, v.func));
+                }
+            }
+        }
+
+        // Callbacks are invoked without the lock, so they could add or remove timers.
+        for (id, func) in fired {
+            if let Some(mut func) = func {
+                func();
+
+                let mut timers = self.timers.lock().unwrap();
+                if let Some(v) = timers.timers.iter_mut().find(|v| v.id == id) {
+                    v.func = Some(func);
+                }
+            }
+        }
+    }
+}
+
+#[inline]
+fn checked_sub(lhs: Duration, rhs: Duration) -> Duration {
+    if lhs > rhs {
+        lhs - rhs
+    } else {
+        Duration::new(0, 0)
+    }
+}
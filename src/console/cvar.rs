@@ -0,0 +1,241 @@
+use std::fmt;
+
+use crate::errors::*;
+
+/// The typed value of console variable.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum CVarValue {
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Str(String),
+}
+
+impl CVarValue {
+    /// Gets the name of value type.
+    pub fn kind(&self) -> &'static str {
+        match *self {
+            CVarValue::Bool(_) => "bool",
+            CVarValue::Int(_) => "int",
+            CVarValue::Float(_) => "float",
+            CVarValue::Str(_) => "string",
+        }
+    }
+
+    /// Parses the text into a value with the same type as `self`.
+    pub fn parse_as(&self, text: &str) -> Result<CVarValue> {
+        let v = match *self {
+            CVarValue::Bool(_) => match text {
+                "1" | "true" | "on" => CVarValue::Bool(true),
+                "0" | "false" | "off" => CVarValue::Bool(false),
+                _ => bail!("Expects bool value, found {}.", text),
+            },
+            CVarValue::Int(_) => CVarValue::Int(text.parse()?),
+            CVarValue::Float(_) => CVarValue::Float(text.parse()?),
+            CVarValue::Str(_) => CVarValue::Str(text.to_owned()),
+        };
+
+        Ok(v)
+    }
+
+    /// Converts the value into the same type as `self`. Integers are widened into floats,
+    /// and strings are parsed.
+    pub fn coerce(&self, v: CVarValue) -> Result<CVarValue> {
+        match (self, v) {
+            (CVarValue::Float(_), CVarValue::Int(v)) => Ok(CVarValue::Float(v as f64)),
+            (_, CVarValue::Str(ref v)) => self.parse_as(v),
+            (lhs, rhs) => {
+                if lhs.kind() != rhs.kind() {
+                    bail!("Expects {} value, found {}.", lhs.kind(), rhs);
+                }
+
+                Ok(rhs)
+            }
+        }
+    }
+
+    /// Formats the value without quotes, which could be parsed back with `parse_as`.
+    pub fn text(&self) -> String {
+        match *self {
+            CVarValue::Str(ref v) => v.clone(),
+            ref v => v.to_string(),
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match *self {
+            CVarValue::Bool(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    pub fn as_i64(&self) -> Option<i64> {
+        match *self {
+            CVarValue::Int(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match *self {
+            CVarValue::Int(v) => Some(v as f64),
+            CVarValue::Float(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match *self {
+            CVarValue::Str(ref v) => Some(v),
+            _ => None,
+        }
+    }
+}
+
+/// Formats the value in the console syntax, strings are quoted.
+impl fmt::Display for CVarValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            CVarValue::Bool(v) => write!(f, "{}", v),
+            CVarValue::Int(v) => write!(f, "{}", v),
+            CVarValue::Float(v) => write!(f, "{:?}", v),
+            CVarValue::Str(ref v) => write!(f, "{}", quote(v)),
+        }
+    }
+}
+
+impl From<bool> for CVarValue {
+    fn from(v: bool) -> Self {
+        CVarValue::Bool(v)
+    }
+}
+
+impl From<i32> for CVarValue {
+    fn from(v: i32) -> Self {
+        CVarValue::Int(i64::from(v))
+    }
+}
+
+impl From<u32> for CVarValue {
+    fn from(v: u32) -> Self {
+        CVarValue::Int(i64::from(v))
+    }
+}
+
+impl From<i64> for CVarValue {
+    fn from(v: i64) -> Self {
+        CVarValue::Int(v)
+    }
+}
+
+impl From<f32> for CVarValue {
+    fn from(v: f32) -> Self {
+        CVarValue::Float(f64::from(v))
+    }
+}
+
+impl From<f64> for CVarValue {
+    fn from(v: f64) -> Self {
+        CVarValue::Float(v)
+    }
+}
+
+impl<'a> From<&'a str> for CVarValue {
+    fn from(v: &'a str) -> Self {
+        CVarValue::Str(v.to_owned())
+    }
+}
+
+impl From<String> for CVarValue {
+    fn from(v: String) -> Self {
+        CVarValue::Str(v)
+    }
+}
+
+/// Quotes the text with escaped `"` and `\`.
+pub fn quote(text: &str) -> String {
+    let mut quoted = String::with_capacity(text.len() + 2);
+    quoted.push('"');
+
+    for c in text.chars() {
+        if c == '"' || c == '\\' {
+            quoted.push('\\');
+        }
+
+        quoted.push(c);
+    }
+
+    quoted.push('"');
+    quoted
+}
+
+/// Splits the source into statements of arguments. Statements are separated by newlines
+/// or `;`, arguments by whitespaces. Arguments could be quoted with `"`, and the rest of
+/// line after `//` or `#` is ignored.
+pub fn tokenize(source: &str) -> Result<Vec<Vec<String>>> {
+    let mut statements = Vec::new();
+    let mut args = Vec::new();
+    let mut chars = source.chars().peekable();
+
+    loop {
+        match chars.peek().cloned() {
+            None | Some('\n') | Some(';') => {
+                if !args.is_empty() {
+                    statements.push(args);
+                    args = Vec::new();
+                }
+
+                if chars.next().is_none() {
+                    return Ok(statements);
+                }
+            }
+            Some('#') => skip_line(&mut chars),
+            Some('/') if chars.clone().nth(1) == Some('/') => skip_line(&mut chars),
+            Some(c) if c.is_whitespace() => {
+                chars.next();
+            }
+            Some('"') => {
+                chars.next();
+
+                let mut arg = String::new();
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some(c) => arg.push(c),
+                            None => bail!("Unterminated quoted string."),
+                        },
+                        Some(c) => arg.push(c),
+                        None => bail!("Unterminated quoted string."),
+                    }
+                }
+
+                args.push(arg);
+            }
+            Some(_) => {
+                let mut arg = String::new();
+                while let Some(c) = chars.peek().cloned() {
+                    if c.is_whitespace() || c == ';' || c == '"' {
+                        break;
+                    }
+
+                    arg.push(c);
+                    chars.next();
+                }
+
+                args.push(arg);
+            }
+        }
+    }
+}
+
+fn skip_line<T: Iterator<Item = char>>(chars: &mut std::iter::Peekable<T>) {
+    while let Some(&c) = chars.peek() {
+        if c == '\n' {
+            return;
+        }
+
+        chars.next();
+    }
+}
@@ -0,0 +1,227 @@
+//! In-game developer console with a registry of commands and typed console variables.
+//!
+//! Systems register named commands and cvars with help text. They could be invoked from
+//! the text overlay, from the inspector server (as `console` inspectable), or from script
+//! files loaded with the resource system.
+//!
+//! ```rust,ignore
+//! console::register_cvar_with_callback("god_mode", "Makes player invincible.", false, |v| {
+//!     ...
+//! });
+//!
+//! console::register_command("spawn", "Spawns a monster at player.", |args| {
+//!     ...
+//!     Ok(String::new())
+//! });
+//!
+//! console::execute("god_mode true; spawn goblin")?;
+//! ```
+//!
+//! # Syntax
+//!
+//! Statements are separated by newlines or `;`, and arguments by whitespaces. Arguments
+//! could be quoted with `"`, and the rest of line after `//` or `#` is ignored. The name of
+//! cvar alone prints its value, and the name followed with a value sets it. Besides the
+//! registered ones, there are built-in commands `help`, `set`, `echo`, `exec` and `save`.
+//!
+//! The cvars of engine like `max_fps` and `time_scale` are registered by default, so they
+//! could be tuned without code changes.
+//!
+//! # Config and Scripts
+//!
+//! The modified cvars are saved into the config file in `ConsoleParams` when the engine
+//! exits, and restored in next run. Values of the cvars that are not registered yet are
+//! kept, and applied once they are registered. The startup script in `ConsoleParams` is
+//! loaded from the resource system after manifests are ready.
+//!
+//! # Overlay
+//!
+//! The overlay is toggled with `ConsoleParams::toggle_key`. When its visible, the console
+//! edits and submits the input line with keyboard. Since there is no text rendering in
+//! crayon, the snapshot of overlay is available with `console::overlay` for UIs to draw.
+
+mod cvar;
+mod system;
+
+pub use self::cvar::CVarValue;
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use crate::errors::*;
+use crate::input::prelude::Key;
+
+use self::inside::{ctx, CTX};
+
+/// The setup parameters of console system.
+#[derive(Debug, Clone)]
+pub struct ConsoleParams {
+    /// The config file which the modified cvars are saved into and restored from.
+    pub config: Option<PathBuf>,
+    /// The script loaded from resource system on startup.
+    pub script: Option<String>,
+    /// The key which toggles the overlay.
+    pub toggle_key: Option<Key>,
+    /// The maximum lines of output kept in overlay.
+    pub max_lines: usize,
+}
+
+impl Default for ConsoleParams {
+    fn default() -> Self {
+        ConsoleParams {
+            config: None,
+            script: None,
+            toggle_key: Some(Key::F1),
+            max_lines: 256,
+        }
+    }
+}
+
+/// The snapshot of overlay.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ConsoleOverlay {
+    pub visible: bool,
+    /// The line being edited.
+    pub input: String,
+    /// The submitted lines and their outputs.
+    pub lines: Vec<String>,
+}
+
+/// Checks if the console system is enabled.
+#[inline]
+pub fn valid() -> bool {
+    unsafe { !CTX.is_null() }
+}
+
+/// Registers a command, the old one with the same name is replaced. It's invoked with the
+/// arguments, and returns the text printed to the console.
+///
+/// Notes that commands from scripts and inspector server are executed on the main thread too,
+/// during the update after they arrive.
+#[inline]
+pub fn register_command<T>(name: &str, help: &str, func: T)
+where
+    T: Fn(&[&str]) -> Result<String> + Send + Sync + 'static,
+{
+    ctx().register_command(name, help, func)
+}
+
+/// Unregisters a command, returns true if it exists.
+#[inline]
+pub fn unregister_command(name: &str) -> bool {
+    ctx().unregister_command(name)
+}
+
+/// Registers a cvar with default value, the old one with the same name is replaced.
+#[inline]
+pub fn register_cvar<T: Into<CVarValue>>(name: &str, help: &str, default: T) {
+    ctx().register_cvar(name, help, default.into(), None)
+}
+
+/// Registers a cvar with callback, which is invoked when its value changes. If there is
+/// a value restored from config, the callback is invoked immediately.
+#[inline]
+pub fn register_cvar_with_callback<T1, T2>(name: &str, help: &str, default: T1, func: T2)
+where
+    T1: Into<CVarValue>,
+    T2: Fn(&CVarValue) + Send + Sync + 'static,
+{
+    ctx().register_cvar(name, help, default.into(), Some(Arc::new(func)))
+}
+
+/// Unregisters a cvar, returns true if it exists. The modified value is kept, and
+/// restored when it is registered again.
+#[inline]
+pub fn unregister_cvar(name: &str) -> bool {
+    ctx().unregister_cvar(name)
+}
+
+/// Gets the value of cvar.
+#[inline]
+pub fn cvar(name: &str) -> Option<CVarValue> {
+    ctx().cvar(name)
+}
+
+/// Sets the value of cvar, which is converted into the type of its default value. If the
+/// cvar is not registered yet, the value is applied on registration.
+#[inline]
+pub fn set_cvar<T: Into<CVarValue>>(name: &str, value: T) -> Result<()> {
+    ctx().set_cvar(name, value.into())
+}
+
+/// Executes console commands, and returns the printed text.
+#[inline]
+pub fn execute(source: &str) -> Result<String> {
+    ctx().execute(source)
+}
+
+/// Loads script from the resource system asynchronously, and executes it line by line on the
+/// main thread once it's loaded. The failed lines are reported and skipped.
+#[inline]
+pub fn exec_script(filename: &str) -> Result<()> {
+    ctx().exec_script(filename)
+}
+
+/// Saves the modified cvars into the config file.
+#[inline]
+pub fn save_config() -> Result<()> {
+    ctx().save_config()
+}
+
+/// Appends text to the output of overlay.
+#[inline]
+pub fn print(text: &str) {
+    ctx().print(text)
+}
+
+/// Shows or hides the overlay.
+#[inline]
+pub fn set_visible(visible: bool) {
+    ctx().set_visible(visible)
+}
+
+/// Gets a snapshot of the overlay, which should be drawn by UIs.
+#[inline]
+pub fn overlay() -> ConsoleOverlay {
+    ctx().overlay()
+}
+
+pub(crate) mod inside {
+    use super::system::ConsoleSystem;
+    use super::ConsoleParams;
+
+    use crate::errors::*;
+
+    pub static mut CTX: *const ConsoleSystem = std::ptr::null();
+
+    #[inline]
+    pub fn ctx() -> &'static ConsoleSystem {
+        unsafe {
+            debug_assert!(
+                !CTX.is_null(),
+                "console system has not been initialized properly."
+            );
+
+            &*CTX
+        }
+    }
+
+    /// Setup the console system.
+    pub unsafe fn setup(params: ConsoleParams) -> Result<()> {
+        debug_assert!(CTX.is_null(), "duplicated setup of console system.");
+
+        let ctx = ConsoleSystem::new(params)?;
+        CTX = Box::into_raw(Box::new(ctx));
+        Ok(())
+    }
+
+    /// Discard the console system.
+    pub unsafe fn discard() {
+        if CTX.is_null() {
+            return;
+        }
+
+        drop(Box::from_raw(CTX as *mut ConsoleSystem));
+        CTX = std::ptr::null();
+    }
+}
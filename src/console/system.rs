@@ -0,0 +1,642 @@
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::{Arc, Mutex, RwLock, Weak};
+
+use serde_json::Value;
+
+use crate::application::ins::Inspectable;
+use crate::application::prelude::{LifecycleListener, LifecycleListenerHandle};
+use crate::errors::*;
+use crate::input::prelude::{InputEvent, Key};
+
+use super::cvar::{self, CVarValue};
+use super::{ConsoleOverlay, ConsoleParams};
+
+pub type CommandFn = dyn Fn(&[&str]) -> Result<String> + Send + Sync;
+pub type CVarFn = dyn Fn(&CVarValue) + Send + Sync;
+
+struct Command {
+    help: String,
+    func: Arc<CommandFn>,
+}
+
+struct CVar {
+    help: String,
+    default: CVarValue,
+    value: CVarValue,
+    on_change: Option<Arc<CVarFn>>,
+}
+
+#[derive(Default)]
+struct Registry {
+    commands: BTreeMap<String, Command>,
+    cvars: BTreeMap<String, CVar>,
+    // The values of cvars that are not registered yet, which are applied on registration.
+    pending: BTreeMap<String, String>,
+}
+
+struct Overlay {
+    visible: bool,
+    input: String,
+    lines: VecDeque<String>,
+    history: Vec<String>,
+    cursor: Option<usize>,
+}
+
+// The statements sent from inspector server or loaded from scripts, which are executed on
+// the main thread.
+enum Statement {
+    Exec(String),
+    Set(String, CVarValue),
+    Script(String, String),
+}
+
+pub struct ConsoleState {
+    params: ConsoleParams,
+    registry: RwLock<Registry>,
+    overlay: Mutex<Overlay>,
+    statements: Mutex<Vec<Statement>>,
+}
+
+impl ConsoleState {
+    fn register_command(&self, name: &str, help: &str, func: Arc<CommandFn>) {
+        let cmd = Command {
+            help: help.to_owned(),
+            func,
+        };
+
+        let mut registry = self.registry.write().unwrap();
+        registry.commands.insert(name.to_owned(), cmd);
+    }
+
+    fn register_cvar(&self, name: &str, help: &str, default: CVarValue, f: Option<Arc<CVarFn>>) {
+        let value = {
+            let mut registry = self.registry.write().unwrap();
+            let value = match registry.pending.remove(name) {
+                Some(text) => default.parse_as(&text).unwrap_or_else(|err| {
+                    warn!("[ConsoleSystem] Failed to restore cvar {}. {}", name, err);
+                    default.clone()
+                }),
+                None => default.clone(),
+            };
+
+            let cvar = CVar {
+                help: help.to_owned(),
+                default: default.clone(),
+                value: value.clone(),
+                on_change: f.clone(),
+            };
+
+            registry.cvars.insert(name.to_owned(), cvar);
+            value
+        };
+
+        // Callbacks are invoked without locks, so they could access console freely.
+        if value != default {
+            if let Some(f) = f {
+                f(&value);
+            }
+        }
+    }
+
+    fn unregister_cvar(&self, name: &str) -> bool {
+        let mut registry = self.registry.write().unwrap();
+        match registry.cvars.remove(name) {
+            Some(v) => {
+                // Keeps the modified value, so it could be saved and restored later.
+                if v.value != v.default {
+                    registry.pending.insert(name.to_owned(), v.value.text());
+                }
+
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn cvar(&self, name: &str) -> Option<CVarValue> {
+        let registry = self.registry.read().unwrap();
+        registry.cvars.get(name).map(|v| v.value.clone())
+    }
+
+    fn set_cvar(&self, name: &str, value: CVarValue) -> Result<()> {
+        let (value, f) = {
+            let mut registry = self.registry.write().unwrap();
+            match registry.cvars.get_mut(name) {
+                Some(cvar) => {
+                    let value = cvar.default.coerce(value)?;
+                    if cvar.value == value {
+                        return Ok(());
+                    }
+
+                    cvar.value = value.clone();
+                    (value, cvar.on_change.clone())
+                }
+                None => {
+                    registry.pending.insert(name.to_owned(), value.text());
+                    return Ok(());
+                }
+            }
+        };
+
+        if let Some(f) = f {
+            f(&value);
+        }
+
+        Ok(())
+    }
+
+    fn execute(&self, source: &str) -> Result<String> {
+        let mut outputs = Vec::new();
+        for args in cvar::tokenize(source)? {
+            let args: Vec<_> = args.iter().map(|v| v.as_str()).collect();
+            let output = self.execute_statement(&args)?;
+            if !output.is_empty() {
+                outputs.push(output);
+            }
+        }
+
+        Ok(outputs.join("\n"))
+    }
+
+    fn execute_statement(&self, args: &[&str]) -> Result<String> {
+        let func = {
+            let registry = self.registry.read().unwrap();
+            if let Some(cvar) = registry.cvars.get(args[0]) {
+                if args.len() == 1 {
+                    return Ok(format!("{} = {}", args[0], cvar.value));
+                }
+
+                None
+            } else if let Some(cmd) = registry.commands.get(args[0]) {
+                Some(cmd.func.clone())
+            } else {
+                bail!("Command or cvar {} is undefined.", args[0]);
+            }
+        };
+
+        match func {
+            Some(func) => func(&args[1..]),
+            None => {
+                if args.len() > 2 {
+                    bail!("Too many arguments for cvar {}.", args[0]);
+                }
+
+                self.set_cvar(args[0], CVarValue::Str(args[1].to_owned()))?;
+                Ok(String::new())
+            }
+        }
+    }
+
+    // Executes the source line by line, the failed lines are reported and skipped.
+    fn execute_script(&self, name: &str, source: &str) {
+        for (i, line) in source.lines().enumerate() {
+            if let Err(err) = self.execute(line) {
+                warn!("[ConsoleSystem] {}:{}: {}", name, i + 1, err);
+            }
+        }
+    }
+
+    fn help(&self, name: Option<&str>) -> Result<String> {
+        let registry = self.registry.read().unwrap();
+        if let Some(name) = name {
+            if let Some(v) = registry.commands.get(name) {
+                return Ok(format!("{} - {}", name, v.help));
+            }
+
+            if let Some(v) = registry.cvars.get(name) {
+                return Ok(format!(
+                    "{} = {} (default: {}) - {}",
+                    name, v.value, v.default, v.help
+                ));
+            }
+
+            bail!("Command or cvar {} is undefined.", name);
+        }
+
+        let mut lines = Vec::new();
+        for (k, v) in &registry.commands {
+            lines.push(format!("{} - {}", k, v.help));
+        }
+
+        for (k, v) in &registry.cvars {
+            lines.push(format!("{} = {} - {}", k, v.value, v.help));
+        }
+
+        Ok(lines.join("\n"))
+    }
+
+    // Serializes the modified cvars into script which sets them back.
+    fn config(&self) -> String {
+        let registry = self.registry.read().unwrap();
+        let mut lines = vec!["// Generated by crayon console.".to_owned()];
+
+        for (k, v) in &registry.cvars {
+            if v.value != v.default {
+                lines.push(format!("set {} {}", k, v.value));
+            }
+        }
+
+        for (k, v) in &registry.pending {
+            lines.push(format!("set {} {}", k, cvar::quote(v)));
+        }
+
+        lines.push(String::new());
+        lines.join("\n")
+    }
+
+    fn save_config(&self) -> Result<()> {
+        match self.params.config {
+            Some(ref path) => {
+                std::fs::write(path, self.config())?;
+                Ok(())
+            }
+            None => bail!("The config file of console is not specified."),
+        }
+    }
+
+    fn print(&self, text: &str) {
+        let mut overlay = self.overlay.lock().unwrap();
+        for line in text.lines() {
+            if overlay.lines.len() >= self.params.max_lines {
+                overlay.lines.pop_front();
+            }
+
+            overlay.lines.push_back(line.to_owned());
+        }
+    }
+
+    // Edits the input line of overlay, returns the submitted line.
+    fn edit(&self, event: InputEvent) -> Option<String> {
+        let mut overlay = self.overlay.lock().unwrap();
+
+        if let InputEvent::KeyboardPressed { key } = event {
+            if Some(key) == self.params.toggle_key {
+                overlay.visible = !overlay.visible;
+                return None;
+            }
+        }
+
+        if !overlay.visible {
+            return None;
+        }
+
+        match event {
+            InputEvent::KeyboardPressed { key } => match key {
+                Key::Escape => overlay.visible = false,
+                Key::Back => {
+                    overlay.input.pop();
+                }
+                Key::Up | Key::Down if !overlay.history.is_empty() => {
+                    let last = overlay.history.len() - 1;
+                    let cursor = match (overlay.cursor, key) {
+                        (None, Key::Up) => Some(last),
+                        (Some(v), Key::Up) => Some(v.saturating_sub(1)),
+                        (Some(v), Key::Down) if v < last => Some(v + 1),
+                        _ => None,
+                    };
+
+                    let input = cursor.map(|v| overlay.history[v].clone());
+                    overlay.cursor = cursor;
+                    overlay.input = input.unwrap_or_default();
+                }
+                Key::Return | Key::NumpadEnter => {
+                    let line = overlay.input.clone();
+                    overlay.input.clear();
+                    overlay.cursor = None;
+
+                    if !line.trim().is_empty() {
+                        overlay.history.push(line.clone());
+                        return Some(line);
+                    }
+                }
+                _ => {}
+            },
+            InputEvent::ReceivedCharacter { character } if !character.is_control() => {
+                overlay.input.push(character);
+            }
+            _ => {}
+        }
+
+        None
+    }
+
+    fn overlay(&self) -> ConsoleOverlay {
+        let overlay = self.overlay.lock().unwrap();
+        ConsoleOverlay {
+            visible: overlay.visible,
+            input: overlay.input.clone(),
+            lines: overlay.lines.iter().cloned().collect(),
+        }
+    }
+}
+
+impl LifecycleListener for Arc<ConsoleState> {
+    fn on_update(&mut self) -> Result<()> {
+        let statements: Vec<_> = self.statements.lock().unwrap().drain(..).collect();
+        for v in statements {
+            let result = match v {
+                Statement::Exec(source) => {
+                    self.print(&format!("> {}", source));
+                    self.execute(&source)
+                }
+                Statement::Set(name, value) => self.set_cvar(&name, value).map(|_| String::new()),
+                Statement::Script(name, source) => {
+                    self.execute_script(&name, &source);
+                    continue;
+                }
+            };
+
+            match result {
+                Ok(output) => self.print(&output),
+                Err(err) => self.print(&err.to_string()),
+            }
+        }
+
+        if !crate::input::valid() {
+            return Ok(());
+        }
+
+        for v in crate::input::events() {
+            if let Some(line) = self.edit(v.event) {
+                self.print(&format!("> {}", line));
+                match self.execute(&line) {
+                    Ok(output) => self.print(&output),
+                    Err(err) => self.print(&err.to_string()),
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Inspectable for ConsoleState {
+    fn inspect(&self) -> Value {
+        let registry = self.registry.read().unwrap();
+
+        let mut cvars = serde_json::Map::new();
+        for (k, v) in &registry.cvars {
+            let cvar = json!({
+                "value": v.value,
+                "default": v.default,
+                "help": v.help,
+            });
+
+            cvars.insert(k.clone(), cvar);
+        }
+
+        let commands: BTreeMap<_, _> = registry
+            .commands
+            .iter()
+            .map(|(k, v)| (k.clone(), v.help.clone()))
+            .collect();
+
+        json!({
+            "cvars": cvars,
+            "commands": commands,
+            "pending": registry.pending,
+        })
+    }
+
+    fn commands(&self) -> Vec<String> {
+        vec!["exec".to_owned(), "set".to_owned()]
+    }
+
+    // The statements are queued and executed on the main thread during next update, with
+    // their outputs printed to the console.
+    fn command(&self, name: &str, args: &Value) -> Result<Value> {
+        match name {
+            "exec" => match args.as_str() {
+                Some(v) => {
+                    cvar::tokenize(v)?;
+                    let statement = Statement::Exec(v.to_owned());
+                    self.statements.lock().unwrap().push(statement);
+                    Ok(Value::Null)
+                }
+                None => bail!("Expects the source of console commands."),
+            },
+            "set" => match (args["name"].as_str(), args.get("value")) {
+                (Some(name), Some(value)) => {
+                    let value = serde_json::from_value(value.clone())?;
+                    let statement = Statement::Set(name.to_owned(), value);
+                    self.statements.lock().unwrap().push(statement);
+                    Ok(Value::Null)
+                }
+                _ => bail!("Expects {{\"name\": .., \"value\": ..}}."),
+            },
+            _ => bail!("Command {} is not supported.", name),
+        }
+    }
+}
+
+pub struct ConsoleSystem {
+    state: Arc<ConsoleState>,
+    lifecycle: LifecycleListenerHandle,
+}
+
+impl Drop for ConsoleSystem {
+    fn drop(&mut self) {
+        crate::application::detach(self.lifecycle);
+
+        if self.state.params.config.is_some() {
+            if let Err(err) = self.save_config() {
+                warn!("[ConsoleSystem] Failed to save config. {}", err);
+            }
+        }
+    }
+}
+
+impl ConsoleSystem {
+    pub fn new(params: ConsoleParams) -> Result<Self> {
+        debug_assert!(crate::application::valid(), "");
+
+        let overlay = Overlay {
+            visible: false,
+            input: String::new(),
+            lines: VecDeque::new(),
+            history: Vec::new(),
+            cursor: None,
+        };
+
+        let state = Arc::new(ConsoleState {
+            params,
+            registry: RwLock::new(Registry::default()),
+            overlay: Mutex::new(overlay),
+            statements: Mutex::new(Vec::new()),
+        });
+
+        let sys = ConsoleSystem {
+            state: state.clone(),
+            lifecycle: crate::application::attach(state),
+        };
+
+        register_builtin_commands(&sys.state);
+
+        if let Some(ref path) = sys.state.params.config {
+            if path.exists() {
+                info!("[ConsoleSystem] Loading config from {:?}.", path);
+                let source = std::fs::read_to_string(path)?;
+                sys.state.execute_script(&path.to_string_lossy(), &source);
+            }
+        }
+
+        Ok(sys)
+    }
+
+    pub(crate) fn inspectable(&self) -> Arc<impl Inspectable> {
+        self.state.clone()
+    }
+
+    /// Registers a command, the old one with the same name is replaced.
+    pub fn register_command<T>(&self, name: &str, help: &str, func: T)
+    where
+        T: Fn(&[&str]) -> Result<String> + Send + Sync + 'static,
+    {
+        self.state.register_command(name, help, Arc::new(func));
+    }
+
+    /// Unregisters a command, returns true if it exists.
+    pub fn unregister_command(&self, name: &str) -> bool {
+        let mut registry = self.state.registry.write().unwrap();
+        registry.commands.remove(name).is_some()
+    }
+
+    /// Registers a cvar with optional callback which is invoked when its value changes.
+    pub fn register_cvar(&self, name: &str, help: &str, v: CVarValue, f: Option<Arc<CVarFn>>) {
+        self.state.register_cvar(name, help, v, f);
+    }
+
+    /// Unregisters a cvar, returns true if it exists.
+    pub fn unregister_cvar(&self, name: &str) -> bool {
+        self.state.unregister_cvar(name)
+    }
+
+    /// Gets the value of cvar.
+    pub fn cvar(&self, name: &str) -> Option<CVarValue> {
+        self.state.cvar(name)
+    }
+
+    /// Sets the value of cvar.
+    pub fn set_cvar(&self, name: &str, value: CVarValue) -> Result<()> {
+        self.state.set_cvar(name, value)
+    }
+
+    /// Executes console commands.
+    pub fn execute(&self, source: &str) -> Result<String> {
+        self.state.execute(source)
+    }
+
+    /// Loads script from resource system, and executes it on the main thread during the
+    /// update after it's loaded.
+    pub fn exec_script(&self, filename: &str) -> Result<()> {
+        exec_script(&self.state, filename)
+    }
+
+    /// Executes the startup script in `ConsoleParams` if exists.
+    pub fn exec_startup_script(&self) -> Result<()> {
+        if let Some(ref filename) = self.state.params.script {
+            self.exec_script(filename)?;
+        }
+
+        Ok(())
+    }
+
+    /// Saves the modified cvars into config file.
+    pub fn save_config(&self) -> Result<()> {
+        self.state.save_config()
+    }
+
+    /// Appends text to the output of overlay.
+    pub fn print(&self, text: &str) {
+        self.state.print(text);
+    }
+
+    /// Shows or hides the overlay.
+    pub fn set_visible(&self, visible: bool) {
+        self.state.overlay.lock().unwrap().visible = visible;
+    }
+
+    /// Gets a snapshot of the overlay.
+    pub fn overlay(&self) -> ConsoleOverlay {
+        self.state.overlay()
+    }
+}
+
+fn exec_script(state: &Arc<ConsoleState>, filename: &str) -> Result<()> {
+    let state = state.clone();
+    let name = filename.to_owned();
+
+    crate::res::load_from_with_callback(filename, move |rsp| match rsp {
+        Ok(bytes) => {
+            let source = String::from_utf8_lossy(&bytes).into_owned();
+            let statement = Statement::Script(name, source);
+            state.statements.lock().unwrap().push(statement);
+        }
+        Err(err) => warn!("[ConsoleSystem] Failed to load script {}. {}", name, err),
+    })
+}
+
+fn upgrade(state: &Weak<ConsoleState>) -> Result<Arc<ConsoleState>> {
+    match state.upgrade() {
+        Some(v) => Ok(v),
+        None => bail!("The console system has been discarded."),
+    }
+}
+
+// The built-in commands hold weak references, since they are owned by the state itself.
+fn register_builtin_commands(state: &Arc<ConsoleState>) {
+    let weak = Arc::downgrade(state);
+    let help = move |args: &[&str]| upgrade(&weak)?.help(args.first().cloned());
+    state.register_command(
+        "help",
+        "Prints the help of commands and cvars.",
+        Arc::new(help),
+    );
+
+    let weak = Arc::downgrade(state);
+    let set = move |args: &[&str]| {
+        if args.len() != 2 {
+            bail!("Usage: set <name> <value>");
+        }
+
+        upgrade(&weak)?.set_cvar(args[0], CVarValue::Str(args[1].to_owned()))?;
+        Ok(String::new())
+    };
+
+    state.register_command(
+        "set",
+        "Sets cvar, even if it's not registered yet.",
+        Arc::new(set),
+    );
+
+    let echo = |args: &[&str]| Ok(args.join(" "));
+    state.register_command("echo", "Prints the arguments.", Arc::new(echo));
+
+    let weak = Arc::downgrade(state);
+    let exec = move |args: &[&str]| {
+        if args.len() != 1 {
+            bail!("Usage: exec <filename>");
+        }
+
+        exec_script(&upgrade(&weak)?, args[0])?;
+        Ok(String::new())
+    };
+
+    state.register_command(
+        "exec",
+        "Executes script from resource system.",
+        Arc::new(exec),
+    );
+
+    let weak = Arc::downgrade(state);
+    let save = move |_: &[&str]| {
+        upgrade(&weak)?.save_config()?;
+        Ok(String::new())
+    };
+
+    state.register_command(
+        "save",
+        "Saves the modified cvars into config.",
+        Arc::new(save),
+    );
+}
@@ -0,0 +1,84 @@
+use std::collections::VecDeque;
+use std::sync::{Mutex, Once};
+
+use log::{Log, Metadata, Record};
+
+/// The maximum number of recent log lines kept for crash reports.
+pub const MAX_LOG_LINES: usize = 64;
+
+static INIT: Once = Once::new();
+static mut LOGS: *const Mutex<VecDeque<String>> = std::ptr::null();
+
+/// A logger which keeps the recent log lines for crash reports, and forwards the records
+/// to the inner logger.
+///
+/// ```rust,ignore
+/// let logger = env_logger::Builder::from_default_env().build();
+/// log::set_max_level(logger.filter());
+/// log::set_boxed_logger(Box::new(crash::CrashLogger::new(logger))).unwrap();
+/// ```
+pub struct CrashLogger<T: Log> {
+    inner: T,
+}
+
+impl<T: Log> CrashLogger<T> {
+    pub fn new(inner: T) -> Self {
+        INIT.call_once(|| unsafe {
+            LOGS = Box::into_raw(Box::new(Mutex::new(VecDeque::with_capacity(MAX_LOG_LINES))));
+        });
+
+        CrashLogger { inner }
+    }
+}
+
+impl<T: Log> Log for CrashLogger<T> {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        self.inner.enabled(metadata)
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.inner.enabled(record.metadata()) {
+            return;
+        }
+
+        if let Some(logs) = logs() {
+            let line = format!(
+                "[{}] {}: {}",
+                record.level(),
+                record.target(),
+                record.args()
+            );
+
+            if let Ok(mut logs) = logs.lock() {
+                if logs.len() >= MAX_LOG_LINES {
+                    logs.pop_front();
+                }
+
+                logs.push_back(line);
+            }
+        }
+
+        self.inner.log(record);
+    }
+
+    fn flush(&self) {
+        self.inner.flush();
+    }
+}
+
+fn logs() -> Option<&'static Mutex<VecDeque<String>>> {
+    unsafe { LOGS.as_ref() }
+}
+
+/// Checks if any `CrashLogger` has been created.
+pub fn installed() -> bool {
+    logs().is_some()
+}
+
+/// Gets the recent log lines, it never blocks since we might be panicking with the lock.
+pub fn lines() -> Vec<String> {
+    match logs().map(|v| v.try_lock()) {
+        Some(Ok(v)) => v.iter().cloned().collect(),
+        _ => Vec::new(),
+    }
+}
@@ -0,0 +1,117 @@
+//! Crash reporting with the context of frames.
+//!
+//! Panics from the main thread and the workers of scheduler are captured with a panic
+//! hook. A report containing backtrace, recent log lines, current frame number, the last
+//! lifecycle phases and engine params is written into the directory of `CrashParams`, and
+//! then the user callback is invoked before shutdown.
+//!
+//! ```rust,ignore
+//! crash::set_callback(|report| {
+//!     // Uploads the report.
+//!     ...
+//! });
+//! ```
+//!
+//! The log lines are only captured if the logger is wrapped with `CrashLogger`.
+//!
+//! Notes that the default `PanicHandler` of scheduler still aborts after the report of a
+//! panicking job is written.
+
+mod logs;
+mod system;
+
+pub use self::logs::{CrashLogger, MAX_LOG_LINES};
+pub use self::system::CrashReport;
+
+use std::path::PathBuf;
+
+use self::inside::{ctx, CTX};
+
+/// The setup parameters of crash system.
+///
+/// Notes that the log lines are only captured if the logger is wrapped with `CrashLogger`.
+/// It's done by the engine in browser, but the desktop `init()` does not install any logger,
+/// so you have to wrap your own one before setup.
+#[derive(Debug, Clone)]
+pub struct CrashParams {
+    /// The directory which the reports are written into, reports are not written if
+    /// its `None`.
+    pub dir: Option<PathBuf>,
+    /// The number of recent lifecycle phases kept in report.
+    pub max_phases: usize,
+}
+
+impl Default for CrashParams {
+    fn default() -> Self {
+        CrashParams {
+            dir: Some(PathBuf::from("crashes")),
+            max_phases: 16,
+        }
+    }
+}
+
+/// Checks if the crash system is enabled.
+#[inline]
+pub fn valid() -> bool {
+    unsafe { !CTX.is_null() }
+}
+
+/// Sets the callback which is invoked with the report when panics, after the report is
+/// written. Notes that it's called on the panicking thread.
+#[inline]
+pub fn set_callback<T>(func: T)
+where
+    T: Fn(&CrashReport) + Send + Sync + 'static,
+{
+    ctx().set_callback(func);
+}
+
+/// Captures the current context into a report without panicking, which could be used
+/// to report fatal errors.
+#[inline]
+pub fn capture(message: &str) -> CrashReport {
+    ctx().capture(message)
+}
+
+/// Gets the number of frames since the engine starts.
+#[inline]
+pub fn frame() -> u64 {
+    ctx().frame()
+}
+
+pub(crate) mod inside {
+    use super::system::CrashSystem;
+    use crate::application::Params;
+
+    pub static mut CTX: *const CrashSystem = std::ptr::null();
+
+    #[inline]
+    pub fn ctx() -> &'static CrashSystem {
+        unsafe {
+            debug_assert!(
+                !CTX.is_null(),
+                "crash system has not been initialized properly."
+            );
+
+            &*CTX
+        }
+    }
+
+    /// Setup the crash system.
+    pub unsafe fn setup(params: &Params) {
+        debug_assert!(CTX.is_null(), "duplicated setup of crash system.");
+
+        let ctx = CrashSystem::new(params.crash.clone(), format!("{:#?}", params));
+        CTX = Box::into_raw(Box::new(ctx));
+    }
+
+    /// Discard the crash system.
+    pub unsafe fn discard() {
+        if CTX.is_null() {
+            return;
+        }
+
+        drop(Box::from_raw(CTX as *mut CrashSystem));
+        CTX = std::ptr::null();
+    }
+}
@@ -0,0 +1,246 @@
+use std::collections::VecDeque;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, Once, RwLock};
+use std::thread;
+
+use crate::errors::*;
+
+use super::CrashParams;
+
+pub type CrashCallback = dyn Fn(&CrashReport) + Send + Sync;
+
+static HOOK: Once = Once::new();
+static mut STATE: *const RwLock<Option<Arc<CrashState>>> = std::ptr::null();
+
+/// The context of a crash.
+#[derive(Debug, Clone)]
+pub struct CrashReport {
+    /// The message of panic.
+    pub message: String,
+    /// The source location where the panic occurred.
+    pub location: Option<String>,
+    /// The name of the panicking thread.
+    pub thread: String,
+    pub backtrace: String,
+    /// The number of frames since the engine starts.
+    pub frame: u64,
+    /// The recent lifecycle phases with their frame numbers, the latest one comes last.
+    pub phases: Vec<(u64, &'static str)>,
+    /// The recent log lines captured by `CrashLogger`.
+    pub logs: Vec<String>,
+    /// The engine params in debug format.
+    pub params: String,
+    /// The path of report file if its written.
+    pub path: Option<PathBuf>,
+}
+
+impl fmt::Display for CrashReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Crash Report")?;
+        writeln!(f, "============")?;
+        writeln!(f, "Message: {}", self.message)?;
+        writeln!(
+            f,
+            "Location: {}",
+            self.location.as_ref().map_or("?", |v| v.as_str())
+        )?;
+        writeln!(f, "Thread: {}", self.thread)?;
+        writeln!(f, "Frame: {}", self.frame)?;
+
+        writeln!(f, "\nLifecycle Phases\n----------------")?;
+        for (frame, phase) in &self.phases {
+            writeln!(f, "#{} {}", frame, phase)?;
+        }
+
+        writeln!(f, "\nRecent Logs\n-----------")?;
+        for v in &self.logs {
+            writeln!(f, "{}", v)?;
+        }
+
+        writeln!(f, "\nParams\n------\n{}", self.params)?;
+        writeln!(f, "\nBacktrace\n---------\n{}", self.backtrace)
+    }
+}
+
+pub struct CrashState {
+    params: CrashParams,
+    engine: String,
+    frame: AtomicU64,
+    phases: Mutex<VecDeque<(u64, &'static str)>>,
+    callback: RwLock<Option<Arc<CrashCallback>>>,
+}
+
+impl CrashState {
+    fn capture(&self, message: String, location: Option<String>) -> CrashReport {
+        // The panic might occur with locks held, so we never block here.
+        let phases = match self.phases.try_lock() {
+            Ok(v) => v.iter().cloned().collect(),
+            Err(_) => Vec::new(),
+        };
+
+        CrashReport {
+            message,
+            location,
+            thread: thread::current().name().unwrap_or("<unnamed>").to_owned(),
+            backtrace: format!("{:?}", backtrace::Backtrace::new()),
+            frame: self.frame.load(Ordering::Acquire),
+            phases,
+            logs: super::logs::lines(),
+            params: self.engine.clone(),
+            path: None,
+        }
+    }
+
+    fn on_panic(&self, message: String, location: Option<String>) {
+        let mut report = self.capture(message, location);
+
+        if let Some(ref dir) = self.params.dir {
+            match write(dir, &report) {
+                Ok(path) => {
+                    error!("[CrashSystem] Crash report is written to {:?}.", path);
+                    report.path = Some(path);
+                }
+                Err(err) => error!("[CrashSystem] Failed to write crash report. {}", err),
+            }
+        }
+
+        let callback = match self.callback.try_read() {
+            Ok(v) => v.clone(),
+            Err(_) => None,
+        };
+
+        if let Some(callback) = callback {
+            callback(&report);
+        }
+    }
+}
+
+fn write(dir: &Path, report: &CrashReport) -> Result<PathBuf> {
+    let secs = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)?
+        .as_secs();
+
+    fs::create_dir_all(dir)?;
+    let path = dir.join(format!("crash-{}-{}.txt", secs, std::process::id()));
+    fs::write(&path, report.to_string())?;
+    Ok(path)
+}
+
+// The panic hook is installed only once, and captures with the state of current crash
+// system if there is one.
+fn state() -> &'static RwLock<Option<Arc<CrashState>>> {
+    HOOK.call_once(|| unsafe {
+        STATE = Box::into_raw(Box::new(RwLock::new(None)));
+
+        // Panics on any threads, including the workers of scheduler, are captured here
+        // before unwinding. The previous hook is still invoked.
+        let previous = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            previous(info);
+
+            // The panic might occur with the lock held, so we never block here.
+            let state = match (*STATE).try_read() {
+                Ok(v) => v.clone(),
+                Err(_) => None,
+            };
+
+            if let Some(state) = state {
+                let location = info
+                    .location()
+                    .map(|v| format!("{}:{}:{}", v.file(), v.line(), v.column()));
+
+                state.on_panic(message(info.payload()), location);
+            }
+        }));
+    });
+
+    unsafe { &*STATE }
+}
+
+fn message(payload: &(dyn std::any::Any + Send)) -> String {
+    if let Some(v) = payload.downcast_ref::<&str>() {
+        return (*v).to_owned();
+    }
+
+    if let Some(v) = payload.downcast_ref::<String>() {
+        return v.clone();
+    }
+
+    "Box<Any>".to_owned()
+}
+
+pub struct CrashSystem {
+    state: Arc<CrashState>,
+}
+
+impl Drop for CrashSystem {
+    fn drop(&mut self) {
+        let mut state = state().write().unwrap();
+        let current = state
+            .as_ref()
+            .map_or(false, |v| Arc::ptr_eq(v, &self.state));
+        if current {
+            *state = None;
+        }
+    }
+}
+
+impl CrashSystem {
+    pub fn new(params: CrashParams, engine: String) -> Self {
+        let state = Arc::new(CrashState {
+            phases: Mutex::new(VecDeque::with_capacity(params.max_phases)),
+            params,
+            engine,
+            frame: AtomicU64::new(0),
+            callback: RwLock::new(None),
+        });
+
+        if !super::logs::installed() {
+            warn!("[CrashSystem] CrashLogger is not installed, log lines are not captured.");
+        }
+
+        *self::state().write().unwrap() = Some(state.clone());
+
+        CrashSystem { state }
+    }
+
+    /// Sets the callback which is invoked after the crash report is written.
+    pub fn set_callback<T>(&self, func: T)
+    where
+        T: Fn(&CrashReport) + Send + Sync + 'static,
+    {
+        *self.state.callback.write().unwrap() = Some(Arc::new(func));
+    }
+
+    /// Captures the current context into a report.
+    pub fn capture(&self, message: &str) -> CrashReport {
+        self.state.capture(message.to_owned(), None)
+    }
+
+    /// Gets the number of frames since the engine starts.
+    #[inline]
+    pub fn frame(&self) -> u64 {
+        self.state.frame.load(Ordering::Acquire)
+    }
+
+    /// Advances to the next frame.
+    #[inline]
+    pub fn advance(&self) {
+        self.state.frame.fetch_add(1, Ordering::AcqRel);
+    }
+
+    /// Records the lifecycle phase which is entered.
+    pub fn enter(&self, phase: &'static str) {
+        let frame = self.frame();
+        let mut phases = self.state.phases.lock().unwrap();
+
+        if phases.len() >= self.state.params.max_phases {
+            phases.pop_front();
+        }
+
+        phases.push_back((frame, phase));
+    }
+}
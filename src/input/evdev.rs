@@ -0,0 +1,712 @@
+//! The gamepad backend which follows the conventions of Linux evdev interface.
+//!
+//! Gamepads under `/dev/input` are discovered periodically on Linux. The raw events of
+//! devices are translated into standardized `GamepadButton`s and `GamepadAxis`s.
+//!
+//! A `VirtualGamepad` could be attached with `input::attach_virtual_gamepad` on any platform,
+//! it works as a stand-in of evdev device which is driven by raw event codes. It's useful
+//! for testing and replaying the inputs.
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::errors::*;
+use crate::utils::hash::FastHashMap;
+use crate::utils::time::Timestamp;
+
+use super::events::InputEvent;
+use super::gamepad::{GamepadAxis, GamepadButton, GamepadId, GamepadParams};
+
+/// The event types and codes of evdev interface, see `linux/input-event-codes.h`.
+pub mod codes {
+    pub const EV_SYN: u16 = 0x00;
+    pub const EV_KEY: u16 = 0x01;
+    pub const EV_ABS: u16 = 0x03;
+    pub const EV_FF: u16 = 0x15;
+
+    pub const SYN_REPORT: u16 = 0x00;
+
+    pub const BTN_SOUTH: u16 = 0x130;
+    pub const BTN_EAST: u16 = 0x131;
+    pub const BTN_NORTH: u16 = 0x133;
+    pub const BTN_WEST: u16 = 0x134;
+    pub const BTN_TL: u16 = 0x136;
+    pub const BTN_TR: u16 = 0x137;
+    pub const BTN_TL2: u16 = 0x138;
+    pub const BTN_TR2: u16 = 0x139;
+    pub const BTN_SELECT: u16 = 0x13a;
+    pub const BTN_START: u16 = 0x13b;
+    pub const BTN_MODE: u16 = 0x13c;
+    pub const BTN_THUMBL: u16 = 0x13d;
+    pub const BTN_THUMBR: u16 = 0x13e;
+    pub const BTN_DPAD_UP: u16 = 0x220;
+    pub const BTN_DPAD_DOWN: u16 = 0x221;
+    pub const BTN_DPAD_LEFT: u16 = 0x222;
+    pub const BTN_DPAD_RIGHT: u16 = 0x223;
+
+    pub const ABS_X: u16 = 0x00;
+    pub const ABS_Y: u16 = 0x01;
+    pub const ABS_Z: u16 = 0x02;
+    pub const ABS_RX: u16 = 0x03;
+    pub const ABS_RY: u16 = 0x04;
+    pub const ABS_RZ: u16 = 0x05;
+    pub const ABS_GAS: u16 = 0x09;
+    pub const ABS_BRAKE: u16 = 0x0a;
+    pub const ABS_HAT0X: u16 = 0x10;
+    pub const ABS_HAT0Y: u16 = 0x11;
+
+    /// The absolute axes that we care about.
+    pub const AXES: [u16; 10] = [
+        ABS_X, ABS_Y, ABS_Z, ABS_RX, ABS_RY, ABS_RZ, ABS_GAS, ABS_BRAKE, ABS_HAT0X, ABS_HAT0Y,
+    ];
+}
+
+use self::codes::*;
+
+/// A raw event of evdev device.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RawEvent {
+    pub kind: u16,
+    pub code: u16,
+    pub value: i32,
+}
+
+/// The value range of an absolute axis.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AbsInfo {
+    pub min: i32,
+    pub max: i32,
+}
+
+impl AbsInfo {
+    /// The default ranges of XBox compatible controllers.
+    pub fn default_of(code: u16) -> AbsInfo {
+        match code {
+            ABS_Z | ABS_RZ | ABS_GAS | ABS_BRAKE => AbsInfo { min: 0, max: 255 },
+            ABS_HAT0X | ABS_HAT0Y => AbsInfo { min: -1, max: 1 },
+            _ => AbsInfo {
+                min: -32768,
+                max: 32767,
+            },
+        }
+    }
+
+    // Maps value into [0, 1].
+    fn normalize(self, value: i32) -> f32 {
+        if self.max <= self.min {
+            return 0.0;
+        }
+
+        // The full range of i32 might overflow.
+        let v =
+            (f64::from(value) - f64::from(self.min)) / (f64::from(self.max) - f64::from(self.min));
+        v.max(0.0).min(1.0) as f32
+    }
+}
+
+pub(crate) trait Device: Send {
+    fn name(&self) -> String;
+    /// Reads the pending events, returns false if the device has been disconnected.
+    fn read(&mut self, events: &mut Vec<RawEvent>) -> bool;
+    fn abs_info(&self, code: u16) -> Option<AbsInfo>;
+    fn rumble(&mut self, strong: f32, weak: f32, duration: Duration) -> Result<()>;
+}
+
+struct VirtualState {
+    name: String,
+    events: Vec<RawEvent>,
+    abs: FastHashMap<u16, AbsInfo>,
+    connected: bool,
+    rumble: Option<(f32, f32, Duration)>,
+}
+
+/// A stand-in of evdev gamepad, which is driven by raw event codes.
+#[derive(Clone)]
+pub struct VirtualGamepad {
+    state: Arc<Mutex<VirtualState>>,
+}
+
+impl VirtualGamepad {
+    /// Creates a virtual gamepad with the axis ranges of XBox controller.
+    pub fn new<T: Into<String>>(name: T) -> Self {
+        let abs = AXES.iter().map(|&v| (v, AbsInfo::default_of(v))).collect();
+
+        let state = VirtualState {
+            name: name.into(),
+            events: Vec::new(),
+            abs,
+            connected: true,
+            rumble: None,
+        };
+
+        VirtualGamepad {
+            state: Arc::new(Mutex::new(state)),
+        }
+    }
+
+    /// Sets the value range of an absolute axis.
+    pub fn set_abs_info(&self, code: u16, min: i32, max: i32) {
+        let mut state = self.state.lock().unwrap();
+        state.abs.insert(code, AbsInfo { min, max });
+    }
+
+    /// Emits a raw event.
+    pub fn emit(&self, kind: u16, code: u16, value: i32) {
+        let mut state = self.state.lock().unwrap();
+        state.events.push(RawEvent { kind, code, value });
+    }
+
+    /// Emits the events of pressing a key.
+    pub fn press(&self, code: u16) {
+        self.emit(EV_KEY, code, 1);
+        self.emit(EV_SYN, SYN_REPORT, 0);
+    }
+
+    /// Emits the events of releasing a key.
+    pub fn release(&self, code: u16) {
+        self.emit(EV_KEY, code, 0);
+        self.emit(EV_SYN, SYN_REPORT, 0);
+    }
+
+    /// Emits the events of moving an absolute axis.
+    pub fn move_axis(&self, code: u16, value: i32) {
+        self.emit(EV_ABS, code, value);
+        self.emit(EV_SYN, SYN_REPORT, 0);
+    }
+
+    /// Disconnects the virtual gamepad.
+    pub fn disconnect(&self) {
+        self.state.lock().unwrap().connected = false;
+    }
+
+    /// Gets the last rumble requested on this gamepad, with the strong and weak magnitude.
+    pub fn rumble(&self) -> Option<(f32, f32, Duration)> {
+        self.state.lock().unwrap().rumble
+    }
+}
+
+impl Device for VirtualGamepad {
+    fn name(&self) -> String {
+        self.state.lock().unwrap().name.clone()
+    }
+
+    fn read(&mut self, events: &mut Vec<RawEvent>) -> bool {
+        let mut state = self.state.lock().unwrap();
+        events.extend(state.events.drain(..));
+        state.connected
+    }
+
+    fn abs_info(&self, code: u16) -> Option<AbsInfo> {
+        self.state.lock().unwrap().abs.get(&code).cloned()
+    }
+
+    fn rumble(&mut self, strong: f32, weak: f32, duration: Duration) -> Result<()> {
+        self.state.lock().unwrap().rumble = Some((strong, weak, duration));
+        Ok(())
+    }
+}
+
+struct Slot {
+    id: GamepadId,
+    path: Option<String>,
+    device: Box<dyn Device>,
+    hat: (i32, i32),
+}
+
+pub(crate) struct EvdevBackend {
+    slots: Vec<Slot>,
+    incoming: Vec<(Option<String>, Box<dyn Device>)>,
+    scan_interval: Option<Duration>,
+    last_scan: Option<Timestamp>,
+    #[cfg(target_os = "linux")]
+    scanner: self::linux::Scanner,
+    buf: Vec<RawEvent>,
+}
+
+impl EvdevBackend {
+    pub fn new(params: &GamepadParams) -> Self {
+        EvdevBackend {
+            slots: Vec::new(),
+            incoming: Vec::new(),
+            scan_interval: if params.scan_devices {
+                Some(params.scan_interval)
+            } else {
+                None
+            },
+            last_scan: None,
+            #[cfg(target_os = "linux")]
+            scanner: self::linux::Scanner::new(),
+            buf: Vec::new(),
+        }
+    }
+
+    /// Attachs a device, which will be connected in the next poll.
+    pub fn attach(&mut self, device: Box<dyn Device>) {
+        self.incoming.push((None, device));
+    }
+
+    /// Gets the name of gamepad.
+    pub fn name(&self, id: GamepadId) -> Option<String> {
+        self.slots
+            .iter()
+            .find(|v| v.id == id)
+            .map(|v| v.device.name())
+    }
+
+    /// Gets the identifiers of connected gamepads.
+    pub fn connected(&self) -> Vec<GamepadId> {
+        self.slots.iter().map(|v| v.id).collect()
+    }
+
+    /// Plays the rumble effect with the strong and weak magnitude in range [0, 1].
+    pub fn rumble(
+        &mut self,
+        id: GamepadId,
+        strong: f32,
+        weak: f32,
+        duration: Duration,
+    ) -> Result<()> {
+        let slot = self
+            .slots
+            .iter_mut()
+            .find(|v| v.id == id)
+            .ok_or_else(|| format_err!("Gamepad {:?} is not connected.", id))?;
+
+        let strong = strong.max(0.0).min(1.0);
+        let weak = weak.max(0.0).min(1.0);
+        slot.device.rumble(strong, weak, duration)
+    }
+
+    /// Polls the connections and events of gamepads.
+    pub fn poll(&mut self, events: &mut Vec<InputEvent>) {
+        self.scan();
+
+        for (path, device) in self.incoming.drain(..) {
+            let id = (0..=255u8)
+                .map(GamepadId)
+                .find(|id| self.slots.iter().all(|v| v.id != *id));
+
+            if let Some(id) = id {
+                info!("[Gamepad] {:?} '{}' is connected.", id, device.name());
+                events.push(InputEvent::GamepadConnected { id });
+                self.slots.push(Slot {
+                    id,
+                    path,
+                    device,
+                    hat: (0, 0),
+                });
+            } else {
+                warn!(
+                    "[Gamepad] Too many gamepads, '{}' is ignored.",
+                    device.name()
+                );
+            }
+        }
+
+        let mut disconnected = Vec::new();
+        for slot in &mut self.slots {
+            let connected = slot.device.read(&mut self.buf);
+            for v in self.buf.drain(..) {
+                translate(slot, v, events);
+            }
+
+            if !connected {
+                disconnected.push(slot.id);
+            }
+        }
+
+        for id in disconnected {
+            info!("[Gamepad] {:?} is disconnected.", id);
+            self.slots.retain(|v| v.id != id);
+            events.push(InputEvent::GamepadDisconnected { id });
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    fn scan(&mut self) {
+        let interval = match self.scan_interval {
+            Some(v) => v,
+            None => return,
+        };
+
+        if let Some(v) = self.last_scan {
+            if v.elapsed() < interval {
+                return;
+            }
+        }
+
+        self.last_scan = Some(Timestamp::now());
+
+        let slots = &self.slots;
+        let opened = |path: &str| {
+            slots
+                .iter()
+                .any(|v| v.path.as_ref().map(|v| v.as_str()) == Some(path))
+        };
+        for (path, device) in self.scanner.scan(opened) {
+            self.incoming.push((Some(path), Box::new(device)));
+        }
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    fn scan(&mut self) {
+        let _ = (self.scan_interval, self.last_scan);
+    }
+}
+
+fn to_button(code: u16) -> Option<GamepadButton> {
+    match code {
+        BTN_SOUTH => Some(GamepadButton::South),
+        BTN_EAST => Some(GamepadButton::East),
+        BTN_NORTH => Some(GamepadButton::North),
+        BTN_WEST => Some(GamepadButton::West),
+        BTN_TL => Some(GamepadButton::LeftBumper),
+        BTN_TR => Some(GamepadButton::RightBumper),
+        BTN_TL2 => Some(GamepadButton::LeftTrigger),
+        BTN_TR2 => Some(GamepadButton::RightTrigger),
+        BTN_SELECT => Some(GamepadButton::Select),
+        BTN_START => Some(GamepadButton::Start),
+        BTN_MODE => Some(GamepadButton::Mode),
+        BTN_THUMBL => Some(GamepadButton::LeftThumb),
+        BTN_THUMBR => Some(GamepadButton::RightThumb),
+        BTN_DPAD_UP => Some(GamepadButton::DPadUp),
+        BTN_DPAD_DOWN => Some(GamepadButton::DPadDown),
+        BTN_DPAD_LEFT => Some(GamepadButton::DPadLeft),
+        BTN_DPAD_RIGHT => Some(GamepadButton::DPadRight),
+        _ => None,
+    }
+}
+
+fn to_axis(code: u16) -> Option<GamepadAxis> {
+    match code {
+        ABS_X => Some(GamepadAxis::LeftStickX),
+        ABS_Y => Some(GamepadAxis::LeftStickY),
+        ABS_RX => Some(GamepadAxis::RightStickX),
+        ABS_RY => Some(GamepadAxis::RightStickY),
+        ABS_Z | ABS_BRAKE => Some(GamepadAxis::LeftTrigger),
+        ABS_RZ | ABS_GAS => Some(GamepadAxis::RightTrigger),
+        _ => None,
+    }
+}
+
+// Translates raw event into standardized gamepad events.
+fn translate(slot: &mut Slot, v: RawEvent, events: &mut Vec<InputEvent>) {
+    let id = slot.id;
+
+    match v.kind {
+        EV_KEY => {
+            if let Some(button) = to_button(v.code) {
+                match v.value {
+                    0 => events.push(InputEvent::GamepadReleased { id, button }),
+                    1 => events.push(InputEvent::GamepadPressed { id, button }),
+                    // Ignores auto-repeats.
+                    _ => {}
+                }
+            }
+        }
+
+        EV_ABS => {
+            let info = slot
+                .device
+                .abs_info(v.code)
+                .unwrap_or_else(|| AbsInfo::default_of(v.code));
+
+            if v.code == ABS_HAT0X || v.code == ABS_HAT0Y {
+                // Most of the controllers report their d-pad as hat switch.
+                let value = (info.normalize(v.value) * 2.0 - 1.0).round() as i32;
+                let (last, negative, positive) = if v.code == ABS_HAT0X {
+                    let last = slot.hat.0;
+                    slot.hat.0 = value;
+                    (last, GamepadButton::DPadLeft, GamepadButton::DPadRight)
+                } else {
+                    let last = slot.hat.1;
+                    slot.hat.1 = value;
+                    (last, GamepadButton::DPadUp, GamepadButton::DPadDown)
+                };
+
+                if last != value {
+                    match last {
+                        -1 => events.push(InputEvent::GamepadReleased {
+                            id,
+                            button: negative,
+                        }),
+                        1 => events.push(InputEvent::GamepadReleased {
+                            id,
+                            button: positive,
+                        }),
+                        _ => {}
+                    }
+
+                    match value {
+                        -1 => events.push(InputEvent::GamepadPressed {
+                            id,
+                            button: negative,
+                        }),
+                        1 => events.push(InputEvent::GamepadPressed {
+                            id,
+                            button: positive,
+                        }),
+                        _ => {}
+                    }
+                }
+
+                return;
+            }
+
+            if let Some(axis) = to_axis(v.code) {
+                let value = info.normalize(v.value);
+                let value = match axis {
+                    GamepadAxis::LeftStickX | GamepadAxis::RightStickX => value * 2.0 - 1.0,
+                    // The y axes of evdev are pointing down.
+                    GamepadAxis::LeftStickY | GamepadAxis::RightStickY => 1.0 - value * 2.0,
+                    GamepadAxis::LeftTrigger | GamepadAxis::RightTrigger => value,
+                };
+
+                events.push(InputEvent::GamepadAxisChanged { id, axis, value });
+            }
+        }
+
+        _ => {}
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use std::fs::{self, File, OpenOptions};
+    use std::io::{self, Read, Write};
+    use std::mem;
+    use std::os::unix::fs::OpenOptionsExt;
+    use std::os::unix::io::AsRawFd;
+    use std::time::Duration;
+
+    use crate::errors::*;
+    use crate::utils::hash::{FastHashMap, FastHashSet};
+
+    use super::codes::*;
+    use super::{AbsInfo, Device, RawEvent};
+
+    const BTN_GAMEPAD: usize = 0x130;
+    const KEY_MAX: usize = 0x2ff;
+    const FF_RUMBLE: u16 = 0x50;
+
+    // The ioctl requests of evdev, see `linux/input.h`.
+    const EV: u32 = b'E' as u32;
+
+    fn eviocgname(len: usize) -> u32 {
+        libc::_IOC(libc::_IOC_READ, EV, 0x06, len)
+    }
+
+    fn eviocgbit(ev: u16, len: usize) -> u32 {
+        libc::_IOC(libc::_IOC_READ, EV, 0x20 + u32::from(ev), len)
+    }
+
+    fn eviocgabs(abs: u16) -> u32 {
+        libc::_IOR::<libc::input_absinfo>(EV, 0x40 + u32::from(abs))
+    }
+
+    fn eviocsff() -> u32 {
+        libc::_IOW::<libc::ff_effect>(EV, 0x80)
+    }
+
+    pub struct FileDevice {
+        file: File,
+        name: String,
+        abs: FastHashMap<u16, AbsInfo>,
+        effect: i16,
+    }
+
+    impl FileDevice {
+        /// Opens the device, returns `None` if its not a gamepad.
+        pub fn open(path: &str) -> io::Result<Option<FileDevice>> {
+            let file = OpenOptions::new()
+                .read(true)
+                .write(true)
+                .custom_flags(libc::O_NONBLOCK)
+                .open(path)
+                .or_else(|_| {
+                    OpenOptions::new()
+                        .read(true)
+                        .custom_flags(libc::O_NONBLOCK)
+                        .open(path)
+                })?;
+
+            let fd = file.as_raw_fd();
+
+            unsafe {
+                let mut keys = [0u8; KEY_MAX / 8 + 1];
+                if libc::ioctl(fd, eviocgbit(EV_KEY, keys.len()) as _, keys.as_mut_ptr()) < 0 {
+                    return Ok(None);
+                }
+
+                if keys[BTN_GAMEPAD / 8] & (1 << (BTN_GAMEPAD % 8)) == 0 {
+                    return Ok(None);
+                }
+
+                let mut name = [0u8; 256];
+                libc::ioctl(fd, eviocgname(name.len()) as _, name.as_mut_ptr());
+                let len = name.iter().position(|&v| v == 0).unwrap_or(name.len());
+                let name = String::from_utf8_lossy(&name[..len]).into_owned();
+
+                let mut abs = FastHashMap::default();
+                for &code in AXES.iter() {
+                    let mut info: libc::input_absinfo = mem::zeroed();
+                    if libc::ioctl(fd, eviocgabs(code) as _, &mut info) >= 0 {
+                        abs.insert(
+                            code,
+                            AbsInfo {
+                                min: info.minimum,
+                                max: info.maximum,
+                            },
+                        );
+                    }
+                }
+
+                Ok(Some(FileDevice {
+                    file,
+                    name,
+                    abs,
+                    effect: -1,
+                }))
+            }
+        }
+    }
+
+    impl Device for FileDevice {
+        fn name(&self) -> String {
+            self.name.clone()
+        }
+
+        fn read(&mut self, events: &mut Vec<RawEvent>) -> bool {
+            let size = mem::size_of::<libc::input_event>();
+            let mut buf = [0u8; 64 * 24];
+            let len = buf.len() / size * size;
+
+            loop {
+                match self.file.read(&mut buf[..len]) {
+                    Ok(0) => return false,
+                    Ok(n) => {
+                        for chunk in buf[..n].chunks(size) {
+                            if chunk.len() == size {
+                                let v = unsafe {
+                                    std::ptr::read_unaligned(
+                                        chunk.as_ptr() as *const libc::input_event
+                                    )
+                                };
+
+                                events.push(RawEvent {
+                                    kind: v.type_,
+                                    code: v.code,
+                                    value: v.value,
+                                });
+                            }
+                        }
+                    }
+                    Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => return true,
+                    Err(ref err) if err.kind() == io::ErrorKind::Interrupted => {}
+                    Err(_) => return false,
+                }
+            }
+        }
+
+        fn abs_info(&self, code: u16) -> Option<AbsInfo> {
+            self.abs.get(&code).cloned()
+        }
+
+        fn rumble(&mut self, strong: f32, weak: f32, duration: Duration) -> Result<()> {
+            let millis = duration.as_secs() * 1000 + u64::from(duration.subsec_millis());
+
+            unsafe {
+                let mut effect: libc::ff_effect = mem::zeroed();
+                effect.type_ = FF_RUMBLE;
+                effect.id = self.effect;
+                effect.replay.length = millis.min(0xFFFF) as u16;
+
+                // The union of effects is represented as raw bytes.
+                let rumble = effect.u.as_mut_ptr() as *mut libc::ff_rumble_effect;
+                (*rumble).strong_magnitude = (strong * 65535.0) as u16;
+                (*rumble).weak_magnitude = (weak * 65535.0) as u16;
+
+                if libc::ioctl(self.file.as_raw_fd(), eviocsff() as _, &mut effect) < 0 {
+                    bail!(
+                        "Failed to upload rumble effect to '{}': {}",
+                        self.name,
+                        io::Error::last_os_error()
+                    );
+                }
+
+                self.effect = effect.id;
+
+                let mut play: libc::input_event = mem::zeroed();
+                play.type_ = EV_FF;
+                play.code = effect.id as u16;
+                play.value = 1;
+
+                let bytes = std::slice::from_raw_parts(
+                    &play as *const libc::input_event as *const u8,
+                    mem::size_of::<libc::input_event>(),
+                );
+
+                self.file.write_all(bytes)?;
+            }
+
+            Ok(())
+        }
+    }
+
+    /// Scans `/dev/input` for gamepads.
+    pub struct Scanner {
+        rejected: FastHashSet<String>,
+    }
+
+    impl Scanner {
+        pub fn new() -> Self {
+            Scanner {
+                rejected: FastHashSet::default(),
+            }
+        }
+
+        pub fn scan<F>(&mut self, opened: F) -> Vec<(String, FileDevice)>
+        where
+            F: Fn(&str) -> bool,
+        {
+            let mut devices = Vec::new();
+            let mut visited = FastHashSet::default();
+
+            let entries = match fs::read_dir("/dev/input") {
+                Ok(v) => v,
+                Err(_) => return devices,
+            };
+
+            for entry in entries.filter_map(|v| v.ok()) {
+                let path = entry.path();
+                let is_event = path
+                    .file_name()
+                    .and_then(|v| v.to_str())
+                    .map(|v| v.starts_with("event"))
+                    .unwrap_or(false);
+
+                let path = match path.to_str() {
+                    Some(v) if is_event => v.to_owned(),
+                    _ => continue,
+                };
+
+                visited.insert(path.clone());
+                if opened(&path) || self.rejected.contains(&path) {
+                    continue;
+                }
+
+                match FileDevice::open(&path) {
+                    Ok(Some(device)) => devices.push((path, device)),
+                    // Devices which are not gamepads or not permitted to read.
+                    _ => {
+                        self.rejected.insert(path);
+                    }
+                }
+            }
+
+            // Devices might be re-plugged with the same path.
+            self.rejected.retain(|v| visited.contains(v));
+            devices
+        }
+    }
+}
@@ -1,16 +1,22 @@
+use std::time::Duration;
+
 use crate::math::prelude::Vector2;
 
-use super::keyboard::Key;
+use super::gamepad::{GamepadAxis, GamepadButton, GamepadId};
+use super::keyboard::{Key, Modifiers};
 use super::mouse::MouseButton;
 use super::touchpad::TouchState;
 
-/// Input device event, supports mouse and keyboard only.
-#[derive(Debug, Clone, Copy)]
+/// Input device event, supports mouse, keyboard, touchpad and gamepads.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum InputEvent {
     /// The cursor has moved on the window.
     /// The parameter are the (x, y) coords in pixels relative to the bottom-left
     /// corner of th window.
     MouseMoved { position: (f32, f32) },
+    /// The raw, unaccelerated motion of mouse device. It keeps being reported even if the
+    /// cursor is grabbed or reaches the border of window.
+    MouseMotion { delta: (f32, f32) },
     /// Pressed event on mouse has been received.
     MousePressed { button: MouseButton },
     /// Released event from mouse has been received.
@@ -39,4 +45,63 @@ pub enum InputEvent {
         state: TouchState,
         position: Vector2<f32>,
     },
+
+    /// A gamepad has been connected.
+    GamepadConnected { id: GamepadId },
+    /// A gamepad has been disconnected.
+    GamepadDisconnected { id: GamepadId },
+    /// Pressed event on gamepad has been received.
+    GamepadPressed { id: GamepadId, button: GamepadButton },
+    /// Released event on gamepad has been received.
+    GamepadReleased { id: GamepadId, button: GamepadButton },
+    /// The raw value of gamepad axis has changed, without dead zones applied.
+    GamepadAxisChanged {
+        id: GamepadId,
+        axis: GamepadAxis,
+        value: f32,
+    },
+}
+
+/// The composition events of input method editor.
+///
+/// # Platform-specific
+///
+/// Only emitted in browser. Desktop composition is unsupported, since glutin does not report
+/// the preedit text; the input method editor displays it itself and only the committed text
+/// is received as `InputEvent::ReceivedCharacter`s. It could be checked at runtime with
+/// `window::ime_composition_supported`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ImeEvent {
+    /// A composition session has started.
+    CompositionStarted,
+    /// The composing (preedit) text has changed, with the byte range of caret or selection
+    /// in the text.
+    CompositionUpdated {
+        text: String,
+        cursor: Option<(usize, usize)>,
+    },
+    /// The composition session has ended. The committed text is received as
+    /// `InputEvent::ReceivedCharacter`s.
+    CompositionEnded,
+}
+
+/// The text being composed with input method editor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImeComposition {
+    /// The composing (preedit) text.
+    pub text: String,
+    /// The byte range of caret or selection in the text.
+    pub cursor: Option<(usize, usize)>,
+}
+
+/// A buffered input event, with the time it was received and the modifier keys held down
+/// right after it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TimedInputEvent {
+    pub event: InputEvent,
+    /// The time since the input system was setup, when the event was polled from the window
+    /// backend. Notes that it's taken once per poll, so the events received in one frame
+    /// usually share the same time, and their order is the only reliable sequence.
+    pub time: Duration,
+    pub modifiers: Modifiers,
 }
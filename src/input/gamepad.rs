@@ -0,0 +1,239 @@
+use std::time::Duration;
+
+use crate::math::prelude::{InnerSpace, Vector2};
+use crate::utils::hash::{FastHashMap, FastHashSet};
+
+/// The setup parameters of gamepad devices.
+#[derive(Debug, Clone, Copy)]
+pub struct GamepadParams {
+    /// The radial dead zone of thumb sticks, in range [0, 1).
+    pub dead_zone: f32,
+    /// The dead zone of analog triggers, in range [0, 1).
+    pub trigger_dead_zone: f32,
+    /// Specifies whether should we look for the gamepads connected to this machine.
+    /// Virtual gamepads could be attached even if its disabled.
+    pub scan_devices: bool,
+    /// The interval between two scans of connected gamepads.
+    pub scan_interval: Duration,
+}
+
+impl Default for GamepadParams {
+    fn default() -> Self {
+        GamepadParams {
+            dead_zone: 0.15,
+            trigger_dead_zone: 0.05,
+            scan_devices: true,
+            scan_interval: Duration::from_secs(1),
+        }
+    }
+}
+
+/// The identifier of connected gamepad. Identifiers are reused after disconnection, so
+/// it could be used as the player index directly.
+#[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Serialize, Deserialize)]
+pub struct GamepadId(pub u8);
+
+/// The standardized buttons of gamepad, which follow the layout of XBox controller.
+#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+pub enum GamepadButton {
+    /// The bottom face button (A on XBox controller).
+    South,
+    /// The right face button (B on XBox controller).
+    East,
+    /// The left face button (X on XBox controller).
+    West,
+    /// The top face button (Y on XBox controller).
+    North,
+    LeftBumper,
+    RightBumper,
+    LeftTrigger,
+    RightTrigger,
+    Select,
+    Start,
+    Mode,
+    LeftThumb,
+    RightThumb,
+    DPadUp,
+    DPadDown,
+    DPadLeft,
+    DPadRight,
+}
+
+/// The standardized axes of gamepad. Sticks are in range [-1, 1] with positive y pointing
+/// up, and triggers are in range [0, 1].
+#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+pub enum GamepadAxis {
+    LeftStickX,
+    LeftStickY,
+    RightStickX,
+    RightStickY,
+    LeftTrigger,
+    RightTrigger,
+}
+
+#[derive(Default)]
+struct GamepadState {
+    downs: FastHashSet<GamepadButton>,
+    presses: FastHashSet<GamepadButton>,
+    releases: FastHashSet<GamepadButton>,
+    axes: FastHashMap<GamepadAxis, f32>,
+}
+
+pub struct Gamepads {
+    states: FastHashMap<GamepadId, GamepadState>,
+    params: GamepadParams,
+}
+
+impl Gamepads {
+    pub fn new(params: GamepadParams) -> Self {
+        Gamepads {
+            states: FastHashMap::default(),
+            params,
+        }
+    }
+
+    #[inline]
+    pub fn reset(&mut self) {
+        for v in self.states.values_mut() {
+            *v = GamepadState::default();
+        }
+    }
+
+    #[inline]
+    pub fn advance(&mut self) {
+        for v in self.states.values_mut() {
+            v.presses.clear();
+            v.releases.clear();
+        }
+    }
+
+    #[inline]
+    pub fn on_connected(&mut self, id: GamepadId) {
+        self.states.insert(id, GamepadState::default());
+    }
+
+    #[inline]
+    pub fn on_disconnected(&mut self, id: GamepadId) {
+        self.states.remove(&id);
+    }
+
+    #[inline]
+    pub fn on_button_pressed(&mut self, id: GamepadId, button: GamepadButton) {
+        if let Some(v) = self.states.get_mut(&id) {
+            if v.downs.insert(button) {
+                v.presses.insert(button);
+            }
+        }
+    }
+
+    #[inline]
+    pub fn on_button_released(&mut self, id: GamepadId, button: GamepadButton) {
+        if let Some(v) = self.states.get_mut(&id) {
+            if v.downs.remove(&button) {
+                v.releases.insert(button);
+            }
+        }
+    }
+
+    #[inline]
+    pub fn on_axis_changed(&mut self, id: GamepadId, axis: GamepadAxis, value: f32) {
+        if let Some(v) = self.states.get_mut(&id) {
+            v.axes.insert(axis, value);
+        }
+    }
+
+    /// Returns the identifiers of connected gamepads in ascending order.
+    pub fn connected(&self) -> Vec<GamepadId> {
+        let mut ids: Vec<_> = self.states.keys().cloned().collect();
+        ids.sort();
+        ids
+    }
+
+    #[inline]
+    pub fn is_connected(&self, id: GamepadId) -> bool {
+        self.states.contains_key(&id)
+    }
+
+    #[inline]
+    pub fn is_button_down(&self, id: GamepadId, button: GamepadButton) -> bool {
+        self.states
+            .get(&id)
+            .map(|v| v.downs.contains(&button))
+            .unwrap_or(false)
+    }
+
+    #[inline]
+    pub fn is_button_press(&self, id: GamepadId, button: GamepadButton) -> bool {
+        self.states
+            .get(&id)
+            .map(|v| v.presses.contains(&button))
+            .unwrap_or(false)
+    }
+
+    #[inline]
+    pub fn is_button_release(&self, id: GamepadId, button: GamepadButton) -> bool {
+        self.states
+            .get(&id)
+            .map(|v| v.releases.contains(&button))
+            .unwrap_or(false)
+    }
+
+    /// Gets the buttons of gamepad pressed during last frame.
+    pub fn presses(&self, id: GamepadId) -> Vec<GamepadButton> {
+        self.states
+            .get(&id)
+            .map(|v| v.presses.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Gets the value of axis with dead zones applied.
+    pub fn axis(&self, id: GamepadId, axis: GamepadAxis) -> f32 {
+        match axis {
+            GamepadAxis::LeftStickX => self.left_stick(id).x,
+            GamepadAxis::LeftStickY => self.left_stick(id).y,
+            GamepadAxis::RightStickX => self.right_stick(id).x,
+            GamepadAxis::RightStickY => self.right_stick(id).y,
+            GamepadAxis::LeftTrigger | GamepadAxis::RightTrigger => {
+                let v = self.raw_axis(id, axis);
+                let dz = self.params.trigger_dead_zone;
+
+                if v <= dz {
+                    0.0
+                } else {
+                    ((v - dz) / (1.0 - dz)).min(1.0)
+                }
+            }
+        }
+    }
+
+    #[inline]
+    pub fn left_stick(&self, id: GamepadId) -> Vector2<f32> {
+        self.stick(id, GamepadAxis::LeftStickX, GamepadAxis::LeftStickY)
+    }
+
+    #[inline]
+    pub fn right_stick(&self, id: GamepadId) -> Vector2<f32> {
+        self.stick(id, GamepadAxis::RightStickX, GamepadAxis::RightStickY)
+    }
+
+    fn raw_axis(&self, id: GamepadId, axis: GamepadAxis) -> f32 {
+        self.states
+            .get(&id)
+            .and_then(|v| v.axes.get(&axis).cloned())
+            .unwrap_or(0.0)
+    }
+
+    // Applies the radial dead zone, and rescales the remaining range into [0, 1].
+    fn stick(&self, id: GamepadId, x: GamepadAxis, y: GamepadAxis) -> Vector2<f32> {
+        let v = Vector2::new(self.raw_axis(id, x), self.raw_axis(id, y));
+        let magnitude = v.magnitude();
+        let dz = self.params.dead_zone;
+
+        if magnitude <= dz {
+            return Vector2::new(0.0, 0.0);
+        }
+
+        let scaled = ((magnitude - dz) / (1.0 - dz)).min(1.0);
+        v * (scaled / magnitude)
+    }
+}
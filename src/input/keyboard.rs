@@ -184,6 +184,16 @@ pub enum Key {
     Wake,
 }
 
+/// The state of modifier keys.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Modifiers {
+    pub shift: bool,
+    pub ctrl: bool,
+    pub alt: bool,
+    /// The super key, which is the Windows key on PC and the Command key on Mac.
+    pub logo: bool,
+}
+
 enum KeyDownState {
     Start(Timestamp),
     Press(Timestamp),
@@ -292,6 +302,30 @@ impl Keyboard {
         }
     }
 
+    /// Gets the modifier keys currently held down.
+    pub fn modifiers(&self) -> Modifiers {
+        let down = |l, r| self.is_key_down(l) || self.is_key_down(r);
+
+        Modifiers {
+            shift: down(Key::LShift, Key::RShift),
+            ctrl: down(Key::LControl, Key::RControl),
+            alt: down(Key::LAlt, Key::RAlt),
+            logo: down(Key::LWin, Key::RWin),
+        }
+    }
+
+    /// Gets the keys currently held down.
+    #[inline]
+    pub fn downs(&self) -> impl Iterator<Item = Key> + '_ {
+        self.downs.keys().cloned()
+    }
+
+    /// Gets the keys pressed during last frame.
+    #[inline]
+    pub fn presses(&self) -> impl Iterator<Item = Key> + '_ {
+        self.presses.iter().cloned()
+    }
+
     #[inline]
     pub fn captured_chars(&self) -> &[char] {
         &self.chars
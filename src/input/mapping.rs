@@ -0,0 +1,282 @@
+//! Named actions and axes which are bound to the physical inputs.
+//!
+//! Bindings are grouped into named `BindingSet`s (e.g. "gameplay" and "menu"), and a
+//! `BindingProfile` holds all the sets that could be serialized into user configurations.
+//! The active sets are organized as a stack, the top-most active set which binds the name
+//! decides the state of an action or axis.
+//!
+//! ```rust,ignore
+//! let mut gameplay = BindingSet::new("gameplay");
+//! gameplay
+//!     .bind_action("jump", ActionBinding::Key(Key::Space))
+//!     .bind_action("jump", ActionBinding::Gamepad(GamepadButton::South))
+//!     .bind_axis("move_x", AxisBinding::keys(Key::A, Key::D))
+//!     .bind_axis("move_x", AxisBinding::new(AxisSource::Gamepad(GamepadAxis::LeftStickX)));
+//!
+//! let mut profile = BindingProfile::default();
+//! profile.contexts.push(gameplay);
+//!
+//! input::set_binding_profile(profile);
+//! input::push_binding_context("gameplay");
+//!
+//! if input::is_action_press("jump") { ... }
+//! let dx = input::axis("move_x");
+//! ```
+
+use std::collections::BTreeMap;
+
+use crate::errors::*;
+
+use super::gamepad::{GamepadAxis, GamepadButton, GamepadId};
+use super::keyboard::Key;
+use super::mouse::MouseButton;
+
+/// The digital inputs which could be bound to actions.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum ActionBinding {
+    Key(Key),
+    Mouse(MouseButton),
+    Gamepad(GamepadButton),
+    /// The mouse wheel scrolled up during last frame.
+    WheelUp,
+    /// The mouse wheel scrolled down during last frame.
+    WheelDown,
+    /// A tap gesture has been detected during last frame.
+    Tap,
+    /// A double tap gesture has been detected during last frame.
+    DoubleTap,
+}
+
+/// The inputs which could be bound to axes.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum AxisSource {
+    /// -1 if the negative key is held down, and 1 if the positive one.
+    Keys { negative: Key, positive: Key },
+    /// -1 if the negative button is held down, and 1 if the positive one.
+    GamepadButtons {
+        negative: GamepadButton,
+        positive: GamepadButton,
+    },
+    /// The gamepad axis with dead zones applied.
+    Gamepad(GamepadAxis),
+    /// The raw horizontal motion of mouse.
+    MouseMotionX,
+    /// The raw vertical motion of mouse.
+    MouseMotionY,
+    /// The horizontal scroll of mouse wheel.
+    WheelX,
+    /// The vertical scroll of mouse wheel.
+    WheelY,
+    /// The horizontal movement of panning gesture.
+    PanX,
+    /// The vertical movement of panning gesture.
+    PanY,
+}
+
+/// The input bound to an axis with scale.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct AxisBinding {
+    pub source: AxisSource,
+    #[serde(default = "default_scale")]
+    pub scale: f32,
+}
+
+fn default_scale() -> f32 {
+    1.0
+}
+
+impl AxisBinding {
+    #[inline]
+    pub fn new(source: AxisSource) -> Self {
+        AxisBinding { source, scale: 1.0 }
+    }
+
+    /// Binds a pair of keys.
+    #[inline]
+    pub fn keys(negative: Key, positive: Key) -> Self {
+        AxisBinding::new(AxisSource::Keys { negative, positive })
+    }
+
+    /// Scales the value of source, a negative scale inverts the axis.
+    #[inline]
+    pub fn with_scale(mut self, scale: f32) -> Self {
+        self.scale = scale;
+        self
+    }
+}
+
+/// A named set of bindings.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct BindingSet {
+    pub name: String,
+    #[serde(default)]
+    pub actions: BTreeMap<String, Vec<ActionBinding>>,
+    #[serde(default)]
+    pub axes: BTreeMap<String, Vec<AxisBinding>>,
+}
+
+impl BindingSet {
+    pub fn new<T: Into<String>>(name: T) -> Self {
+        BindingSet {
+            name: name.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Binds an input to action, an action could have multiple bindings.
+    pub fn bind_action<T: Into<String>>(&mut self, action: T, binding: ActionBinding) -> &mut Self {
+        let bindings = self.actions.entry(action.into()).or_insert_with(Vec::new);
+        if !bindings.contains(&binding) {
+            bindings.push(binding);
+        }
+
+        self
+    }
+
+    /// Removes a binding of action, returns false if its not bound.
+    pub fn unbind_action(&mut self, action: &str, binding: ActionBinding) -> bool {
+        match self.actions.get_mut(action) {
+            Some(bindings) => {
+                let len = bindings.len();
+                bindings.retain(|v| *v != binding);
+                len != bindings.len()
+            }
+            None => false,
+        }
+    }
+
+    /// Binds an input to axis, an axis could have multiple bindings.
+    pub fn bind_axis<T: Into<String>>(&mut self, axis: T, binding: AxisBinding) -> &mut Self {
+        let bindings = self.axes.entry(axis.into()).or_insert_with(Vec::new);
+        if !bindings.contains(&binding) {
+            bindings.push(binding);
+        }
+
+        self
+    }
+
+    /// Removes the bindings of axis with source, returns false if its not bound.
+    pub fn unbind_axis(&mut self, axis: &str, source: AxisSource) -> bool {
+        match self.axes.get_mut(axis) {
+            Some(bindings) => {
+                let len = bindings.len();
+                bindings.retain(|v| v.source != source);
+                len != bindings.len()
+            }
+            None => false,
+        }
+    }
+}
+
+/// All the binding sets of a player, which could be saved and loaded as user configurations.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct BindingProfile {
+    /// The gamepad used by this profile, any connected gamepad if not specified.
+    #[serde(default)]
+    pub gamepad: Option<GamepadId>,
+    #[serde(default)]
+    pub contexts: Vec<BindingSet>,
+}
+
+impl BindingProfile {
+    /// Loads a profile from JSON.
+    pub fn from_json(json: &str) -> Result<Self> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    /// Saves the profile into JSON.
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    #[inline]
+    pub fn context(&self, name: &str) -> Option<&BindingSet> {
+        self.contexts.iter().find(|v| v.name == name)
+    }
+
+    #[inline]
+    pub fn context_mut(&mut self, name: &str) -> Option<&mut BindingSet> {
+        self.contexts.iter_mut().find(|v| v.name == name)
+    }
+}
+
+/// The binding profile with the stack of active contexts.
+#[derive(Debug, Default)]
+pub struct InputMap {
+    profile: BindingProfile,
+    active: Vec<String>,
+}
+
+impl InputMap {
+    pub fn new() -> Self {
+        InputMap::default()
+    }
+
+    /// Replaces the profile, the active contexts that still exist are kept.
+    pub fn set_profile(&mut self, profile: BindingProfile) {
+        self.profile = profile;
+
+        let profile = &self.profile;
+        self.active.retain(|v| profile.context(v).is_some());
+    }
+
+    #[inline]
+    pub fn profile(&self) -> &BindingProfile {
+        &self.profile
+    }
+
+    #[inline]
+    pub fn profile_mut(&mut self) -> &mut BindingProfile {
+        &mut self.profile
+    }
+
+    /// Activates the context on the top of stack.
+    pub fn push_context(&mut self, name: &str) -> Result<()> {
+        if self.profile.context(name).is_none() {
+            bail!("Binding context {} is undefined.", name);
+        }
+
+        self.active.retain(|v| v != name);
+        self.active.push(name.to_owned());
+        Ok(())
+    }
+
+    /// Deactivates the context, returns false if its not active.
+    pub fn pop_context(&mut self, name: &str) -> bool {
+        let len = self.active.len();
+        self.active.retain(|v| v != name);
+        len != self.active.len()
+    }
+
+    /// Gets the active contexts, from bottom to top.
+    #[inline]
+    pub fn active_contexts(&self) -> &[String] {
+        &self.active
+    }
+
+    /// Gets the bindings of action in the top-most active context which binds it.
+    pub fn action_bindings(&self, action: &str) -> &[ActionBinding] {
+        for name in self.active.iter().rev() {
+            if let Some(v) = self
+                .profile
+                .context(name)
+                .and_then(|v| v.actions.get(action))
+            {
+                return v;
+            }
+        }
+
+        &[]
+    }
+
+    /// Gets the bindings of axis in the top-most active context which binds it.
+    pub fn axis_bindings(&self, axis: &str) -> &[AxisBinding] {
+        for name in self.active.iter().rev() {
+            if let Some(v) = self.profile.context(name).and_then(|v| v.axes.get(axis)) {
+                return v;
+            }
+        }
+
+        &[]
+    }
+}
@@ -44,6 +44,27 @@
 //! input::text();
 //! ```
 //!
+//! The polled states might lose the order of inputs, e.g. a key could be pressed and
+//! released in the same frame. The buffered events of last frame are available in order,
+//! with the time they were received and the modifier keys held down:
+//!
+//! ```rust
+//! use crayon::prelude::*;
+//! application::oneshot().unwrap();
+//!
+//! for v in input::events() {
+//!     if let InputEvent::KeyboardPressed { key: Key::Z } = v.event {
+//!         if v.modifiers.ctrl {
+//!             // Undo.
+//!         }
+//!     }
+//! }
+//! ```
+//!
+//! Text fields could also display the text being composed with input method editor with
+//! `input::ime_composition`, and move the candidate window to the caret with
+//! `window::set_ime_position`.
+//!
 //! # Mouse Inputs
 //!
 //! Similar to keyboard device, to find out whether the host platform provides mouse
@@ -155,28 +176,123 @@
 //!     GesturePan::End { start_position, position } => { },
 //!     GesturePan::None => { },
 //! }
+//!
+//! // Gets the pinching gesture of two fingers.
+//! match input::finger_pinch() {
+//!     GesturePinch::Start { center } => { },
+//!     GesturePinch::Move { center, scale, delta } => { },
+//!     GesturePinch::End { center, scale } => { },
+//!     GesturePinch::None => { },
+//! }
+//!
+//! // Gets the swipe gesture.
+//! if let GestureSwipe::Action { direction, .. } = input::finger_swipe() { }
 //! ```
 //!
+//! The rotating gesture (`finger_rotate`) and long press gesture (`finger_long_press`)
+//! are also available, and their thresholds could be configured with `TouchPadParams`.
+//!
 //! Notes we also have APIs with `_in_points` suffix to works in logical points.
 //!
+//! # Gamepad Inputs
+//!
+//! Multiple gamepads are supported, which are identified by `GamepadId` in the order
+//! they are connected. The buttons and axes are mapped into the standardized layout of
+//! XBox controller, with dead zones applied.
+//!
+//! ```rust
+//! use crayon::prelude::*;
+//! application::oneshot().unwrap();
+//!
+//! for id in input::gamepads() {
+//!     // Checks if a gamepad button has been pressed during last frame.
+//!     input::is_gamepad_button_press(id, GamepadButton::South);
+//!
+//!     // Gets the position of left thumb stick.
+//!     input::gamepad_left_stick(id);
+//! }
+//! ```
+//!
+//! Gamepads are only discovered on Linux (with evdev) right now.
+//!
+//! # Actions and Axes
+//!
+//! Instead of polling devices directly, inputs could be bound to named actions and axes.
+//! Bindings are grouped into contexts (e.g. "gameplay" and "menu"), and the active
+//! contexts are organized as a stack.
+//!
+//! ```rust
+//! use crayon::prelude::*;
+//! application::oneshot().unwrap();
+//!
+//! let mut gameplay = BindingSet::new("gameplay");
+//! gameplay
+//!     .bind_action("jump", ActionBinding::Key(Key::Space))
+//!     .bind_action("jump", ActionBinding::Gamepad(GamepadButton::South))
+//!     .bind_axis("move_x", AxisBinding::keys(Key::A, Key::D));
+//!
+//! let mut profile = BindingProfile::default();
+//! profile.contexts.push(gameplay);
+//!
+//! input::set_binding_profile(profile);
+//! input::push_binding_context("gameplay").unwrap();
+//!
+//! // Checks if the action has been pressed during last frame.
+//! input::is_action_press("jump");
+//!
+//! // Gets the value of axis.
+//! input::axis("move_x");
+//! ```
+//!
+//! Binding profiles could be serialized with serde, and rebound at runtime with
+//! `pressed_action_binding`.
+//!
+//! # Recording and Playback
+//!
+//! The input events could be recorded with frame timestamps, and played back later in
+//! place of live inputs. Combined with the headless mode, it's useful to reproduce bug
+//! reports and run scripted tests.
+//!
+//! ```rust
+//! use crayon::prelude::*;
+//! application::oneshot().unwrap();
+//!
+//! input::start_recording();
+//! application::run_frames(2).unwrap();
+//! let recording = input::stop_recording().unwrap();
+//!
+//! // Live inputs are ignored until the playback is finished.
+//! input::play_recording(recording);
+//! application::run_frames(2).unwrap();
+//! assert!(!input::is_playing());
+//! ```
+//!
 //! # Others Inputs
 //!
 //! Somethings that nice to have, but not implemented right now:
 //!
-//! 1. Device sensor inputs;
-//! 2. Game pad inputs;
-//! 3. More touch gesture like `Pinching`.
+//! 1. Device sensor inputs.
 
+pub mod evdev;
 pub mod events;
+pub mod gamepad;
 pub mod keyboard;
+pub mod mapping;
 pub mod mouse;
+pub mod record;
 pub mod touchpad;
 
 pub mod prelude {
-    pub use super::events::InputEvent;
-    pub use super::keyboard::{Key, KeyboardParams};
+    pub use super::events::{ImeComposition, ImeEvent, InputEvent, TimedInputEvent};
+    pub use super::gamepad::{GamepadAxis, GamepadButton, GamepadId, GamepadParams};
+    pub use super::keyboard::{Key, KeyboardParams, Modifiers};
+    pub use super::mapping::{ActionBinding, AxisBinding, AxisSource, BindingProfile, BindingSet};
     pub use super::mouse::{MouseButton, MouseParams};
-    pub use super::touchpad::{GesturePan, GestureTap, TouchPadParams};
+    pub use super::record::{InputRecording, RecordedEvent};
+    pub use super::touchpad::{
+        GesturePan, GesturePinch, GestureRotate, GestureSwipe, GestureTap, SwipeDirection,
+        TouchPadParams,
+    };
     pub use super::InputParams;
 }
 
@@ -185,12 +301,22 @@ mod system;
 /// Maximum touches that would be tracked at sametime.
 pub const MAX_TOUCHES: usize = 4;
 
+use std::time::Duration;
+
+use crate::errors::*;
 use crate::math::prelude::Vector2;
 
+use self::evdev::VirtualGamepad;
+use self::events::{ImeComposition, TimedInputEvent};
+use self::gamepad::{GamepadAxis, GamepadButton, GamepadId, GamepadParams};
 use self::inside::{ctx, CTX};
-use self::keyboard::{Key, KeyboardParams};
+use self::keyboard::{Key, KeyboardParams, Modifiers};
+use self::mapping::{ActionBinding, BindingProfile};
 use self::mouse::{MouseButton, MouseParams};
-use self::touchpad::{GesturePan, GestureTap, TouchPadParams};
+use self::record::InputRecording;
+use self::touchpad::{
+    GesturePan, GesturePinch, GestureRotate, GestureSwipe, GestureTap, TouchPadParams,
+};
 
 /// The setup parameters of all supported input devices.
 #[derive(Debug, Clone, Copy, Default)]
@@ -199,6 +325,7 @@ pub struct InputParams {
     pub keyboard: KeyboardParams,
     pub mouse: MouseParams,
     pub touchpad: TouchPadParams,
+    pub gamepad: GamepadParams,
 }
 
 /// Checks if the resource system is enabled.
@@ -249,6 +376,33 @@ pub fn text() -> String {
     ctx().text()
 }
 
+/// Gets the modifier keys currently held down.
+#[inline]
+pub fn modifiers() -> Modifiers {
+    ctx().modifiers()
+}
+
+/// Gets an iterator over the events received during last frame in order, with the time
+/// they were received and the modifier keys held down. Unlike the polled states, fast
+/// sequences like pressing and releasing a key in the same frame are preserved.
+#[inline]
+pub fn events() -> impl Iterator<Item = TimedInputEvent> {
+    ctx().events().into_iter()
+}
+
+/// Gets the text being composed with input method editor, which should be displayed at
+/// the caret of text field. The committed text is available with `text` and `events`.
+///
+/// # Platform-specific
+///
+/// The composing text is only available in browser. On other platforms, it is displayed by
+/// the input method editor itself, and the position could be set with
+/// `window::set_ime_position`.
+#[inline]
+pub fn ime_composition() -> Option<ImeComposition> {
+    ctx().ime_composition()
+}
+
 /// Returns true if a mouse is attached
 #[inline]
 pub fn has_mouse_attached() -> bool {
@@ -303,6 +457,14 @@ pub fn mouse_scroll() -> Vector2<f32> {
     ctx().mouse_scroll()
 }
 
+/// Gets the raw motion of mouse device since last frame. Unlike `mouse_movement`, it is not
+/// affected by the cursor being grabbed or reaching the border of window, which makes it
+/// suitable for FPS-style camera controls.
+#[inline]
+pub fn mouse_motion() -> Vector2<f32> {
+    ctx().mouse_motion()
+}
+
 /// Returns true if a touchpad is attached
 #[inline]
 pub fn has_touchpad_attached() -> bool {
@@ -339,6 +501,214 @@ pub fn finger_pan() -> GesturePan {
     ctx().finger_pan()
 }
 
+/// Gets the long press gesture, which is detected once a touch has been held down still
+/// for a while.
+#[inline]
+pub fn finger_long_press() -> GestureTap {
+    ctx().finger_long_press()
+}
+
+/// Gets the swipe gesture.
+#[inline]
+pub fn finger_swipe() -> GestureSwipe {
+    ctx().finger_swipe()
+}
+
+/// Gets the pinching gesture of two fingers.
+#[inline]
+pub fn finger_pinch() -> GesturePinch {
+    ctx().finger_pinch()
+}
+
+/// Gets the rotating gesture of two fingers.
+#[inline]
+pub fn finger_rotate() -> GestureRotate {
+    ctx().finger_rotate()
+}
+
+/// Returns true if a gamepad is connected.
+#[inline]
+pub fn has_gamepad_attached() -> bool {
+    ctx().has_gamepad_attached()
+}
+
+/// Gets the identifiers of connected gamepads in ascending order.
+#[inline]
+pub fn gamepads() -> Vec<GamepadId> {
+    ctx().gamepads()
+}
+
+/// Checks if the gamepad is connected.
+#[inline]
+pub fn is_gamepad_connected(id: GamepadId) -> bool {
+    ctx().is_gamepad_connected(id)
+}
+
+/// Gets the name of gamepad.
+#[inline]
+pub fn gamepad_name(id: GamepadId) -> Option<String> {
+    ctx().gamepad_name(id)
+}
+
+/// Checks if a gamepad button is currently held down.
+#[inline]
+pub fn is_gamepad_button_down(id: GamepadId, button: GamepadButton) -> bool {
+    ctx().is_gamepad_button_down(id, button)
+}
+
+/// Checks if a gamepad button has been pressed during last frame.
+#[inline]
+pub fn is_gamepad_button_press(id: GamepadId, button: GamepadButton) -> bool {
+    ctx().is_gamepad_button_press(id, button)
+}
+
+/// Checks if a gamepad button has been released during last frame.
+#[inline]
+pub fn is_gamepad_button_release(id: GamepadId, button: GamepadButton) -> bool {
+    ctx().is_gamepad_button_release(id, button)
+}
+
+/// Gets the value of gamepad axis with dead zones applied. Sticks are in range [-1, 1],
+/// and triggers are in range [0, 1].
+#[inline]
+pub fn gamepad_axis(id: GamepadId, axis: GamepadAxis) -> f32 {
+    ctx().gamepad_axis(id, axis)
+}
+
+/// Gets the position of left thumb stick with dead zone applied.
+#[inline]
+pub fn gamepad_left_stick(id: GamepadId) -> Vector2<f32> {
+    ctx().gamepad_left_stick(id)
+}
+
+/// Gets the position of right thumb stick with dead zone applied.
+#[inline]
+pub fn gamepad_right_stick(id: GamepadId) -> Vector2<f32> {
+    ctx().gamepad_right_stick(id)
+}
+
+/// Plays the rumble effect on gamepad, with the magnitude of strong (low frequency) and
+/// weak (high frequency) motors in range [0, 1].
+///
+/// # Platform-specific
+///
+/// Returns error if the gamepad does not support force feedback.
+#[inline]
+pub fn gamepad_rumble(id: GamepadId, strong: f32, weak: f32, duration: Duration) -> Result<()> {
+    ctx().gamepad_rumble(id, strong, weak, duration)
+}
+
+/// Attachs a virtual gamepad, which will be connected in the next frame.
+#[inline]
+pub fn attach_virtual_gamepad(gamepad: VirtualGamepad) {
+    ctx().attach_virtual_gamepad(gamepad);
+}
+
+/// Replaces the binding profile. The active contexts that still exist in the new profile
+/// are kept.
+#[inline]
+pub fn set_binding_profile(profile: BindingProfile) {
+    ctx().set_binding_profile(profile);
+}
+
+/// Gets the binding profile, which has all the changes made at runtime.
+#[inline]
+pub fn binding_profile() -> BindingProfile {
+    ctx().binding_profile()
+}
+
+/// Modifies the binding profile at runtime, e.g. rebinding an action.
+#[inline]
+pub fn update_binding_profile<F, R>(func: F) -> R
+where
+    F: FnOnce(&mut BindingProfile) -> R,
+{
+    ctx().update_binding_profile(func)
+}
+
+/// Activates the binding context on the top of stack.
+#[inline]
+pub fn push_binding_context(name: &str) -> Result<()> {
+    ctx().push_binding_context(name)
+}
+
+/// Deactivates the binding context, returns false if its not active.
+#[inline]
+pub fn pop_binding_context(name: &str) -> bool {
+    ctx().pop_binding_context(name)
+}
+
+/// Checks if any input bound to the action is currently held down.
+#[inline]
+pub fn is_action_down(action: &str) -> bool {
+    ctx().is_action_down(action)
+}
+
+/// Checks if any input bound to the action has been pressed during last frame.
+#[inline]
+pub fn is_action_press(action: &str) -> bool {
+    ctx().is_action_press(action)
+}
+
+/// Checks if any input bound to the action has been released during last frame.
+#[inline]
+pub fn is_action_release(action: &str) -> bool {
+    ctx().is_action_release(action)
+}
+
+/// Gets the value of axis. If there are multiple bindings, the one with the largest
+/// magnitude wins.
+#[inline]
+pub fn axis(axis: &str) -> f32 {
+    ctx().axis(axis)
+}
+
+/// Gets an input that has been pressed during last frame, which is useful to rebind
+/// actions interactively.
+#[inline]
+pub fn pressed_action_binding() -> Option<ActionBinding> {
+    ctx().pressed_action_binding()
+}
+
+/// Starts recording the input events. The connected gamepads are recorded as connected in
+/// the first frame, but the keys and buttons held down before are not captured.
+#[inline]
+pub fn start_recording() {
+    ctx().start_recording();
+}
+
+/// Stops recording, returns the recorded events.
+#[inline]
+pub fn stop_recording() -> Option<InputRecording> {
+    ctx().stop_recording()
+}
+
+/// Checks if the recording is in progress.
+#[inline]
+pub fn is_recording() -> bool {
+    ctx().is_recording()
+}
+
+/// Plays the recording back from the next frame. Input states are reset, and the live
+/// inputs are ignored until the playback is finished or stopped.
+#[inline]
+pub fn play_recording(recording: InputRecording) {
+    ctx().play_recording(recording);
+}
+
+/// Stops the playback and resumes live inputs, returns false if there is no playback in
+/// progress.
+#[inline]
+pub fn stop_playback() -> bool {
+    ctx().stop_playback()
+}
+
+/// Checks if the playback is in progress.
+#[inline]
+pub fn is_playing() -> bool {
+    ctx().is_playing()
+}
+
 pub(crate) mod inside {
     use super::system::InputSystem;
     use super::InputParams;
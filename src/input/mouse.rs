@@ -29,7 +29,7 @@ impl Default for MouseParams {
 }
 
 /// Describes a button of a mouse controller.
-#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
+#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
 pub enum MouseButton {
     Left,
     Right,
@@ -44,6 +44,7 @@ pub struct Mouse {
     last_position: Vector2<f32>,
     position: Vector2<f32>,
     scrol: Vector2<f32>,
+    motion: Vector2<f32>,
     click_detectors: FastHashMap<MouseButton, ClickDetector>,
     params: MouseParams,
 }
@@ -58,6 +59,7 @@ impl Mouse {
             last_position: Vector2::new(0.0, 0.0),
             position: Vector2::new(0.0, 0.0),
             scrol: Vector2::new(0.0, 0.0),
+            motion: Vector2::new(0.0, 0.0),
             click_detectors: FastHashMap::default(),
         }
     }
@@ -70,6 +72,7 @@ impl Mouse {
         self.last_position = Vector2::new(0.0, 0.0);
         self.position = Vector2::new(0.0, 0.0);
         self.scrol = Vector2::new(0.0, 0.0);
+        self.motion = Vector2::new(0.0, 0.0);
 
         for v in self.click_detectors.values_mut() {
             v.reset();
@@ -81,6 +84,7 @@ impl Mouse {
         self.presses.clear();
         self.releases.clear();
         self.scrol = Vector2::new(0.0, 0.0);
+        self.motion = Vector2::new(0.0, 0.0);
         self.last_position = self.position;
 
         for v in self.click_detectors.values_mut() {
@@ -93,6 +97,11 @@ impl Mouse {
         self.position = position.into();
     }
 
+    #[inline]
+    pub fn on_motion(&mut self, delta: (f32, f32)) {
+        self.motion += Vector2::from(delta);
+    }
+
     #[inline]
     pub fn on_button_pressed(&mut self, button: MouseButton) {
         if !self.downs.contains(&button) {
@@ -145,6 +154,18 @@ impl Mouse {
         self.releases.contains(&button)
     }
 
+    /// Gets the buttons currently held down.
+    #[inline]
+    pub fn downs(&self) -> impl Iterator<Item = MouseButton> + '_ {
+        self.downs.iter().cloned()
+    }
+
+    /// Gets the buttons pressed during last frame.
+    #[inline]
+    pub fn presses(&self) -> impl Iterator<Item = MouseButton> + '_ {
+        self.presses.iter().cloned()
+    }
+
     #[inline]
     pub fn is_button_click(&self, button: MouseButton) -> bool {
         if let Some(v) = self.click_detectors.get(&button) {
@@ -177,6 +198,11 @@ impl Mouse {
     pub fn scroll(&self) -> Vector2<f32> {
         self.scrol
     }
+
+    #[inline]
+    pub fn motion(&self) -> Vector2<f32> {
+        self.motion
+    }
 }
 
 struct ClickDetector {
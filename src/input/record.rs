@@ -0,0 +1,207 @@
+//! Records the stream of input events, and plays it back later in place of live inputs.
+//!
+//! Combined with the headless mode, recordings could be used to reproduce bug reports and
+//! to run scripted tests.
+//!
+//! ```rust,ignore
+//! input::start_recording();
+//! application::run_frames(60)?;
+//! let recording = input::stop_recording().unwrap();
+//! std::fs::write("inputs.json", recording.to_json()?)?;
+//!
+//! let recording = InputRecording::from_json(&std::fs::read_to_string("inputs.json")?)?;
+//! input::play_recording(recording);
+//! application::run_frames(60)?;
+//! ```
+
+use std::time::Duration;
+
+use crate::errors::*;
+
+use super::events::InputEvent;
+
+/// A recorded input event with its frame timestamp.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct RecordedEvent {
+    /// The frame index since the recording started.
+    pub frame: u64,
+    /// The real time since the recording started.
+    pub time: Duration,
+    pub event: InputEvent,
+}
+
+/// A stream of recorded input events.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct InputRecording {
+    /// The number of frames recorded.
+    pub frames: u64,
+    /// The recorded events sorted by frame.
+    pub events: Vec<RecordedEvent>,
+}
+
+impl InputRecording {
+    /// Loads a recording from JSON.
+    pub fn from_json(json: &str) -> Result<Self> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    /// Saves the recording into JSON.
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string(self)?)
+    }
+
+    /// Gets the real time duration of recording.
+    #[inline]
+    pub fn duration(&self) -> Duration {
+        self.events
+            .last()
+            .map(|v| v.time)
+            .unwrap_or_else(|| Duration::from_secs(0))
+    }
+}
+
+pub(crate) enum Recorder {
+    Idle,
+    Recording {
+        frame: u64,
+        start: Duration,
+        recording: InputRecording,
+    },
+    Playing {
+        frame: u64,
+        cursor: usize,
+        recording: InputRecording,
+    },
+}
+
+impl Recorder {
+    pub fn new() -> Self {
+        Recorder::Idle
+    }
+
+    #[inline]
+    pub fn is_recording(&self) -> bool {
+        match *self {
+            Recorder::Recording { .. } => true,
+            _ => false,
+        }
+    }
+
+    #[inline]
+    pub fn is_playing(&self) -> bool {
+        match *self {
+            Recorder::Playing { .. } => true,
+            _ => false,
+        }
+    }
+
+    /// Starts recording, the playback in progress is stopped.
+    pub fn start_recording(&mut self, now: Duration) {
+        *self = Recorder::Recording {
+            frame: 0,
+            start: now,
+            recording: InputRecording::default(),
+        };
+    }
+
+    /// Stops recording, returns the recorded events.
+    pub fn stop_recording(&mut self) -> Option<InputRecording> {
+        if !self.is_recording() {
+            return None;
+        }
+
+        match std::mem::replace(self, Recorder::Idle) {
+            Recorder::Recording {
+                frame,
+                mut recording,
+                ..
+            } => {
+                recording.frames = frame;
+                Some(recording)
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    /// Starts playback, the recording in progress is discarded.
+    pub fn play(&mut self, mut recording: InputRecording) {
+        recording.events.sort_by_key(|v| v.frame);
+
+        *self = Recorder::Playing {
+            frame: 0,
+            cursor: 0,
+            recording,
+        };
+    }
+
+    /// Stops playback, returns false if there is no playback in progress.
+    pub fn stop_playback(&mut self) -> bool {
+        if !self.is_playing() {
+            return false;
+        }
+
+        *self = Recorder::Idle;
+        true
+    }
+
+    /// Records an event if recording is in progress.
+    pub fn record(&mut self, event: InputEvent, now: Duration) {
+        if let Recorder::Recording {
+            frame,
+            start,
+            ref mut recording,
+        } = *self
+        {
+            recording.events.push(RecordedEvent {
+                frame,
+                time: now
+                    .checked_sub(start)
+                    .unwrap_or_else(|| Duration::from_secs(0)),
+                event,
+            });
+        }
+    }
+
+    /// Emits the events of current frame if playback is in progress.
+    pub fn playback(&mut self, events: &mut Vec<InputEvent>) {
+        if let Recorder::Playing {
+            frame,
+            ref mut cursor,
+            ref recording,
+        } = *self
+        {
+            while let Some(v) = recording.events.get(*cursor) {
+                if v.frame > frame {
+                    break;
+                }
+
+                events.push(v.event);
+                *cursor += 1;
+            }
+        }
+    }
+
+    /// Advances to the next frame, returns true if the playback has just been finished.
+    pub fn advance(&mut self) -> bool {
+        match *self {
+            Recorder::Recording { ref mut frame, .. } => {
+                *frame += 1;
+                false
+            }
+            Recorder::Playing {
+                ref mut frame,
+                ref recording,
+                ..
+            } => {
+                *frame += 1;
+                if *frame >= recording.frames {
+                    *self = Recorder::Idle;
+                    true
+                } else {
+                    false
+                }
+            }
+            Recorder::Idle => false,
+        }
+    }
+}
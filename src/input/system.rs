@@ -1,12 +1,20 @@
-use std::sync::{Arc, RwLock};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Duration;
 
+use crate::application::ins::Inspectable;
 use crate::application::prelude::{LifecycleListener, LifecycleListenerHandle};
 use crate::window::prelude::{Event, EventListener, EventListenerHandle};
 
-use super::events::InputEvent;
-use super::keyboard::{Key, Keyboard};
+use super::evdev::{EvdevBackend, VirtualGamepad};
+use super::events::{ImeComposition, ImeEvent, InputEvent, TimedInputEvent};
+use super::gamepad::{GamepadAxis, GamepadButton, GamepadId, Gamepads};
+use super::keyboard::{Key, Keyboard, Modifiers};
+use super::mapping::{ActionBinding, AxisSource, BindingProfile, InputMap};
 use super::mouse::{Mouse, MouseButton};
-use super::touchpad::{GesturePan, GestureTap, TouchPad, TouchState};
+use super::record::{InputRecording, Recorder};
+use super::touchpad::{
+    GesturePan, GesturePinch, GestureRotate, GestureSwipe, GestureTap, TouchPad, TouchState,
+};
 use super::InputParams;
 
 use crate::math::prelude::Vector2;
@@ -25,86 +33,289 @@ struct InputState {
     mouse: RwLock<Mouse>,
     keyboard: RwLock<Keyboard>,
     touchpad: RwLock<TouchPad>,
+    gamepads: RwLock<Gamepads>,
+    gamepad_backend: Mutex<EvdevBackend>,
+    mapping: RwLock<InputMap>,
+    recorder: Mutex<Recorder>,
+    events: RwLock<Vec<TimedInputEvent>>,
+    epoch: u64,
+    ime: RwLock<Option<ImeComposition>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Phase {
+    Down,
+    Press,
+    Release,
 }
 
 impl EventListener for Arc<InputState> {
     fn on(&mut self, v: &Event) -> Result<(), failure::Error> {
-        if let Event::InputDevice(v) = *v {
-            match v {
-                InputEvent::MouseMoved { position } => {
-                    if self.touch_emulation_button.read().unwrap().is_some() {
-                        self.touchpad.write().unwrap().on_touch(
-                            255,
-                            TouchState::Move,
-                            self.mouse.read().unwrap().position(),
-                        );
+        match *v {
+            Event::InputDevice(v) => self.dispatch(v, crate::window::polled_timestamp()),
+
+            Event::Ime(ref v) => {
+                let mut ime = self.ime.write().unwrap();
+                match *v {
+                    ImeEvent::CompositionStarted => {
+                        *ime = Some(ImeComposition {
+                            text: String::new(),
+                            cursor: None,
+                        });
                     }
-
-                    self.mouse.write().unwrap().on_move(position)
+                    ImeEvent::CompositionUpdated { ref text, cursor } => {
+                        *ime = Some(ImeComposition {
+                            text: text.clone(),
+                            cursor,
+                        });
+                    }
+                    ImeEvent::CompositionEnded => *ime = None,
                 }
+            }
 
-                InputEvent::MousePressed { button } => {
-                    if self.touch_emulation {
-                        *self.touch_emulation_button.write().unwrap() = Some(button);
-                        self.touchpad.write().unwrap().on_touch(
-                            255,
-                            TouchState::Start,
-                            self.mouse.read().unwrap().position(),
-                        );
-                    }
+            Event::Window(_) => {}
+        }
 
-                    self.mouse.write().unwrap().on_button_pressed(button)
-                }
+        Ok(())
+    }
+}
 
-                InputEvent::MouseReleased { button } => {
-                    if *self.touch_emulation_button.read().unwrap() == Some(button) {
-                        *self.touch_emulation_button.write().unwrap() = None;
+impl Inspectable for InputState {
+    fn inspect(&self) -> serde_json::Value {
+        // Takes the locks one by one, to keep away from blocking the main thread.
+        let keys: Vec<_> = self.keyboard.read().unwrap().downs().collect();
+        let (position, buttons) = {
+            let mouse = self.mouse.read().unwrap();
+            (mouse.position(), mouse.downs().collect::<Vec<_>>())
+        };
+
+        let touches: Vec<_> = {
+            let touchpad = self.touchpad.read().unwrap();
+            (0..)
+                .map(|i| touchpad.position(i))
+                .take_while(Option::is_some)
+                .flatten()
+                .map(|v| [v.x, v.y])
+                .collect()
+        };
+
+        let gamepads: Vec<_> = {
+            let connected = self.gamepads.read().unwrap().connected();
+            let backend = self.gamepad_backend.lock().unwrap();
+            connected
+                .into_iter()
+                .map(|id| json!({ "id": id, "name": backend.name(id) }))
+                .collect()
+        };
+
+        let contexts = self.mapping.read().unwrap().active_contexts().to_vec();
+        let events = self.events.read().unwrap().len();
+        let (recording, playing) = {
+            let recorder = self.recorder.lock().unwrap();
+            (recorder.is_recording(), recorder.is_playing())
+        };
+
+        json!({
+            "keys": keys,
+            "mouse": {
+                "position": [position.x, position.y],
+                "buttons": buttons,
+            },
+            "touches": touches,
+            "gamepads": gamepads,
+            "contexts": contexts,
+            "events": events,
+            "recording": recording,
+            "playing": playing,
+        })
+    }
+}
 
-                        self.touchpad.write().unwrap().on_touch(
-                            255,
-                            TouchState::End,
-                            self.mouse.read().unwrap().position(),
-                        );
-                    }
+impl InputState {
+    // Records and applies the live input event which was polled at `time`.
+    fn dispatch(&self, v: InputEvent, time: u64) {
+        {
+            // Live inputs are replaced during playback.
+            let mut recorder = self.recorder.lock().unwrap();
+            if recorder.is_playing() {
+                return;
+            }
 
-                    self.mouse.write().unwrap().on_button_released(button)
-                }
+            recorder.record(v, crate::application::real_time());
+        }
 
-                InputEvent::MouseWheel { delta } => {
-                    self.mouse.write().unwrap().on_wheel_scroll(delta)
-                }
+        self.apply(v, time);
+    }
 
-                InputEvent::KeyboardPressed { key } => {
-                    self.keyboard.write().unwrap().on_key_pressed(key)
-                }
+    // Applies the input event which was polled at `time`, in microseconds.
+    fn apply(&self, v: InputEvent, time: u64) {
+        self.update(v);
+
+        self.events.write().unwrap().push(TimedInputEvent {
+            event: v,
+            time: Duration::from_micros(time.saturating_sub(self.epoch)),
+            modifiers: self.keyboard.read().unwrap().modifiers(),
+        });
+    }
 
-                InputEvent::KeyboardReleased { key } => {
-                    self.keyboard.write().unwrap().on_key_released(key)
+    fn update(&self, v: InputEvent) {
+        match v {
+            InputEvent::MouseMoved { position } => {
+                if self.touch_emulation_button.read().unwrap().is_some() {
+                    self.touchpad.write().unwrap().on_touch(
+                        255,
+                        TouchState::Move,
+                        self.mouse.read().unwrap().position(),
+                    );
                 }
 
-                InputEvent::ReceivedCharacter { character } => {
-                    self.keyboard.write().unwrap().on_char(character)
+                self.mouse.write().unwrap().on_move(position)
+            }
+
+            InputEvent::MousePressed { button } => {
+                if self.touch_emulation {
+                    *self.touch_emulation_button.write().unwrap() = Some(button);
+                    self.touchpad.write().unwrap().on_touch(
+                        255,
+                        TouchState::Start,
+                        self.mouse.read().unwrap().position(),
+                    );
                 }
 
-                InputEvent::Touch {
-                    id,
-                    state,
-                    position,
-                } => {
-                    self.touchpad.write().unwrap().on_touch(id, state, position);
+                self.mouse.write().unwrap().on_button_pressed(button)
+            }
+
+            InputEvent::MouseReleased { button } => {
+                if *self.touch_emulation_button.read().unwrap() == Some(button) {
+                    *self.touch_emulation_button.write().unwrap() = None;
+
+                    self.touchpad.write().unwrap().on_touch(
+                        255,
+                        TouchState::End,
+                        self.mouse.read().unwrap().position(),
+                    );
                 }
+
+                self.mouse.write().unwrap().on_button_released(button)
+            }
+
+            InputEvent::MouseMotion { delta } => {
+                self.mouse.write().unwrap().on_motion(delta)
+            }
+
+            InputEvent::MouseWheel { delta } => {
+                self.mouse.write().unwrap().on_wheel_scroll(delta)
+            }
+
+            InputEvent::KeyboardPressed { key } => {
+                self.keyboard.write().unwrap().on_key_pressed(key)
+            }
+
+            InputEvent::KeyboardReleased { key } => {
+                self.keyboard.write().unwrap().on_key_released(key)
+            }
+
+            InputEvent::ReceivedCharacter { character } => {
+                self.keyboard.write().unwrap().on_char(character)
+            }
+
+            InputEvent::Touch {
+                id,
+                state,
+                position,
+            } => {
+                self.touchpad.write().unwrap().on_touch(id, state, position);
+            }
+
+            InputEvent::GamepadConnected { id } => {
+                self.gamepads.write().unwrap().on_connected(id);
+            }
+
+            InputEvent::GamepadDisconnected { id } => {
+                self.gamepads.write().unwrap().on_disconnected(id);
+            }
+
+            InputEvent::GamepadPressed { id, button } => {
+                self.gamepads.write().unwrap().on_button_pressed(id, button);
+            }
+
+            InputEvent::GamepadReleased { id, button } => {
+                self.gamepads.write().unwrap().on_button_released(id, button);
+            }
+
+            InputEvent::GamepadAxisChanged { id, axis, value } => {
+                self.gamepads
+                    .write()
+                    .unwrap()
+                    .on_axis_changed(id, axis, value);
             }
         }
+    }
 
-        Ok(())
+    fn disconnect_gamepads(&self) {
+        let mut gamepads = self.gamepads.write().unwrap();
+        for id in gamepads.connected() {
+            gamepads.on_disconnected(id);
+        }
+    }
+
+    fn reset(&self) {
+        self.mouse.write().unwrap().reset();
+        self.keyboard.write().unwrap().reset();
+        self.touchpad.write().unwrap().reset();
+        self.gamepads.write().unwrap().reset();
+        self.events.write().unwrap().clear();
+        *self.ime.write().unwrap() = None;
+
+        *self.touch_emulation_button.write().unwrap() = None;
+    }
+
+    // Drops the states of played inputs, and reconnects the live gamepads.
+    fn restore_live_inputs(&self) {
+        self.reset();
+        self.disconnect_gamepads();
+
+        let mut gamepads = self.gamepads.write().unwrap();
+        for id in self.gamepad_backend.lock().unwrap().connected() {
+            gamepads.on_connected(id);
+        }
     }
 }
 
 impl LifecycleListener for Arc<InputState> {
+    fn on_pre_update(&mut self) -> Result<(), failure::Error> {
+        // Gamepads are not driven by window, so we poll them here.
+        let mut events = Vec::new();
+        self.gamepad_backend.lock().unwrap().poll(&mut events);
+
+        let now = crate::application::sys::precise_timestamp();
+        for v in events {
+            self.dispatch(v, now);
+        }
+
+        let mut events = Vec::new();
+        self.recorder.lock().unwrap().playback(&mut events);
+
+        let now = crate::application::sys::precise_timestamp();
+        for v in events {
+            self.apply(v, now);
+        }
+
+        self.touchpad.write().unwrap().update();
+        Ok(())
+    }
+
     fn on_post_update(&mut self) -> Result<(), failure::Error> {
         self.mouse.write().unwrap().advance();
         self.keyboard.write().unwrap().advance();
         self.touchpad.write().unwrap().advance();
+        self.gamepads.write().unwrap().advance();
+        self.events.write().unwrap().clear();
+
+        if self.recorder.lock().unwrap().advance() {
+            self.restore_live_inputs();
+        }
+
         Ok(())
     }
 }
@@ -126,6 +337,13 @@ impl InputSystem {
             mouse: RwLock::new(Mouse::new(setup.mouse)),
             keyboard: RwLock::new(Keyboard::new(setup.keyboard)),
             touchpad: RwLock::new(TouchPad::new(setup.touchpad)),
+            gamepads: RwLock::new(Gamepads::new(setup.gamepad)),
+            gamepad_backend: Mutex::new(EvdevBackend::new(&setup.gamepad)),
+            mapping: RwLock::new(InputMap::new()),
+            recorder: Mutex::new(Recorder::new()),
+            events: RwLock::new(Vec::new()),
+            epoch: crate::application::sys::precise_timestamp(),
+            ime: RwLock::new(None),
         });
 
         InputSystem {
@@ -135,13 +353,13 @@ impl InputSystem {
         }
     }
 
+    pub(crate) fn inspectable(&self) -> Arc<impl Inspectable> {
+        self.state.clone()
+    }
+
     /// Reset input to initial states.
     pub fn reset(&self) {
-        self.state.mouse.write().unwrap().reset();
-        self.state.keyboard.write().unwrap().reset();
-        self.state.touchpad.write().unwrap().reset();
-
-        *self.state.touch_emulation_button.write().unwrap() = None;
+        self.state.reset();
     }
 
     /// Returns true if a keyboard is attached
@@ -182,6 +400,24 @@ impl InputSystem {
         String::from_iter(self.state.keyboard.read().unwrap().captured_chars())
     }
 
+    /// Gets the modifier keys currently held down.
+    #[inline]
+    pub fn modifiers(&self) -> Modifiers {
+        self.state.keyboard.read().unwrap().modifiers()
+    }
+
+    /// Gets the events received during last frame in order.
+    #[inline]
+    pub fn events(&self) -> Vec<TimedInputEvent> {
+        self.state.events.read().unwrap().clone()
+    }
+
+    /// Gets the text being composed with input method editor.
+    #[inline]
+    pub fn ime_composition(&self) -> Option<ImeComposition> {
+        self.state.ime.read().unwrap().clone()
+    }
+
     /// Returns true if a mouse is attached
     #[inline]
     pub fn has_mouse_attached(&self) -> bool {
@@ -241,6 +477,12 @@ impl InputSystem {
         self.state.mouse.read().unwrap().scroll()
     }
 
+    /// Gets the raw motion of mouse device since last frame.
+    #[inline]
+    pub fn mouse_motion(&self) -> Vector2<f32> {
+        self.state.mouse.read().unwrap().motion()
+    }
+
     /// Returns true if a touchpad is attached
     #[inline]
     pub fn has_touchpad_attached(&self) -> bool {
@@ -277,4 +519,357 @@ impl InputSystem {
     pub fn finger_pan(&self) -> GesturePan {
         self.state.touchpad.read().unwrap().pan()
     }
+
+    /// Gets the long press gesture.
+    #[inline]
+    pub fn finger_long_press(&self) -> GestureTap {
+        self.state.touchpad.read().unwrap().long_press()
+    }
+
+    /// Gets the swipe gesture.
+    #[inline]
+    pub fn finger_swipe(&self) -> GestureSwipe {
+        self.state.touchpad.read().unwrap().swipe()
+    }
+
+    /// Gets the pinching gesture of two fingers.
+    #[inline]
+    pub fn finger_pinch(&self) -> GesturePinch {
+        self.state.touchpad.read().unwrap().pinch()
+    }
+
+    /// Gets the rotating gesture of two fingers.
+    #[inline]
+    pub fn finger_rotate(&self) -> GestureRotate {
+        self.state.touchpad.read().unwrap().rotate()
+    }
+
+    /// Returns true if a gamepad is connected.
+    #[inline]
+    pub fn has_gamepad_attached(&self) -> bool {
+        !self.state.gamepads.read().unwrap().connected().is_empty()
+    }
+
+    /// Gets the identifiers of connected gamepads in ascending order.
+    #[inline]
+    pub fn gamepads(&self) -> Vec<GamepadId> {
+        self.state.gamepads.read().unwrap().connected()
+    }
+
+    /// Checks if the gamepad is connected.
+    #[inline]
+    pub fn is_gamepad_connected(&self, id: GamepadId) -> bool {
+        self.state.gamepads.read().unwrap().is_connected(id)
+    }
+
+    /// Gets the name of gamepad.
+    #[inline]
+    pub fn gamepad_name(&self, id: GamepadId) -> Option<String> {
+        self.state.gamepad_backend.lock().unwrap().name(id)
+    }
+
+    /// Checks if a gamepad button is currently held down.
+    #[inline]
+    pub fn is_gamepad_button_down(&self, id: GamepadId, button: GamepadButton) -> bool {
+        self.state.gamepads.read().unwrap().is_button_down(id, button)
+    }
+
+    /// Checks if a gamepad button has been pressed during last frame.
+    #[inline]
+    pub fn is_gamepad_button_press(&self, id: GamepadId, button: GamepadButton) -> bool {
+        self.state
+            .gamepads
+            .read()
+            .unwrap()
+            .is_button_press(id, button)
+    }
+
+    /// Checks if a gamepad button has been released during last frame.
+    #[inline]
+    pub fn is_gamepad_button_release(&self, id: GamepadId, button: GamepadButton) -> bool {
+        self.state
+            .gamepads
+            .read()
+            .unwrap()
+            .is_button_release(id, button)
+    }
+
+    /// Gets the value of gamepad axis with dead zones applied.
+    #[inline]
+    pub fn gamepad_axis(&self, id: GamepadId, axis: GamepadAxis) -> f32 {
+        self.state.gamepads.read().unwrap().axis(id, axis)
+    }
+
+    /// Gets the position of left thumb stick with dead zone applied.
+    #[inline]
+    pub fn gamepad_left_stick(&self, id: GamepadId) -> Vector2<f32> {
+        self.state.gamepads.read().unwrap().left_stick(id)
+    }
+
+    /// Gets the position of right thumb stick with dead zone applied.
+    #[inline]
+    pub fn gamepad_right_stick(&self, id: GamepadId) -> Vector2<f32> {
+        self.state.gamepads.read().unwrap().right_stick(id)
+    }
+
+    /// Plays the rumble effect on gamepad.
+    #[inline]
+    pub fn gamepad_rumble(
+        &self,
+        id: GamepadId,
+        strong: f32,
+        weak: f32,
+        duration: Duration,
+    ) -> Result<(), failure::Error> {
+        self.state
+            .gamepad_backend
+            .lock()
+            .unwrap()
+            .rumble(id, strong, weak, duration)
+    }
+
+    /// Attachs a virtual gamepad, which will be connected in the next frame.
+    #[inline]
+    pub fn attach_virtual_gamepad(&self, gamepad: VirtualGamepad) {
+        self.state
+            .gamepad_backend
+            .lock()
+            .unwrap()
+            .attach(Box::new(gamepad));
+    }
+
+    /// Replaces the binding profile.
+    #[inline]
+    pub fn set_binding_profile(&self, profile: BindingProfile) {
+        self.state.mapping.write().unwrap().set_profile(profile);
+    }
+
+    /// Gets the binding profile, which has all the changes made at runtime.
+    #[inline]
+    pub fn binding_profile(&self) -> BindingProfile {
+        self.state.mapping.read().unwrap().profile().clone()
+    }
+
+    /// Modifies the binding profile at runtime.
+    #[inline]
+    pub fn update_binding_profile<F, R>(&self, func: F) -> R
+    where
+        F: FnOnce(&mut BindingProfile) -> R,
+    {
+        func(self.state.mapping.write().unwrap().profile_mut())
+    }
+
+    /// Activates the binding context on the top of stack.
+    #[inline]
+    pub fn push_binding_context(&self, name: &str) -> Result<(), failure::Error> {
+        self.state.mapping.write().unwrap().push_context(name)
+    }
+
+    /// Deactivates the binding context.
+    #[inline]
+    pub fn pop_binding_context(&self, name: &str) -> bool {
+        self.state.mapping.write().unwrap().pop_context(name)
+    }
+
+    /// Checks if any input bound to the action is currently held down.
+    #[inline]
+    pub fn is_action_down(&self, action: &str) -> bool {
+        self.is_action(action, Phase::Down)
+    }
+
+    /// Checks if any input bound to the action has been pressed during last frame.
+    #[inline]
+    pub fn is_action_press(&self, action: &str) -> bool {
+        self.is_action(action, Phase::Press)
+    }
+
+    /// Checks if any input bound to the action has been released during last frame.
+    #[inline]
+    pub fn is_action_release(&self, action: &str) -> bool {
+        self.is_action(action, Phase::Release)
+    }
+
+    /// Gets the value of axis, the binding with the largest magnitude wins.
+    pub fn axis(&self, axis: &str) -> f32 {
+        let mapping = self.state.mapping.read().unwrap();
+        let gamepads = self.bound_gamepads(mapping.profile().gamepad);
+
+        let mut value = 0.0f32;
+        for v in mapping.axis_bindings(axis) {
+            let x = self.axis_source(v.source, &gamepads) * v.scale;
+            if x.abs() > value.abs() {
+                value = x;
+            }
+        }
+
+        value
+    }
+
+    /// Gets an input pressed during last frame, which is useful to rebind actions.
+    pub fn pressed_action_binding(&self) -> Option<ActionBinding> {
+        if let Some(v) = self.state.keyboard.read().unwrap().presses().next() {
+            return Some(ActionBinding::Key(v));
+        }
+
+        if let Some(v) = self.state.mouse.read().unwrap().presses().next() {
+            return Some(ActionBinding::Mouse(v));
+        }
+
+        let gamepads = self.state.gamepads.read().unwrap();
+        for id in gamepads.connected() {
+            if let Some(&v) = gamepads.presses(id).first() {
+                return Some(ActionBinding::Gamepad(v));
+            }
+        }
+
+        None
+    }
+
+    fn bound_gamepads(&self, gamepad: Option<GamepadId>) -> Vec<GamepadId> {
+        match gamepad {
+            Some(id) => vec![id],
+            None => self.state.gamepads.read().unwrap().connected(),
+        }
+    }
+
+    fn is_action(&self, action: &str, phase: Phase) -> bool {
+        let mapping = self.state.mapping.read().unwrap();
+        let bindings = mapping.action_bindings(action);
+        if bindings.is_empty() {
+            return false;
+        }
+
+        let gamepads = self.bound_gamepads(mapping.profile().gamepad);
+        bindings
+            .iter()
+            .any(|&v| self.is_action_binding(v, phase, &gamepads))
+    }
+
+    fn is_action_binding(&self, v: ActionBinding, phase: Phase, gamepads: &[GamepadId]) -> bool {
+        match v {
+            ActionBinding::Key(key) => match phase {
+                Phase::Down => self.is_key_down(key),
+                Phase::Press => self.is_key_press(key),
+                Phase::Release => self.is_key_release(key),
+            },
+            ActionBinding::Mouse(button) => match phase {
+                Phase::Down => self.is_mouse_down(button),
+                Phase::Press => self.is_mouse_press(button),
+                Phase::Release => self.is_mouse_release(button),
+            },
+            ActionBinding::Gamepad(button) => gamepads.iter().any(|&id| match phase {
+                Phase::Down => self.is_gamepad_button_down(id, button),
+                Phase::Press => self.is_gamepad_button_press(id, button),
+                Phase::Release => self.is_gamepad_button_release(id, button),
+            }),
+            // The wheel and gestures are instant, they are pressed and released in the same
+            // frame.
+            ActionBinding::WheelUp => self.mouse_scroll().y > 0.0,
+            ActionBinding::WheelDown => self.mouse_scroll().y < 0.0,
+            ActionBinding::Tap => match self.finger_tap() {
+                GestureTap::Action { .. } => true,
+                GestureTap::None => false,
+            },
+            ActionBinding::DoubleTap => match self.finger_double_tap() {
+                GestureTap::Action { .. } => true,
+                GestureTap::None => false,
+            },
+        }
+    }
+
+    fn axis_source(&self, v: AxisSource, gamepads: &[GamepadId]) -> f32 {
+        let button = |id, b| {
+            if self.is_gamepad_button_down(id, b) {
+                1.0
+            } else {
+                0.0
+            }
+        };
+
+        let largest = |values: &mut dyn Iterator<Item = f32>| {
+            values.fold(0.0f32, |acc, v| if v.abs() > acc.abs() { v } else { acc })
+        };
+
+        match v {
+            AxisSource::Keys { negative, positive } => {
+                let n = if self.is_key_down(negative) { 1.0 } else { 0.0 };
+                let p = if self.is_key_down(positive) { 1.0 } else { 0.0 };
+                p - n
+            }
+            AxisSource::GamepadButtons { negative, positive } => largest(
+                &mut gamepads
+                    .iter()
+                    .map(|&id| button(id, positive) - button(id, negative)),
+            ),
+            AxisSource::Gamepad(axis) => {
+                largest(&mut gamepads.iter().map(|&id| self.gamepad_axis(id, axis)))
+            }
+            AxisSource::MouseMotionX => self.mouse_motion().x,
+            AxisSource::MouseMotionY => self.mouse_motion().y,
+            AxisSource::WheelX => self.mouse_scroll().x,
+            AxisSource::WheelY => self.mouse_scroll().y,
+            AxisSource::PanX | AxisSource::PanY => match self.finger_pan() {
+                GesturePan::Move { movement, .. } => {
+                    if v == AxisSource::PanX {
+                        movement.x
+                    } else {
+                        movement.y
+                    }
+                }
+                _ => 0.0,
+            },
+        }
+    }
+
+    /// Starts recording the input events. The playback in progress is stopped, and the
+    /// connected gamepads are recorded as connected in the first frame.
+    pub fn start_recording(&self) {
+        if self.state.recorder.lock().unwrap().stop_playback() {
+            self.state.restore_live_inputs();
+        }
+
+        let now = crate::application::real_time();
+        let mut recorder = self.state.recorder.lock().unwrap();
+        recorder.start_recording(now);
+
+        for id in self.gamepads() {
+            recorder.record(InputEvent::GamepadConnected { id }, now);
+        }
+    }
+
+    /// Stops recording, returns the recorded events.
+    #[inline]
+    pub fn stop_recording(&self) -> Option<InputRecording> {
+        self.state.recorder.lock().unwrap().stop_recording()
+    }
+
+    /// Checks if the recording is in progress.
+    #[inline]
+    pub fn is_recording(&self) -> bool {
+        self.state.recorder.lock().unwrap().is_recording()
+    }
+
+    /// Plays the recording back from the next frame. The live inputs are ignored until the
+    /// playback is finished or stopped.
+    pub fn play_recording(&self, recording: InputRecording) {
+        self.state.recorder.lock().unwrap().play(recording);
+        self.state.reset();
+        self.state.disconnect_gamepads();
+    }
+
+    /// Stops the playback, returns false if there is no playback in progress.
+    pub fn stop_playback(&self) -> bool {
+        if self.state.recorder.lock().unwrap().stop_playback() {
+            self.state.restore_live_inputs();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Checks if the playback is in progress.
+    #[inline]
+    pub fn is_playing(&self) -> bool {
+        self.state.recorder.lock().unwrap().is_playing()
+    }
 }
@@ -1,4 +1,5 @@
 use std::cmp::Ordering;
+use std::f32::consts::PI;
 use std::time::Duration;
 
 use crate::math::prelude::{MetricSpace, Vector2};
@@ -21,6 +22,21 @@ pub struct TouchPadParams {
     pub touch_timeout: Duration,
     /// The minimum distance before a touch the touch pressing and releasing.
     pub max_touch_distance: f32,
+    /// The minimum change of distance between two fingers before they are recognized as
+    /// pinching.
+    pub min_pinch_distance: f32,
+    /// The minimum angle in radians two fingers rotated before they are recognized as
+    /// rotating.
+    pub min_rotation_angle: f32,
+    /// The minimum time duration a touch should be held down before it is recognized as
+    /// long press.
+    pub long_press_timeout: Duration,
+    /// The maximum distance a touch could move during long press.
+    pub max_long_press_distance: f32,
+    /// The maximum time duration between the touch pressing and releasing of a swipe.
+    pub swipe_timeout: Duration,
+    /// The minimum distance between the touch pressing and releasing of a swipe.
+    pub min_swipe_distance: f32,
 }
 
 impl Default for TouchPadParams {
@@ -33,12 +49,21 @@ impl Default for TouchPadParams {
 
             touch_timeout: Duration::from_millis(250),
             max_touch_distance: 20.0,
+
+            min_pinch_distance: 10.0,
+            min_rotation_angle: 0.1,
+
+            long_press_timeout: Duration::from_millis(500),
+            max_long_press_distance: 10.0,
+
+            swipe_timeout: Duration::from_millis(500),
+            min_swipe_distance: 50.0,
         }
     }
 }
 
 /// Describes touch-screen input state.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum TouchState {
     Start,
     Move,
@@ -57,6 +82,18 @@ pub struct TouchPad {
 
     double_tap_detector: GestureTapDetector,
     double_tap: GestureTap,
+
+    long_press_detector: GestureLongPressDetector,
+    long_press: GestureTap,
+
+    swipe_detector: GestureSwipeDetector,
+    swipe: GestureSwipe,
+
+    pinch_detector: GesturePinchDetector,
+    pinch: GesturePinch,
+
+    rotate_detector: GestureRotateDetector,
+    rotate: GestureRotate,
 }
 
 impl TouchPad {
@@ -72,6 +109,25 @@ impl TouchPad {
 
             double_tap_detector: GestureTapDetector::new(2, params),
             double_tap: GestureTap::None,
+
+            long_press_detector: GestureLongPressDetector::new(params),
+            long_press: GestureTap::None,
+
+            swipe_detector: GestureSwipeDetector::new(params),
+            swipe: GestureSwipe::None,
+
+            pinch_detector: GesturePinchDetector::new(params),
+            pinch: GesturePinch::None,
+
+            rotate_detector: GestureRotateDetector::new(params),
+            rotate: GestureRotate::None,
+        }
+    }
+
+    /// Detects the gestures which are driven by time instead of touch events.
+    pub fn update(&mut self) {
+        if let GestureTap::Action { position } = self.long_press_detector.update() {
+            self.long_press = GestureTap::Action { position };
         }
     }
 
@@ -79,6 +135,15 @@ impl TouchPad {
         self.pan = GesturePan::None;
         self.tap = GestureTap::None;
         self.double_tap = GestureTap::None;
+        self.long_press = GestureTap::None;
+        self.swipe = GestureSwipe::None;
+        self.pinch = GesturePinch::None;
+        self.rotate = GestureRotate::None;
+
+        // Multiple touch events might be received in one frame, the deltas of gestures are
+        // measured against the snapshots at the start of frame.
+        self.pinch_detector.advance();
+        self.rotate_detector.advance();
     }
 
     pub fn reset(&mut self) {
@@ -89,6 +154,14 @@ impl TouchPad {
         self.tap = GestureTap::None;
         self.double_tap_detector.reset();
         self.double_tap = GestureTap::None;
+        self.long_press_detector.reset();
+        self.long_press = GestureTap::None;
+        self.swipe_detector.reset();
+        self.swipe = GestureSwipe::None;
+        self.pinch_detector.reset();
+        self.pinch = GesturePinch::None;
+        self.rotate_detector.reset();
+        self.rotate = GestureRotate::None;
     }
 
     pub fn on_touch(&mut self, id: u8, state: TouchState, position: Vector2<f32>) {
@@ -103,6 +176,24 @@ impl TouchPad {
         self.pan = self.pan_detector.detect(&self.record);
         self.tap = self.tap_detector.detect(&self.record);
         self.double_tap = self.double_tap_detector.detect(&self.record);
+        self.long_press_detector.detect(&self.record, &touch);
+
+        // Multiple touch events might be received in one frame, the detected gestures should
+        // not be overwritten by the following events.
+        match self.swipe_detector.detect(&self.record, &touch) {
+            GestureSwipe::None => {}
+            v => self.swipe = v,
+        }
+
+        match self.pinch_detector.detect(&self.record) {
+            GesturePinch::None => {}
+            v => self.pinch = v,
+        }
+
+        match self.rotate_detector.detect(&self.record) {
+            GestureRotate::None => {}
+            v => self.rotate = v,
+        }
     }
 
     #[inline]
@@ -129,6 +220,26 @@ impl TouchPad {
     pub fn double_tap(&self) -> GestureTap {
         self.double_tap
     }
+
+    #[inline]
+    pub fn long_press(&self) -> GestureTap {
+        self.long_press
+    }
+
+    #[inline]
+    pub fn swipe(&self) -> GestureSwipe {
+        self.swipe
+    }
+
+    #[inline]
+    pub fn pinch(&self) -> GesturePinch {
+        self.pinch
+    }
+
+    #[inline]
+    pub fn rotate(&self) -> GestureRotate {
+        self.rotate
+    }
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -396,6 +507,485 @@ impl GesturePanDetector {
     }
 }
 
+struct GestureLongPressDetector {
+    id: Option<u8>,
+    position: Vector2<f32>,
+    start_time: Timestamp,
+
+    params: TouchPadParams,
+}
+
+impl GestureLongPressDetector {
+    pub fn new(params: TouchPadParams) -> Self {
+        GestureLongPressDetector {
+            id: None,
+            position: Vector2::new(0.0, 0.0),
+            start_time: Timestamp::now(),
+            params,
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.id = None;
+    }
+
+    pub fn detect(&mut self, record: &TouchesRecord, touch: &TouchEvent) {
+        // Checks for required number of touches.
+        if record.len != 1 {
+            self.reset();
+            return;
+        }
+
+        match touch.state {
+            TouchState::Start => {
+                self.id = Some(touch.id);
+                self.position = touch.position;
+                self.start_time = Timestamp::now();
+            }
+
+            TouchState::Move => {
+                if self.id == Some(touch.id)
+                    && touch.position.distance(self.position) > self.params.max_long_press_distance
+                {
+                    self.reset();
+                }
+            }
+
+            TouchState::End | TouchState::Cancel => self.reset(),
+        }
+    }
+
+    pub fn update(&mut self) -> GestureTap {
+        if self.id.is_some() && self.start_time.elapsed() >= self.params.long_press_timeout {
+            self.reset();
+            GestureTap::Action {
+                position: self.position,
+            }
+        } else {
+            GestureTap::None
+        }
+    }
+}
+
+/// The direction of swipe gesture.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SwipeDirection {
+    Left,
+    Right,
+    Up,
+    Down,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum GestureSwipe {
+    Action {
+        /// The dominant direction of swipe.
+        direction: SwipeDirection,
+        /// The start touch position.
+        start_position: Vector2<f32>,
+        /// The end touch position.
+        position: Vector2<f32>,
+        /// The average velocity per second.
+        velocity: Vector2<f32>,
+    },
+    None,
+}
+
+impl GestureSwipe {
+    pub fn scale(&self, device_pixel_ratio: f32) -> GestureSwipe {
+        match *self {
+            GestureSwipe::Action {
+                direction,
+                start_position,
+                position,
+                velocity,
+            } => GestureSwipe::Action {
+                direction,
+                start_position: start_position * device_pixel_ratio,
+                position: position * device_pixel_ratio,
+                velocity: velocity * device_pixel_ratio,
+            },
+
+            GestureSwipe::None => GestureSwipe::None,
+        }
+    }
+}
+
+struct GestureSwipeDetector {
+    id: Option<u8>,
+    start_position: Vector2<f32>,
+    start_time: Timestamp,
+
+    params: TouchPadParams,
+}
+
+impl GestureSwipeDetector {
+    pub fn new(params: TouchPadParams) -> Self {
+        GestureSwipeDetector {
+            id: None,
+            start_position: Vector2::new(0.0, 0.0),
+            start_time: Timestamp::now(),
+            params,
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.id = None;
+    }
+
+    pub fn detect(&mut self, record: &TouchesRecord, touch: &TouchEvent) -> GestureSwipe {
+        match touch.state {
+            TouchState::Start => {
+                // Swipes are recognized with one finger only.
+                if record.len == 1 {
+                    self.id = Some(touch.id);
+                    self.start_position = touch.position;
+                    self.start_time = Timestamp::now();
+                } else {
+                    self.reset();
+                }
+
+                GestureSwipe::None
+            }
+
+            TouchState::Move => GestureSwipe::None,
+
+            TouchState::End => {
+                if self.id != Some(touch.id) {
+                    return GestureSwipe::None;
+                }
+
+                self.reset();
+
+                let duration = self.start_time.elapsed();
+                let movement = touch.position - self.start_position;
+                let distance = touch.position.distance(self.start_position);
+
+                if duration > self.params.swipe_timeout
+                    || distance < self.params.min_swipe_distance
+                {
+                    return GestureSwipe::None;
+                }
+
+                let direction = if movement.x.abs() >= movement.y.abs() {
+                    if movement.x > 0.0 {
+                        SwipeDirection::Right
+                    } else {
+                        SwipeDirection::Left
+                    }
+                } else if movement.y > 0.0 {
+                    SwipeDirection::Up
+                } else {
+                    SwipeDirection::Down
+                };
+
+                let secs = duration.as_secs() as f32 + duration.subsec_millis() as f32 / 1000.0;
+                let velocity = if secs > 0.0 {
+                    movement / secs
+                } else {
+                    movement
+                };
+
+                GestureSwipe::Action {
+                    direction,
+                    start_position: self.start_position,
+                    position: touch.position,
+                    velocity,
+                }
+            }
+
+            TouchState::Cancel => {
+                self.reset();
+                GestureSwipe::None
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum GesturePinch {
+    Start {
+        /// The center of two fingers.
+        center: Vector2<f32>,
+    },
+    Move {
+        /// The center of two fingers.
+        center: Vector2<f32>,
+        /// The ratio of current distance between two fingers to the start distance.
+        scale: f32,
+        /// The ratio of distance changes during last frame.
+        delta: f32,
+    },
+    End {
+        /// The center of two fingers.
+        center: Vector2<f32>,
+        /// The ratio of final distance between two fingers to the start distance.
+        scale: f32,
+    },
+    None,
+}
+
+impl GesturePinch {
+    pub fn scale(&self, device_pixel_ratio: f32) -> GesturePinch {
+        match *self {
+            GesturePinch::Start { center } => GesturePinch::Start {
+                center: center * device_pixel_ratio,
+            },
+
+            GesturePinch::Move {
+                center,
+                scale,
+                delta,
+            } => GesturePinch::Move {
+                center: center * device_pixel_ratio,
+                scale,
+                delta,
+            },
+
+            GesturePinch::End { center, scale } => GesturePinch::End {
+                center: center * device_pixel_ratio,
+                scale,
+            },
+
+            GesturePinch::None => GesturePinch::None,
+        }
+    }
+}
+
+struct GesturePinchDetector {
+    ids: Option<(u8, u8)>,
+    center: Vector2<f32>,
+    start_distance: f32,
+    frame_distance: f32,
+    distance: f32,
+    pinch: bool,
+
+    params: TouchPadParams,
+}
+
+impl GesturePinchDetector {
+    pub fn new(params: TouchPadParams) -> Self {
+        GesturePinchDetector {
+            ids: None,
+            center: Vector2::new(0.0, 0.0),
+            start_distance: 0.0,
+            frame_distance: 0.0,
+            distance: 0.0,
+            pinch: false,
+            params,
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.ids = None;
+        self.pinch = false;
+    }
+
+    pub fn advance(&mut self) {
+        self.frame_distance = self.distance;
+    }
+
+    pub fn detect(&mut self, record: &TouchesRecord) -> GesturePinch {
+        // Checks for required number of touches.
+        if record.len != 2 {
+            return self.end();
+        }
+
+        let (ids, p1, p2) = record.pair();
+        // The distance is clamped to avoid dividing by zero.
+        let distance = p1.distance(p2).max(1.0);
+        let center = (p1 + p2) * 0.5;
+
+        // Checks if touch identifiers are unchanged.
+        if self.ids != Some(ids) {
+            let v = self.end();
+            self.ids = Some(ids);
+            self.center = center;
+            self.start_distance = distance;
+            self.frame_distance = distance;
+            self.distance = distance;
+            return v;
+        }
+
+        self.center = center;
+        self.distance = distance;
+
+        if self.pinch {
+            GesturePinch::Move {
+                center,
+                scale: distance / self.start_distance,
+                delta: distance / self.frame_distance,
+            }
+        } else if (distance - self.start_distance).abs() >= self.params.min_pinch_distance {
+            // Checks if min-distance is reached before starting pinching.
+            self.pinch = true;
+            GesturePinch::Start { center }
+        } else {
+            GesturePinch::None
+        }
+    }
+
+    fn end(&mut self) -> GesturePinch {
+        let v = if self.pinch {
+            GesturePinch::End {
+                center: self.center,
+                scale: self.distance / self.start_distance,
+            }
+        } else {
+            GesturePinch::None
+        };
+
+        self.reset();
+        v
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum GestureRotate {
+    Start {
+        /// The center of two fingers.
+        center: Vector2<f32>,
+    },
+    Move {
+        /// The center of two fingers.
+        center: Vector2<f32>,
+        /// The counter-clockwise angle in radians since rotation started.
+        angle: f32,
+        /// The counter-clockwise angle in radians during last frame.
+        delta: f32,
+    },
+    End {
+        /// The center of two fingers.
+        center: Vector2<f32>,
+        /// The counter-clockwise angle in radians since rotation started.
+        angle: f32,
+    },
+    None,
+}
+
+impl GestureRotate {
+    pub fn scale(&self, device_pixel_ratio: f32) -> GestureRotate {
+        match *self {
+            GestureRotate::Start { center } => GestureRotate::Start {
+                center: center * device_pixel_ratio,
+            },
+
+            GestureRotate::Move {
+                center,
+                angle,
+                delta,
+            } => GestureRotate::Move {
+                center: center * device_pixel_ratio,
+                angle,
+                delta,
+            },
+
+            GestureRotate::End { center, angle } => GestureRotate::End {
+                center: center * device_pixel_ratio,
+                angle,
+            },
+
+            GestureRotate::None => GestureRotate::None,
+        }
+    }
+}
+
+struct GestureRotateDetector {
+    ids: Option<(u8, u8)>,
+    center: Vector2<f32>,
+    orientation: f32,
+    frame_angle: f32,
+    angle: f32,
+    rotate: bool,
+
+    params: TouchPadParams,
+}
+
+impl GestureRotateDetector {
+    pub fn new(params: TouchPadParams) -> Self {
+        GestureRotateDetector {
+            ids: None,
+            center: Vector2::new(0.0, 0.0),
+            orientation: 0.0,
+            frame_angle: 0.0,
+            angle: 0.0,
+            rotate: false,
+            params,
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.ids = None;
+        self.rotate = false;
+    }
+
+    pub fn advance(&mut self) {
+        self.frame_angle = self.angle;
+    }
+
+    pub fn detect(&mut self, record: &TouchesRecord) -> GestureRotate {
+        // Checks for required number of touches.
+        if record.len != 2 {
+            return self.end();
+        }
+
+        let (ids, p1, p2) = record.pair();
+        let orientation = (p2.y - p1.y).atan2(p2.x - p1.x);
+        let center = (p1 + p2) * 0.5;
+
+        // Checks if touch identifiers are unchanged.
+        if self.ids != Some(ids) {
+            let v = self.end();
+            self.ids = Some(ids);
+            self.center = center;
+            self.orientation = orientation;
+            self.frame_angle = 0.0;
+            self.angle = 0.0;
+            return v;
+        }
+
+        // Wraps the delta into [-PI, PI].
+        let mut delta = orientation - self.orientation;
+        if delta > PI {
+            delta -= 2.0 * PI;
+        } else if delta < -PI {
+            delta += 2.0 * PI;
+        }
+
+        self.center = center;
+        self.orientation = orientation;
+        self.angle += delta;
+
+        if self.rotate {
+            GestureRotate::Move {
+                center,
+                angle: self.angle,
+                delta: self.angle - self.frame_angle,
+            }
+        } else if self.angle.abs() >= self.params.min_rotation_angle {
+            // Checks if min-angle is reached before starting rotating.
+            self.rotate = true;
+            GestureRotate::Start { center }
+        } else {
+            GestureRotate::None
+        }
+    }
+
+    fn end(&mut self) -> GestureRotate {
+        let v = if self.rotate {
+            GestureRotate::End {
+                center: self.center,
+                angle: self.angle,
+            }
+        } else {
+            GestureRotate::None
+        };
+
+        self.reset();
+        v
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 struct TouchEvent {
     pub id: u8,
@@ -456,6 +1046,16 @@ impl TouchesRecord {
         }
     }
 
+    // Gets the first two touches, which are ordered by identifiers.
+    fn pair(&self) -> ((u8, u8), Vector2<f32>, Vector2<f32>) {
+        let (t1, t2) = (self.touches[0].1, self.touches[1].1);
+        if t1.id < t2.id {
+            ((t1.id, t2.id), t1.position, t2.position)
+        } else {
+            ((t2.id, t1.id), t2.position, t1.position)
+        }
+    }
+
     fn update_touch(&mut self, touch: TouchEvent) {
         let mut found = false;
         for i in 0..self.len {
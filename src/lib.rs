@@ -29,6 +29,8 @@
 extern crate gl;
 #[cfg(not(target_arch = "wasm32"))]
 extern crate glutin;
+#[cfg(not(target_arch = "wasm32"))]
+extern crate clipboard;
 
 #[cfg(target_arch = "wasm32")]
 extern crate console_error_panic_hook;
@@ -48,14 +50,16 @@ extern crate log;
 extern crate cgmath;
 #[macro_use]
 extern crate serde;
+extern crate backtrace;
 extern crate byteorder;
-extern crate serde_json;
 
 extern crate crossbeam_deque;
 extern crate inlinable_string;
 extern crate smallvec;
 
 pub extern crate bincode;
+#[macro_use]
+pub extern crate serde_json;
 pub extern crate uuid;
 
 pub use cgmath::{assert_relative_eq, assert_relative_ne, assert_ulps_eq, assert_ulps_ne};
@@ -66,11 +70,15 @@ pub mod errors;
 #[macro_use]
 pub mod utils;
 pub mod application;
+pub mod console;
+pub mod crash;
 #[macro_use]
 pub mod video;
 pub mod input;
 pub mod math;
 pub mod prelude;
+#[macro_use]
+pub mod prof;
 pub mod res;
 pub mod sched;
 pub mod window;
@@ -5,6 +5,6 @@ pub use crate::res::prelude::*;
 pub use crate::sched::prelude::*;
 pub use crate::video::prelude::*;
 pub use crate::window::prelude::*;
-pub use crate::{application, input, main, math, res, sched, video, window};
+pub use crate::{application, console, input, main, math, res, sched, video, window};
 
 pub use crate::errors::{Error as CrError, Result as CrResult};
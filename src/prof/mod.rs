@@ -0,0 +1,173 @@
+//! Built-in instrumentation profiler, which exports the Chrome trace-event format.
+//!
+//! Named timing markers could be placed on any thread with `prof::scope` or the
+//! `profile_scope!` macro. The phases of `LifecycleListener`s and the jobs of scheduler
+//! are instrumented automatically. Nothing is recorded until the profiler is enabled.
+//!
+//! ```rust,ignore
+//! crayon::prof::set_enabled(true);
+//!
+//! {
+//!     profile_scope!("physics");
+//!     ...
+//! }
+//!
+//! crayon::prof::save_chrome_trace("trace.json").unwrap();
+//! ```
+//!
+//! The exported file could be opened with `chrome://tracing` or other compatible viewers.
+
+mod system;
+
+pub use self::system::MAX_EVENTS;
+
+use std::borrow::Cow;
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+
+use crate::errors::Result;
+
+use self::inside::{ctx, CTX};
+
+/// Checks if the profiler system is enabled.
+#[inline]
+pub fn valid() -> bool {
+    unsafe { !CTX.is_null() }
+}
+
+/// Enables or disables the recording of events.
+#[inline]
+pub fn set_enabled(enabled: bool) {
+    if valid() {
+        ctx().set_enabled(enabled);
+    }
+}
+
+/// Checks if the events are recorded.
+#[inline]
+pub fn enabled() -> bool {
+    valid() && ctx().enabled()
+}
+
+/// Creates a named timing marker, which records the span from now till it is dropped.
+#[inline]
+pub fn scope<T: Into<Cow<'static, str>>>(name: T) -> ProfileScope {
+    ProfileScope::new("user", name)
+}
+
+/// Records an instant event.
+pub fn instant<T: Into<Cow<'static, str>>>(name: T) {
+    if enabled() {
+        ctx().instant("user", name.into());
+    }
+}
+
+/// Returns the number of recorded events.
+#[inline]
+pub fn len() -> usize {
+    if valid() {
+        ctx().len()
+    } else {
+        0
+    }
+}
+
+/// Removes all the recorded events.
+#[inline]
+pub fn clear() {
+    if valid() {
+        ctx().clear();
+    }
+}
+
+/// Writes the recorded events in Chrome trace-event JSON format.
+pub fn write_chrome_trace<W: Write>(w: &mut W) -> Result<()> {
+    if !valid() {
+        bail!("The profiler system has not been initialized.");
+    }
+
+    ctx().write_chrome_trace(w)
+}
+
+/// Saves the recorded events into file in Chrome trace-event JSON format.
+pub fn save_chrome_trace<P: AsRef<Path>>(path: P) -> Result<()> {
+    let mut bytes = Vec::new();
+    write_chrome_trace(&mut bytes)?;
+    fs::write(path, &bytes)?;
+    Ok(())
+}
+
+/// A timing marker which records a span when it's dropped.
+pub struct ProfileScope {
+    name: Option<(&'static str, Cow<'static, str>)>,
+    start: u64,
+}
+
+impl ProfileScope {
+    /// Creates a timing marker with category. Does nothing if profiler is not enabled.
+    pub fn new<T: Into<Cow<'static, str>>>(category: &'static str, name: T) -> Self {
+        if enabled() {
+            ProfileScope {
+                name: Some((category, name.into())),
+                start: ctx().now(),
+            }
+        } else {
+            ProfileScope {
+                name: None,
+                start: 0,
+            }
+        }
+    }
+}
+
+impl Drop for ProfileScope {
+    fn drop(&mut self) {
+        if let Some((category, name)) = self.name.take() {
+            if valid() {
+                ctx().complete(category, name, self.start);
+            }
+        }
+    }
+}
+
+/// Creates a named timing marker which lasts till the end of current scope.
+#[macro_export]
+macro_rules! profile_scope {
+    ($name:expr) => {
+        let _profile_scope = $crate::prof::scope($name);
+    };
+}
+
+pub(crate) mod inside {
+    use super::system::ProfilerSystem;
+
+    pub static mut CTX: *const ProfilerSystem = std::ptr::null();
+
+    pub fn ctx() -> &'static ProfilerSystem {
+        unsafe {
+            debug_assert!(
+                !CTX.is_null(),
+                "profiler system has not been initialized properly."
+            );
+
+            &*CTX
+        }
+    }
+
+    /// Setup the profiler system.
+    pub unsafe fn setup() {
+        debug_assert!(CTX.is_null(), "duplicated setup of profiler system.");
+        CTX = Box::into_raw(Box::new(ProfilerSystem::new()));
+    }
+
+    /// Discard the profiler system.
+    pub unsafe fn discard() {
+        if CTX.is_null() {
+            return;
+        }
+
+        drop(Box::from_raw(CTX as *mut ProfilerSystem));
+        CTX = std::ptr::null();
+    }
+}
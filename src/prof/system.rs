@@ -0,0 +1,213 @@
+use std::borrow::Cow;
+use std::cell::Cell;
+use std::io::Write;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+use crate::errors::Result;
+use crate::utils::hash::FastHashMap;
+
+/// The maximum number of events recorded before exporting. Events beyond this are dropped.
+pub const MAX_EVENTS: usize = 1 << 20;
+
+#[derive(Debug, Clone)]
+enum EventKind {
+    Complete { dur: u64 },
+    Instant,
+}
+
+#[derive(Debug, Clone)]
+struct Event {
+    name: Cow<'static, str>,
+    category: &'static str,
+    kind: EventKind,
+    ts: u64,
+    tid: usize,
+}
+
+#[derive(Default)]
+struct Events {
+    events: Vec<Event>,
+    threads: FastHashMap<usize, String>,
+    dropped: usize,
+}
+
+pub struct ProfilerSystem {
+    enabled: AtomicBool,
+    epoch: u64,
+    events: Mutex<Events>,
+}
+
+#[derive(Serialize)]
+struct ChromeEvent<'a> {
+    name: &'a str,
+    cat: &'a str,
+    ph: &'static str,
+    ts: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    dur: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    s: Option<&'static str>,
+    pid: u32,
+    tid: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    args: Option<ChromeArgs<'a>>,
+}
+
+#[derive(Serialize)]
+struct ChromeArgs<'a> {
+    name: &'a str,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ChromeTrace<'a> {
+    trace_events: Vec<ChromeEvent<'a>>,
+    display_time_unit: &'static str,
+}
+
+thread_local! {
+    static THREAD_ID: Cell<usize> = Cell::new(0);
+}
+
+// Returns the id of current thread, which is allocated when the thread records its first event.
+fn thread_id() -> usize {
+    static COUNTER: AtomicUsize = AtomicUsize::new(1);
+
+    THREAD_ID.with(|v| {
+        if v.get() == 0 {
+            v.set(COUNTER.fetch_add(1, Ordering::Relaxed));
+        }
+
+        v.get()
+    })
+}
+
+impl ProfilerSystem {
+    pub fn new() -> Self {
+        ProfilerSystem {
+            enabled: AtomicBool::new(false),
+            epoch: crate::application::sys::micros(),
+            events: Mutex::new(Events::default()),
+        }
+    }
+
+    #[inline]
+    pub fn set_enabled(&self, enabled: bool) {
+        self.enabled.store(enabled, Ordering::Relaxed);
+    }
+
+    #[inline]
+    pub fn enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
+    /// Returns the microseconds since the profiler has been created.
+    #[inline]
+    pub fn now(&self) -> u64 {
+        crate::application::sys::micros().saturating_sub(self.epoch)
+    }
+
+    pub fn complete(&self, category: &'static str, name: Cow<'static, str>, start: u64) {
+        let dur = self.now().saturating_sub(start);
+        self.record(category, name, EventKind::Complete { dur }, start);
+    }
+
+    pub fn instant(&self, category: &'static str, name: Cow<'static, str>) {
+        let ts = self.now();
+        self.record(category, name, EventKind::Instant, ts);
+    }
+
+    fn record(
+        &self,
+        category: &'static str,
+        name: Cow<'static, str>,
+        kind: EventKind,
+        ts: u64,
+    ) {
+        let tid = thread_id();
+        let mut events = self.events.lock().unwrap();
+
+        if !events.threads.contains_key(&tid) {
+            let thread = ::std::thread::current();
+            let name = thread
+                .name()
+                .map(|v| v.to_owned())
+                .unwrap_or_else(|| format!("thread-{}", tid));
+
+            events.threads.insert(tid, name);
+        }
+
+        if events.events.len() >= MAX_EVENTS {
+            if events.dropped == 0 {
+                warn!("[Profiler] Too many events, the later ones are dropped.");
+            }
+
+            events.dropped += 1;
+            return;
+        }
+
+        events.events.push(Event {
+            name,
+            category,
+            kind,
+            ts,
+            tid,
+        });
+    }
+
+    pub fn len(&self) -> usize {
+        self.events.lock().unwrap().events.len()
+    }
+
+    pub fn clear(&self) {
+        let mut events = self.events.lock().unwrap();
+        events.events.clear();
+        events.dropped = 0;
+    }
+
+    pub fn write_chrome_trace(&self, w: &mut dyn Write) -> Result<()> {
+        let events = self.events.lock().unwrap();
+
+        let mut trace = ChromeTrace {
+            trace_events: Vec::with_capacity(events.events.len() + events.threads.len()),
+            display_time_unit: "ms",
+        };
+
+        for (&tid, name) in &events.threads {
+            trace.trace_events.push(ChromeEvent {
+                name: "thread_name",
+                cat: "__metadata",
+                ph: "M",
+                ts: 0,
+                dur: None,
+                s: None,
+                pid: 1,
+                tid,
+                args: Some(ChromeArgs { name }),
+            });
+        }
+
+        for v in &events.events {
+            let (ph, dur, s) = match v.kind {
+                EventKind::Complete { dur } => ("X", Some(dur), None),
+                EventKind::Instant => ("i", None, Some("t")),
+            };
+
+            trace.trace_events.push(ChromeEvent {
+                name: &v.name,
+                cat: v.category,
+                ph,
+                ts: v.ts,
+                dur,
+                s,
+                pid: 1,
+                tid: v.tid,
+                args: None,
+            });
+        }
+
+        serde_json::to_writer(w, &trace)?;
+        Ok(())
+    }
+}
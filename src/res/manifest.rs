@@ -1,13 +1,15 @@
 //! Manifest for all the AssetBundles in the build.
 
+use std::collections::BTreeMap;
 use std::io::Read;
+use std::ops::Bound;
 
 use bincode;
 use inlinable_string::{InlinableString, StringExt};
 use uuid::Uuid;
 
 use crate::errors::*;
-use crate::utils::prelude::{DataBuffer, DataBufferPtr, FastHashMap, HashValue};
+use crate::utils::prelude::{DataBuffer, DataBufferPtr, FastHashMap};
 
 pub const NAME: &str = "MANIFEST";
 pub const MAGIC: [u8; 8] = [b'M', b'N', b'F', b'T', b' ', 0, 0, 1];
@@ -50,7 +52,8 @@ pub struct ManfiestResolver {
     manifests: Vec<Manifest>,
     manifest_prefixs: Vec<InlinableString>,
     uuids: FastHashMap<Uuid, (usize, usize)>,
-    filenames: FastHashMap<HashValue<str>, Uuid>,
+    // The full names are sorted, so the ones with the same prefix could be ranged.
+    filenames: BTreeMap<String, Uuid>,
 }
 
 impl ManfiestResolver {
@@ -59,10 +62,21 @@ impl ManfiestResolver {
             manifests: Vec::new(),
             manifest_prefixs: Vec::new(),
             uuids: FastHashMap::default(),
-            filenames: FastHashMap::default(),
+            filenames: BTreeMap::new(),
         }
     }
 
+    /// Gets the prefixes and the number of items of attached manifests.
+    pub fn manifests(&self) -> impl Iterator<Item = (&str, usize)> + '_ {
+        self.manifest_prefixs
+            .iter()
+            .zip(self.manifests.iter())
+            .map(|(prefix, v)| {
+                let prefix: &str = prefix.as_ref();
+                (prefix, v.items.len())
+            })
+    }
+
     pub fn add<T: Into<InlinableString>>(&mut self, prefix: T, file: &mut dyn Read) -> Result<()> {
         let manifest = Manifest::load_from(file)?;
 
@@ -77,7 +91,7 @@ impl ManfiestResolver {
             let fullname = format!("{}{}", prefix, filename);
 
             self.uuids.insert(v.uuid, (index, sub_index));
-            self.filenames.insert(fullname.into(), v.uuid);
+            self.filenames.insert(fullname, v.uuid);
         }
 
         self.manifests.push(manifest);
@@ -94,8 +108,18 @@ impl ManfiestResolver {
     /// Return the UUID if the fullname exists in this registry.
     #[inline]
     pub fn find<T: AsRef<str>>(&self, fullname: T) -> Option<Uuid> {
-        let fullname = fullname.as_ref().into();
-        self.filenames.get(&fullname).cloned()
+        self.filenames.get(fullname.as_ref()).cloned()
+    }
+
+    /// Return the fullnames and UUIDs of all the resources whose fullname starts with
+    /// `prefix`, in the order of fullnames.
+    pub fn find_with_prefix<T: AsRef<str>>(&self, prefix: T) -> Vec<(String, Uuid)> {
+        let prefix = prefix.as_ref();
+        self.filenames
+            .range::<str, _>((Bound::Included(prefix), Bound::Unbounded))
+            .take_while(|(k, _)| k.starts_with(prefix))
+            .map(|(k, &v)| (k.clone(), v))
+            .collect()
     }
 
     /// Resolve the UUID to full path of corresponding resource.
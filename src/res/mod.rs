@@ -15,7 +15,7 @@
 //! # Resource
 //!
 //! A _resource_ is a abstraction of some `piece of data` that are fully prepared for using at runtime.
-//! We are providing a command line tool [crayon-cli](https://github.com/shawnscode/crayon-tools) that
+//! We are providing a command line tool `crayon-tools` (placed under `tools/crayon-tools`) that
 //! automatically compiles assets into resources for runtime.
 //!
 //! ## UUID
@@ -23,7 +23,7 @@
 //! An asset can produces multiple resources eventually. For example, `FBX` file can have multiple
 //! models, and it can also contains a spatial description of objects. For every resource that an
 //! asset might produces, a universal-uniqued id (UUID) is assigned to it. UUIDs are stored in .meta
-//! files. These .meta files are generated when _crayon-cli_ first imports an asset, and are stored
+//! files. These .meta files are generated when _crayon-tools_ first imports an asset, and are stored
 //! in the same directory as the asset.
 //!
 //! # Virtual Filesystem (VFS)
@@ -35,11 +35,21 @@
 //! data asynchronously. A trival `Directory` is provided to supports local host filesystem.
 //! And it should be easy to add features like compression and encrpytion.
 //!
+//! The `Http` VFS is registered with `http://` schema by default. On native platforms, it
+//! could be configured with `HttpParams` to cache downloaded files on disk, which makes it
+//! convenient to stream assets from a shared asset server during development.
+//!
 //! ## Manifest
 //!
 //! Every VFS should have a `Manifest` file which could be used to locate resources in actual path
 //! from general UUID or readable identifier. The `Manifest` file is generated after the build
-//! process of `crayon-cli`.
+//! process of `crayon-tools`.
+//!
+//! # Resource Group
+//!
+//! Resources are usually loaded and released level by level. The `ResourceGroup` bundles a set
+//! of resources from various `ResourcePool`s, tracks their loading states as a whole and drops
+//! all of them in one call.
 //!
 
 pub mod manifest;
@@ -50,7 +60,7 @@ pub mod utils;
 pub mod vfs;
 
 pub mod prelude {
-    pub use super::utils::prelude::ResourceState;
+    pub use super::utils::prelude::{ResourceGroup, ResourceState};
     pub use super::ResourceParams;
 }
 
@@ -80,7 +90,6 @@ impl Default for ResourceParams {
 
         #[cfg(not(target_arch = "wasm32"))]
         params.schemas.add("file", self::vfs::dir::Dir::new());
-        #[cfg(target_arch = "wasm32")]
         params.schemas.add("http", self::vfs::http::Http::new());
 
         params
@@ -105,6 +114,12 @@ pub fn find<T: AsRef<str>>(filename: T) -> Option<Uuid> {
     ctx().find(filename)
 }
 
+/// Return the full names and UUIDs of all the resources located under provided prefix.
+#[inline]
+pub fn find_with_prefix<T: AsRef<str>>(prefix: T) -> Vec<(String, Uuid)> {
+    ctx().find_with_prefix(prefix)
+}
+
 /// Checks if the resource exists in this registry.
 #[inline]
 pub fn exists(uuid: Uuid) -> bool {
@@ -1,7 +1,11 @@
 //! A asynchronous loading request.
 
+use std::future::Future;
+use std::pin::Pin;
 use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
 
+use crate::sched::future::pending;
 use crate::sched::prelude::{LatchProbe, LockLatch};
 
 pub type Response = Result<Box<[u8]>, failure::Error>;
@@ -9,6 +13,8 @@ pub type Response = Result<Box<[u8]>, failure::Error>;
 /// A asynchronous loading request. You sould checks the completion status with
 /// `poll` method manually. Once the polling returns true, you could fetch the
 /// result by `response`.
+///
+/// `Request` is also a `Future` which resolves into the `Response`.
 pub enum Request {
     NotReady(Arc<LockLatch<Response>>),
     Ok(Response),
@@ -74,6 +80,23 @@ impl Into<Option<Response>> for Request {
     }
 }
 
+impl Future for Request {
+    type Output = Response;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        if !this.poll() {
+            return pending(cx);
+        }
+
+        let consumed = Request::err(format_err!("Request has been consumed."));
+        match std::mem::replace(this, consumed) {
+            Request::Ok(rsp) => Poll::Ready(rsp),
+            Request::NotReady(_) => unreachable!(),
+        }
+    }
+}
+
 type FrameTasks = Mutex<Vec<(Request, Box<dyn FnMut(Response) + Send>)>>;
 
 #[derive(Default)]
@@ -93,6 +116,12 @@ impl RequestQueue {
         }
     }
 
+    /// Gets the number of requests that are still pending.
+    pub fn pending(&self) -> usize {
+        let num = self.tasks.lock().unwrap().len();
+        num + self.last_frame_tasks.lock().unwrap().len()
+    }
+
     pub fn add<T: FnOnce(Response) + Send + 'static>(&self, request: Request, func: T) {
         let mut v = Some(func);
         let wrapper = move |rsp| {
@@ -131,7 +160,8 @@ impl RequestQueue {
         for i in idxes.drain(..) {
             let (request, mut func) = tasks.remove(i);
             let v: Option<Response> = request.into();
-            crate::sched::spawn(move || func(v.unwrap()));
+            // Callbacks usually decode the bytes into resources, which might take a while.
+            crate::sched::spawn_on("io", move || func(v.unwrap()));
         }
     }
 }
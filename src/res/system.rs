@@ -3,6 +3,7 @@ use std::sync::{Arc, RwLock};
 
 use uuid::Uuid;
 
+use crate::application::ins::Inspectable;
 use crate::application::prelude::{LifecycleListener, LifecycleListenerHandle};
 
 use super::manifest::ManfiestResolver;
@@ -15,7 +16,7 @@ use super::ResourceParams;
 pub struct ResourceSystem {
     shortcut: ShortcutResolver,
     schemas: SchemaResolver,
-    manifest: RwLock<ManfiestResolver>,
+    manifest: Arc<RwLock<ManfiestResolver>>,
     requests: Arc<RequestQueue>,
     lifecycle: LifecycleListenerHandle,
 }
@@ -31,6 +32,28 @@ impl LifecycleListener for Lifecycle {
     }
 }
 
+struct Inspector {
+    schemas: Vec<String>,
+    manifest: Arc<RwLock<ManfiestResolver>>,
+    requests: Arc<RequestQueue>,
+}
+
+impl Inspectable for Inspector {
+    fn inspect(&self) -> serde_json::Value {
+        let manifest = self.manifest.read().unwrap();
+        let manifests: Vec<_> = manifest
+            .manifests()
+            .map(|(prefix, items)| json!({ "prefix": prefix, "items": items }))
+            .collect();
+
+        json!({
+            "schemas": self.schemas,
+            "manifests": manifests,
+            "pending_requests": self.requests.pending(),
+        })
+    }
+}
+
 impl Drop for ResourceSystem {
     fn drop(&mut self) {
         crate::application::detach(self.lifecycle);
@@ -45,7 +68,7 @@ impl ResourceSystem {
         let sys = ResourceSystem {
             shortcut: params.shortcuts,
             schemas: params.schemas,
-            manifest: RwLock::new(ManfiestResolver::new()),
+            manifest: Arc::new(RwLock::new(ManfiestResolver::new())),
             requests: requests.clone(),
             lifecycle: crate::application::attach(Lifecycle { requests }),
         };
@@ -53,6 +76,14 @@ impl ResourceSystem {
         Ok(sys)
     }
 
+    pub(crate) fn inspectable(&self) -> Arc<impl Inspectable> {
+        Arc::new(Inspector {
+            schemas: self.schemas.schemas(),
+            manifest: self.manifest.clone(),
+            requests: self.requests.clone(),
+        })
+    }
+
     /// Attach a manifest to this registry.
     #[inline]
     pub fn attach<T>(&self, prefix: T, file: &mut dyn Read) -> Result<(), failure::Error>
@@ -82,6 +113,16 @@ impl ResourceSystem {
             .and_then(|url| self.manifest.read().unwrap().find(&url))
     }
 
+    /// Return the full names and UUIDs of all the resources located under provided prefix.
+    #[inline]
+    pub fn find_with_prefix<T: AsRef<str>>(&self, prefix: T) -> Vec<(String, Uuid)> {
+        let prefix = prefix.as_ref();
+        self.shortcut
+            .resolve(prefix)
+            .map(|url| self.manifest.read().unwrap().find_with_prefix(&url))
+            .unwrap_or_else(Vec::new)
+    }
+
     /// Checks if the resource exists in this registry.
     #[inline]
     pub fn exists(&self, uuid: Uuid) -> bool {
@@ -121,7 +162,7 @@ impl ResourceSystem {
         self.requests.add(req, func);
 
         let vfs = self.schemas.locate(url.schema())?;
-        crate::sched::spawn(move || vfs.request(&url, state));
+        crate::sched::spawn_on("io", move || vfs.request(&url, state));
 
         Ok(())
     }
@@ -157,7 +198,7 @@ impl ResourceSystem {
         let state = Request::latch();
         let req = Request::new(state.clone());
 
-        crate::sched::spawn(move || vfs.request(&url, state));
+        crate::sched::spawn_on("io", move || vfs.request(&url, state));
         Ok(req)
     }
 
@@ -0,0 +1,35 @@
+//! Futures of resources.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use failure::Error;
+
+use crate::sched::future::pending;
+use crate::sched::prelude::LatchProbe;
+
+use super::group::{ResourceGroup, ResourceHandle};
+use super::state::ResourceState;
+
+/// Polls the resource state of `handle`. This is used to implement `Future` for resource
+/// handles, which resolves into the handle itself once the resource is ready.
+pub fn poll_resource<T: ResourceHandle>(handle: T, cx: &mut Context) -> Poll<Result<T, Error>> {
+    match handle.state() {
+        ResourceState::Ok => Poll::Ready(Ok(handle)),
+        ResourceState::Err => Poll::Ready(Err(format_err!("{:?} failed to load.", handle))),
+        ResourceState::NotReady => pending(cx),
+    }
+}
+
+impl<'a> Future for &'a ResourceGroup {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        if self.is_set() {
+            Poll::Ready(())
+        } else {
+            pending(cx)
+        }
+    }
+}
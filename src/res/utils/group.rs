@@ -0,0 +1,178 @@
+//! # ResourceGroup
+//!
+//! Games usually load levels instead of single files. The `ResourceGroup` bundles a set of
+//! resources, which might live in different `ResourcePool`s, and manages them as a whole.
+//!
+//! All the resources in a group are loaded asynchronously, and the group itself could be used
+//! as a latch (`LatchProbe`) which will be set once every resource has finished loading. When
+//! you are done with these resources, a single call to `release` (or dropping the group) drops
+//! the ownerships of all of them, so level transitions don't leak handles.
+
+use std::sync::Mutex;
+
+use failure::Error;
+use uuid::Uuid;
+
+use crate::sched::prelude::LatchProbe;
+use crate::utils::prelude::HandleLike;
+
+use super::state::ResourceState;
+
+/// A handle of resource which could be created from file asynchronously. This is implemented
+/// by all the built-in resource handles (e.g. `TextureHandle` and `MeshHandle`).
+pub trait ResourceHandle: HandleLike + 'static {
+    /// Creates the resource from file asynchronously.
+    fn create_from_uuid(uuid: Uuid) -> Result<Self, Error>;
+    /// Gets the resource state.
+    fn state(self) -> ResourceState;
+    /// Drops the ownership of this resource.
+    fn delete(self);
+}
+
+trait GroupEntry: Send + Sync {
+    fn state(&self) -> ResourceState;
+    fn delete(&self);
+}
+
+impl<T: ResourceHandle> GroupEntry for T {
+    #[inline]
+    fn state(&self) -> ResourceState {
+        ResourceHandle::state(*self)
+    }
+
+    #[inline]
+    fn delete(&self) {
+        ResourceHandle::delete(*self)
+    }
+}
+
+/// A set of resources that are loaded and released together.
+#[derive(Default)]
+pub struct ResourceGroup {
+    entries: Mutex<Vec<(Uuid, Box<dyn GroupEntry>)>>,
+}
+
+impl ResourceGroup {
+    /// Creates a new and empty `ResourceGroup`.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Creates a resource from file asynchronously, and adds it into this group.
+    pub fn add<T, U>(&self, url: U) -> Result<T, Error>
+    where
+        T: ResourceHandle,
+        U: AsRef<str>,
+    {
+        let url = url.as_ref();
+        let uuid = crate::res::find(url)
+            .ok_or_else(|| format_err!("Could not found resource '{}'.", url))?;
+        self.add_uuid(uuid)
+    }
+
+    /// Creates a resource with `uuid` asynchronously, and adds it into this group.
+    pub fn add_uuid<T: ResourceHandle>(&self, uuid: Uuid) -> Result<T, Error> {
+        let handle = T::create_from_uuid(uuid)?;
+        self.entries.lock().unwrap().push((uuid, Box::new(handle)));
+        Ok(handle)
+    }
+
+    /// Creates the resources located under `prefix` (e.g. "res:levels/1/") in the attached
+    /// manifests asynchronously, and adds them into this group. Since a prefix usually
+    /// holds resources of different types, only the ones whose full names are accepted by
+    /// `filter` are created as `T`.
+    ///
+    /// ```rust,ignore
+    /// let prefix = "res:levels/1/";
+    /// let textures: Vec<TextureHandle> = group.add_prefix(prefix, |v| v.ends_with(".png"))?;
+    /// let meshes: Vec<MeshHandle> = group.add_prefix(prefix, |v| v.ends_with(".obj"))?;
+    /// ```
+    pub fn add_prefix<T, U, F>(&self, prefix: U, filter: F) -> Result<Vec<T>, Error>
+    where
+        T: ResourceHandle,
+        U: AsRef<str>,
+        F: Fn(&str) -> bool,
+    {
+        let mut handles = Vec::new();
+        for (name, uuid) in crate::res::find_with_prefix(prefix) {
+            if filter(&name) {
+                handles.push(self.add_uuid(uuid)?);
+            }
+        }
+
+        Ok(handles)
+    }
+
+    /// Returns the number of resources in this group.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap().len()
+    }
+
+    /// Checks if this group is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Checks if the resource with `uuid` is part of this group.
+    #[inline]
+    pub fn contains(&self, uuid: Uuid) -> bool {
+        self.entries.lock().unwrap().iter().any(|v| v.0 == uuid)
+    }
+
+    /// Gets the aggregated state of all the resources in this group. It returns `NotReady`
+    /// if any resource is still loading, `Err` if any resource failed to load, and `Ok`
+    /// otherwise.
+    pub fn state(&self) -> ResourceState {
+        let mut state = ResourceState::Ok;
+        for (_, v) in self.entries.lock().unwrap().iter() {
+            match v.state() {
+                ResourceState::NotReady => return ResourceState::NotReady,
+                ResourceState::Err => state = ResourceState::Err,
+                ResourceState::Ok => {}
+            }
+        }
+
+        state
+    }
+
+    /// Gets the ratio of resources that have finished loading, in range [0, 1].
+    pub fn progress(&self) -> f32 {
+        let entries = self.entries.lock().unwrap();
+        if entries.is_empty() {
+            return 1.0;
+        }
+
+        let finished = entries
+            .iter()
+            .filter(|v| v.1.state() != ResourceState::NotReady)
+            .count();
+
+        finished as f32 / entries.len() as f32
+    }
+
+    /// Drops the ownerships of all the resources in this group.
+    pub fn release(&self) {
+        // Makes sure that the lock has been freed before deleting resources.
+        let entries = std::mem::replace(&mut *self.entries.lock().unwrap(), Vec::new());
+        for (_, v) in entries {
+            v.delete();
+        }
+    }
+}
+
+impl LatchProbe for ResourceGroup {
+    fn is_set(&self) -> bool {
+        self.state() != ResourceState::NotReady
+    }
+}
+
+impl Drop for ResourceGroup {
+    fn drop(&mut self) {
+        // The resources have been freed with their systems if the engine is discarded.
+        if crate::application::valid() {
+            self.release();
+        }
+    }
+}
@@ -1,7 +1,11 @@
+pub mod future;
+pub mod group;
 pub mod pool;
 pub mod state;
 
 pub mod prelude {
-    pub use super::pool::{ResourceLoader, ResourcePool};
+    pub use super::future::poll_resource;
+    pub use super::group::{ResourceGroup, ResourceHandle};
+    pub use super::pool::{ResourceInfo, ResourceLoader, ResourcePool, ResourcePoolStats};
     pub use super::state::ResourceState;
 }
@@ -24,12 +24,21 @@
 //! the resource by 1. And when you are done with the resource, its the user's responsibility to
 //! drop the ownership of the resource. And when the last ownership to a given resource is dropped,
 //! the corresponding resource is also destroyed.
+//!
+//! ## Budget & Eviction
+//!
+//! Every `ResourcePool` could be assigned with an optional memory budget, the memory usage of
+//! resources are reported by `ResourceLoader::size`. Resources that are created from file could
+//! be marked as _cached_, which means the pool is allowed to evict them in least-recently-used
+//! order when the memory usage exceeds the budget, even if they are still owned by someone. An
+//! evicted resource will be reloaded from its UUID transparently when its accessed again.
 
 use failure::Error;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
 use uuid::Uuid;
 
-use crate::utils::prelude::{FastHashMap, HandleLike, ObjectPool};
+use crate::utils::prelude::{FastHashMap, HandleIndex, HandleLike, ObjectPool};
 
 use super::state::ResourceState;
 
@@ -41,6 +50,42 @@ pub trait ResourceLoader: Send + Sync {
     fn load(&self, _: Self::Handle, _: &[u8]) -> Result<Self::Intermediate, Error>;
     fn create(&self, _: Self::Handle, _: Self::Intermediate) -> Result<Self::Resource, Error>;
     fn delete(&self, _: Self::Handle, _: Self::Resource);
+
+    /// Returns the memory usage of resource in bytes.
+    fn size(&self, _: &Self::Resource) -> usize {
+        0
+    }
+}
+
+/// The statistics of resources in a `ResourcePool`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResourcePoolStats {
+    /// The number of alive resources.
+    pub num: usize,
+    /// The number of resources that are still loading.
+    pub num_loading: usize,
+    /// The number of resources that have been evicted.
+    pub num_evicted: usize,
+    /// The memory usage in bytes of all the resources.
+    pub usage: usize,
+    /// The memory budget in bytes.
+    pub budget: Option<usize>,
+}
+
+/// The description of a resource in `ResourcePool`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResourceInfo {
+    pub index: HandleIndex,
+    pub version: HandleIndex,
+    /// The UUID if the resource is created from file.
+    pub uuid: Option<Uuid>,
+    pub state: ResourceState,
+    /// The reference count.
+    pub rc: u32,
+    /// The memory usage in bytes.
+    pub size: usize,
+    pub cached: bool,
+    pub evicted: bool,
 }
 
 // The `ResourcePool` is a standardized resources manager that defines a set of interface for creation,
@@ -54,6 +99,10 @@ where
     requests: FastHashMap<H, Arc<Mutex<ResourceAsyncState<Loader::Intermediate>>>>,
     registry: FastHashMap<Uuid, H>,
     loader: Loader,
+    budget: Option<usize>,
+    usage: usize,
+    tick: AtomicUsize,
+    last_advance_tick: usize,
 }
 
 impl<H, Loader> ResourcePool<H, Loader>
@@ -68,12 +117,19 @@ where
             registry: FastHashMap::default(),
             requests: FastHashMap::default(),
             loader,
+            budget: None,
+            usage: 0,
+            tick: AtomicUsize::new(0),
+            last_advance_tick: 0,
         }
     }
 
     pub fn advance(&mut self) -> Result<(), Error> {
+        self.reload_evicted();
+
         let items = &mut self.items;
         let loader = &self.loader;
+        let usage = &mut self.usage;
 
         self.requests.retain(|&handle, req| {
             let mut req = req.lock().unwrap();
@@ -94,7 +150,11 @@ where
                 ResourceAsyncState::Ok(intermediate) => {
                     if let Some(item) = items.get_mut(handle) {
                         match loader.create(handle, intermediate) {
-                            Ok(resource) => item.resource = Some(resource),
+                            Ok(resource) => {
+                                item.size = loader.size(&resource);
+                                item.resource = Some(resource);
+                                *usage += item.size;
+                            }
                             Err(err) => {
                                 warn!("{:?}", err);
                                 item.error = Some(err);
@@ -108,6 +168,8 @@ where
             false
         });
 
+        self.evict_exceeded();
+        self.last_advance_tick = self.tick.load(Ordering::Relaxed);
         Ok(())
     }
 
@@ -119,7 +181,10 @@ where
         let handle = self.alloc(None);
         match self.loader.create(handle, params) {
             Ok(value) => {
-                self.items.get_mut(handle).unwrap().resource = Some(value);
+                let item = self.items.get_mut(handle).unwrap();
+                item.size = self.loader.size(&value);
+                item.resource = Some(value);
+                self.usage += item.size;
                 Ok(handle)
             }
             Err(error) => {
@@ -142,40 +207,15 @@ where
     #[inline]
     pub fn create_from_uuid(&mut self, uuid: Uuid) -> Result<H, Error> {
         if let Some(&handle) = self.registry.get(&uuid) {
-            self.items.get_mut(handle).unwrap().rc += 1;
+            let item = self.items.get_mut(handle).unwrap();
+            item.rc += 1;
+            item.touch(&self.tick);
             return Ok(handle);
         }
 
         let handle = self.alloc(Some(uuid));
-
-        let rx = Arc::new(Mutex::new(ResourceAsyncState::NotReady));
-        let tx = rx.clone();
-        let loader = self.loader.clone();
-
-        let result = crate::res::load_with_callback(uuid, move |rsp| match rsp {
-            Ok(bytes) => {
-                let itermediate = loader.load(handle, &bytes);
-
-                match itermediate {
-                    Ok(item) => {
-                        *tx.lock().unwrap() = ResourceAsyncState::Ok(item);
-                    }
-                    Err(err) => {
-                        *tx.lock().unwrap() = ResourceAsyncState::Err(err);
-                    }
-                }
-            }
-
-            Err(err) => {
-                *tx.lock().unwrap() = ResourceAsyncState::Err(err);
-            }
-        });
-
-        match result {
-            Ok(_) => {
-                self.requests.insert(handle, rx);
-                Ok(handle)
-            }
+        match self.request(handle, uuid) {
+            Ok(_) => Ok(handle),
             Err(err) => {
                 self.delete(handle);
                 Err(err)
@@ -202,17 +242,22 @@ where
             }
 
             if let Some(resource) = e.resource {
+                self.usage -= e.size;
                 self.loader.delete(handle, resource);
             }
         }
     }
 
     /// Get the resource state.
+    ///
+    /// Notes that querying the state of an evicted resource will trigger the reloading of it.
     #[inline]
     pub fn state(&self, handle: H) -> ResourceState {
         self.items
             .get(handle)
             .map(|e| {
+                e.touch(&self.tick);
+
                 if e.resource.is_some() {
                     ResourceState::Ok
                 } else if e.error.is_some() {
@@ -230,16 +275,97 @@ where
         self.items.contains(handle)
     }
 
+    /// Marks the resource as recently used, so it would be the last one to be evicted.
+    ///
+    /// Notes that touching an evicted resource will trigger the reloading of it.
+    #[inline]
+    pub fn touch(&self, handle: H) {
+        if let Some(e) = self.items.get(handle) {
+            e.touch(&self.tick);
+        }
+    }
+
     /// Return immutable reference to internal value with name `Handle`.
+    ///
+    /// Notes that `None` is returned if the resource has been evicted, and it will be reloaded
+    /// asynchronously.
     #[inline]
     pub fn resource(&self, handle: H) -> Option<&Loader::Resource> {
-        self.items.get(handle).and_then(|e| e.resource.as_ref())
+        let tick = &self.tick;
+        self.items.get(handle).and_then(|e| {
+            e.touch(tick);
+            e.resource.as_ref()
+        })
     }
 
     /// Return mutable reference to internal value with name `Handle`.
+    ///
+    /// Notes that `None` is returned if the resource has been evicted, and it will be reloaded
+    /// asynchronously.
     #[inline]
     pub fn resource_mut(&mut self, handle: H) -> Option<&mut Loader::Resource> {
-        self.items.get_mut(handle).and_then(|e| e.resource.as_mut())
+        let tick = &self.tick;
+        self.items.get_mut(handle).and_then(|e| {
+            e.touch(tick);
+            e.resource.as_mut()
+        })
+    }
+
+    /// Sets the memory budget in bytes of this pool. Cached resources will be evicted in
+    /// least-recently-used order if the memory usage exceeds the budget.
+    #[inline]
+    pub fn set_budget(&mut self, budget: Option<usize>) {
+        self.budget = budget;
+    }
+
+    /// Marks the resource as cached, which allows it to be evicted when the memory usage
+    /// exceeds the budget. Only resources that are created from file could be cached.
+    pub fn set_cached(&mut self, handle: H, cached: bool) -> Result<(), Error> {
+        let item = self
+            .items
+            .get_mut(handle)
+            .ok_or_else(|| format_err!("{:?} is invalid.", handle))?;
+
+        if cached && item.uuid.is_none() {
+            bail!("{:?} could not be cached since its not created from file.", handle);
+        }
+
+        item.cached = cached;
+        Ok(())
+    }
+
+    /// Gets the statistics of resources in this pool.
+    pub fn stats(&self) -> ResourcePoolStats {
+        ResourcePoolStats {
+            num: self.items.len(),
+            num_loading: self.requests.len(),
+            num_evicted: self.items.values().filter(|v| v.evicted).count(),
+            usage: self.usage,
+            budget: self.budget,
+        }
+    }
+
+    /// Gets the descriptions of all the resources in this pool.
+    pub fn infos(&self) -> Vec<ResourceInfo> {
+        self.items
+            .iter()
+            .map(|(handle, v)| ResourceInfo {
+                index: handle.index(),
+                version: handle.version(),
+                uuid: v.uuid,
+                state: if v.resource.is_some() {
+                    ResourceState::Ok
+                } else if v.error.is_some() {
+                    ResourceState::Err
+                } else {
+                    ResourceState::NotReady
+                },
+                rc: v.rc,
+                size: v.size,
+                cached: v.cached,
+                evicted: v.evicted,
+            })
+            .collect()
     }
 
     #[inline]
@@ -249,6 +375,11 @@ where
             uuid,
             resource: None,
             error: None,
+            size: 0,
+            cached: false,
+            evicted: false,
+            reload: AtomicBool::new(false),
+            last_access: AtomicUsize::new(self.tick.fetch_add(1, Ordering::Relaxed)),
         };
 
         let handle = self.items.create(entry);
@@ -259,6 +390,91 @@ where
 
         handle
     }
+
+    fn request(&mut self, handle: H, uuid: Uuid) -> Result<(), Error> {
+        let rx = Arc::new(Mutex::new(ResourceAsyncState::NotReady));
+        let tx = rx.clone();
+        let loader = self.loader.clone();
+
+        crate::res::load_with_callback(uuid, move |rsp| match rsp {
+            Ok(bytes) => {
+                let itermediate = loader.load(handle, &bytes);
+
+                match itermediate {
+                    Ok(item) => {
+                        *tx.lock().unwrap() = ResourceAsyncState::Ok(item);
+                    }
+                    Err(err) => {
+                        *tx.lock().unwrap() = ResourceAsyncState::Err(err);
+                    }
+                }
+            }
+
+            Err(err) => {
+                *tx.lock().unwrap() = ResourceAsyncState::Err(err);
+            }
+        })?;
+
+        self.requests.insert(handle, rx);
+        Ok(())
+    }
+
+    fn reload_evicted(&mut self) {
+        let reloads: Vec<_> = self
+            .items
+            .iter()
+            .filter(|(_, v)| v.evicted && v.reload.load(Ordering::Relaxed))
+            .filter_map(|(handle, v)| v.uuid.map(|uuid| (handle, uuid)))
+            .collect();
+
+        for (handle, uuid) in reloads {
+            if let Some(item) = self.items.get_mut(handle) {
+                item.evicted = false;
+                item.reload.store(false, Ordering::Relaxed);
+            }
+
+            if let Err(err) = self.request(handle, uuid) {
+                warn!("{:?}", err);
+                if let Some(item) = self.items.get_mut(handle) {
+                    item.error = Some(err);
+                }
+            }
+        }
+    }
+
+    fn evict_exceeded(&mut self) {
+        let budget = match self.budget {
+            Some(budget) => budget,
+            None => return,
+        };
+
+        while self.usage > budget {
+            // Resources that have been accessed since last frame will not be evicted, this
+            // prevents us from reloading resources back and forth.
+            let last_advance_tick = self.last_advance_tick;
+            let lru = self
+                .items
+                .iter()
+                .filter(|(_, v)| v.cached && v.resource.is_some())
+                .map(|(handle, v)| (handle, v.last_access.load(Ordering::Relaxed)))
+                .filter(|&(_, last_access)| last_access < last_advance_tick)
+                .min_by_key(|&(_, last_access)| last_access)
+                .map(|(handle, _)| handle);
+
+            let handle = match lru {
+                Some(handle) => handle,
+                None => return,
+            };
+
+            let item = self.items.get_mut(handle).unwrap();
+            if let Some(resource) = item.resource.take() {
+                self.usage -= item.size;
+                item.size = 0;
+                item.evicted = true;
+                self.loader.delete(handle, resource);
+            }
+        }
+    }
 }
 
 struct Item<T> {
@@ -266,6 +482,23 @@ struct Item<T> {
     uuid: Option<Uuid>,
     resource: Option<T>,
     error: Option<Error>,
+    size: usize,
+    cached: bool,
+    evicted: bool,
+    reload: AtomicBool,
+    last_access: AtomicUsize,
+}
+
+impl<T> Item<T> {
+    #[inline]
+    fn touch(&self, tick: &AtomicUsize) {
+        let now = tick.fetch_add(1, Ordering::Relaxed);
+        self.last_access.store(now, Ordering::Relaxed);
+
+        if self.evicted {
+            self.reload.store(true, Ordering::Relaxed);
+        }
+    }
 }
 
 enum ResourceAsyncState<T> {
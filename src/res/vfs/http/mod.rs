@@ -0,0 +1,16 @@
+//! The `http://` virtual file system.
+//!
+//! On web platforms, files are fetched with `XmlHttpRequest` by the browser. On native platforms,
+//! a plain HTTP/1.1 client is used instead, which supports keep-alive connections, range requests,
+//! retries and an optional on-disk cache keyed by ETag. This makes it possible to stream assets
+//! from a shared asset server during development.
+
+#[cfg(not(target_arch = "wasm32"))]
+mod native;
+#[cfg(not(target_arch = "wasm32"))]
+pub use self::native::{Http, HttpParams};
+
+#[cfg(target_arch = "wasm32")]
+mod web;
+#[cfg(target_arch = "wasm32")]
+pub use self::web::Http;
@@ -0,0 +1,423 @@
+use std::fs;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use crate::sched::prelude::LockLatch;
+use crate::utils::hash::{hash64, FastHashMap};
+
+use super::super::super::request::Response;
+use super::super::super::url::Url;
+use super::super::VFS;
+
+/// The setup parameters of native `Http`.
+#[derive(Debug, Clone)]
+pub struct HttpParams {
+    /// The directory to cache the downloaded files. Cached files are validated with ETag
+    /// before reusing. Caching is disabled if its `None`.
+    pub cache: Option<PathBuf>,
+    /// The maximum number of retries when a request failed because of IO errors or server
+    /// errors (5xx).
+    pub retries: u32,
+    /// The delay before the first retry, it's doubled after each failed attempt.
+    pub retry_delay: Duration,
+    /// The timeout of connecting, reading and writing.
+    pub timeout: Duration,
+    /// The number of bytes to request with `Range` header each time. If its `None`, the file
+    /// will be requested at once. Notes that only the finished chunks could be resumed when
+    /// retrying.
+    pub chunk_size: Option<usize>,
+    /// The maximum number of idle keep-alive connections for each host.
+    pub max_idle_connections: usize,
+}
+
+impl Default for HttpParams {
+    fn default() -> Self {
+        HttpParams {
+            cache: None,
+            retries: 3,
+            retry_delay: Duration::from_millis(100),
+            timeout: Duration::from_secs(10),
+            chunk_size: None,
+            max_idle_connections: 4,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Http {
+    params: Arc<HttpParams>,
+    idles: Arc<Mutex<FastHashMap<String, Vec<BufReader<TcpStream>>>>>,
+}
+
+impl Default for Http {
+    fn default() -> Self {
+        Http::new()
+    }
+}
+
+impl Http {
+    pub fn new() -> Self {
+        Http::with_params(HttpParams::default())
+    }
+
+    pub fn with_params(params: HttpParams) -> Self {
+        Http {
+            params: Arc::new(params),
+            idles: Arc::new(Mutex::new(FastHashMap::default())),
+        }
+    }
+
+    fn load_from(&self, url: &Url) -> Response {
+        if url.schema() != "http" {
+            bail!("The schema of URL({}) is not supported by Http.", url);
+        }
+
+        let cache = self.params.cache.as_ref().map(|v| Cache::new(v, url));
+        let mut cached_etag = cache.as_ref().and_then(|v| v.etag());
+
+        let mut buf: Vec<u8> = Vec::new();
+        let mut total = None;
+        let mut etag: Option<String> = None;
+        let mut retries = 0;
+
+        while total.map(|v| buf.len() < v).unwrap_or(true) {
+            let mut headers = Vec::new();
+
+            if let Some(chunk_size) = self.params.chunk_size {
+                let end = buf.len() + chunk_size.max(1) - 1;
+                headers.push(format!("Range: bytes={}-{}", buf.len(), end));
+            } else if !buf.is_empty() {
+                headers.push(format!("Range: bytes={}-", buf.len()));
+            }
+
+            if let Some(etag) = etag.as_ref() {
+                // The server responses with full contents if file has been modified.
+                headers.push(format!("If-Range: {}", etag));
+            } else if let Some(etag) = cached_etag.as_ref() {
+                headers.push(format!("If-None-Match: {}", etag));
+            }
+
+            let rsp = match self.get(url, &headers) {
+                Ok(rsp) => rsp,
+                Err(err) => {
+                    let err = format_err!("Failed to request {}. {}", url, err);
+                    self.retry(&mut retries, err)?;
+                    continue;
+                }
+            };
+
+            match rsp.status {
+                200 => {
+                    total = Some(rsp.body.len());
+                    etag = rsp.header("ETag").map(|v| v.to_owned());
+                    buf = rsp.body;
+                }
+                206 => {
+                    let (start, size) = rsp
+                        .header("Content-Range")
+                        .and_then(parse_content_range)
+                        .ok_or_else(|| format_err!("Invalid Content-Range from {}.", url))?;
+
+                    if start != buf.len() {
+                        bail!("Unexpected Content-Range from {}.", url);
+                    }
+
+                    if rsp.body.is_empty() {
+                        let err = format_err!("Empty partial content from {}.", url);
+                        self.retry(&mut retries, err)?;
+                        continue;
+                    }
+
+                    if etag.is_none() {
+                        etag = rsp.header("ETag").map(|v| v.to_owned());
+                    }
+
+                    total = Some(size);
+                    buf.extend_from_slice(&rsp.body);
+                }
+                // The range of empty file is never satisfiable, which is reported with
+                // `Content-Range: bytes */0`.
+                416 if buf.is_empty()
+                    && rsp.header("Content-Range").map(|v| v.trim()) == Some("bytes */0") =>
+                {
+                    total = Some(0);
+                }
+                304 => match cache.as_ref().map(|v| v.bytes()) {
+                    Some(Ok(bytes)) => return Ok(bytes.into_boxed_slice()),
+                    _ => {
+                        // The cached file is broken, request it again without ETag.
+                        cached_etag = None;
+                    }
+                },
+                status if status >= 500 => {
+                    let err = format_err!("Failed to request {} (HTTP {}).", url, status);
+                    self.retry(&mut retries, err)?;
+                }
+                status => bail!("Failed to request {} (HTTP {}).", url, status),
+            }
+        }
+
+        if let (Some(cache), Some(etag)) = (cache, etag) {
+            if let Err(err) = cache.save(&etag, &buf) {
+                warn!("[Http] failed to cache {}. {:?}", url, err);
+            }
+        }
+
+        Ok(buf.into_boxed_slice())
+    }
+
+    /// Counts the failed attempt, and sleeps with exponential backoff before retrying. The
+    /// error is returned if there are no retries left.
+    fn retry(&self, retries: &mut u32, err: failure::Error) -> Result<(), failure::Error> {
+        *retries += 1;
+        if *retries > self.params.retries {
+            return Err(err);
+        }
+
+        let delay = self.params.retry_delay * 2u32.pow((*retries - 1).min(16));
+        warn!("[Http] {} Retrying in {:?}.", err, delay);
+        thread::sleep(delay);
+        Ok(())
+    }
+
+    fn get(&self, url: &Url, headers: &[String]) -> Result<HttpResponse, failure::Error> {
+        let host = match url.port() {
+            Some(port) => format!("{}:{}", url.host(), port),
+            None => format!("{}:80", url.host()),
+        };
+
+        let mut req = format!(
+            "GET {}{}{} HTTP/1.1\r\nHost: {}\r\nConnection: keep-alive\r\nAccept-Encoding: identity\r\n",
+            url.path(),
+            if url.query().is_some() { "?" } else { "" },
+            url.query().unwrap_or(""),
+            host
+        );
+
+        for v in headers {
+            req.push_str(v);
+            req.push_str("\r\n");
+        }
+
+        req.push_str("\r\n");
+
+        // Idle connections might have been closed by server, so we retry with a brand new
+        // connection if the reused one failed.
+        let idle = self.idles.lock().unwrap().get_mut(&host).and_then(|v| v.pop());
+        let (rsp, conn) = match idle.map(|mut conn| send(&mut conn, &req).map(|rsp| (rsp, conn))) {
+            Some(Ok(v)) => v,
+            _ => {
+                let mut conn = self.connect(&host)?;
+                let rsp = send(&mut conn, &req)?;
+                (rsp, conn)
+            }
+        };
+
+        if rsp.keep_alive {
+            let mut idles = self.idles.lock().unwrap();
+            let conns = idles.entry(host).or_insert_with(Vec::new);
+            if conns.len() < self.params.max_idle_connections {
+                conns.push(conn);
+            }
+        }
+
+        Ok(rsp)
+    }
+
+    fn connect(&self, host: &str) -> Result<BufReader<TcpStream>, failure::Error> {
+        let timeout = self.params.timeout;
+
+        let mut last_err = None;
+        for addr in host.to_socket_addrs()? {
+            match TcpStream::connect_timeout(&addr, timeout) {
+                Ok(stream) => {
+                    stream.set_read_timeout(Some(timeout))?;
+                    stream.set_write_timeout(Some(timeout))?;
+                    stream.set_nodelay(true)?;
+                    return Ok(BufReader::new(stream));
+                }
+                Err(err) => last_err = Some(err),
+            }
+        }
+
+        match last_err {
+            Some(err) => Err(err.into()),
+            None => bail!("Could not resolve host {}.", host),
+        }
+    }
+}
+
+impl VFS for Http {
+    fn request(&self, url: &Url, state: Arc<LockLatch<Response>>) {
+        let response = self.load_from(url);
+        state.set(response);
+    }
+}
+
+struct HttpResponse {
+    status: u16,
+    headers: Vec<(String, String)>,
+    body: Vec<u8>,
+    keep_alive: bool,
+}
+
+impl HttpResponse {
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|v| v.0.eq_ignore_ascii_case(name))
+            .map(|v| v.1.as_str())
+    }
+}
+
+fn send(conn: &mut BufReader<TcpStream>, req: &str) -> Result<HttpResponse, failure::Error> {
+    conn.get_mut().write_all(req.as_bytes())?;
+    conn.get_mut().flush()?;
+
+    let mut line = String::new();
+    if conn.read_line(&mut line)? == 0 {
+        bail!("Connection closed by server.");
+    }
+
+    let mut iter = line.split_whitespace();
+    let version = iter.next().unwrap_or("");
+    let status: u16 = iter
+        .next()
+        .and_then(|v| v.parse().ok())
+        .ok_or_else(|| format_err!("Malformed status line: {}", line.trim()))?;
+
+    let mut rsp = HttpResponse {
+        status,
+        headers: Vec::new(),
+        body: Vec::new(),
+        keep_alive: version == "HTTP/1.1",
+    };
+
+    loop {
+        line.clear();
+        conn.read_line(&mut line)?;
+
+        let header = line.trim_end();
+        if header.is_empty() {
+            break;
+        }
+
+        if let Some(index) = header.find(':') {
+            let name = header[..index].trim().to_owned();
+            let value = header[index + 1..].trim().to_owned();
+            rsp.headers.push((name, value));
+        }
+    }
+
+    if let Some(v) = rsp.header("Connection") {
+        rsp.keep_alive = !v.eq_ignore_ascii_case("close");
+    }
+
+    if status == 204 || status == 304 || status < 200 {
+        return Ok(rsp);
+    }
+
+    let chunked = rsp
+        .header("Transfer-Encoding")
+        .map(|v| v.eq_ignore_ascii_case("chunked"))
+        .unwrap_or(false);
+
+    let len = rsp
+        .header("Content-Length")
+        .and_then(|v| v.parse::<usize>().ok());
+
+    if chunked {
+        loop {
+            line.clear();
+            conn.read_line(&mut line)?;
+
+            let size = line.split(';').next().unwrap_or("").trim();
+            let size = usize::from_str_radix(size, 16)
+                .map_err(|_| format_err!("Malformed chunk size: {}", line.trim()))?;
+
+            if size == 0 {
+                // Skips trailers.
+                loop {
+                    line.clear();
+                    if conn.read_line(&mut line)? == 0 || line.trim_end().is_empty() {
+                        break;
+                    }
+                }
+
+                break;
+            }
+
+            let offset = rsp.body.len();
+            rsp.body.resize(offset + size, 0);
+            conn.read_exact(&mut rsp.body[offset..])?;
+
+            line.clear();
+            conn.read_line(&mut line)?;
+        }
+    } else if let Some(len) = len {
+        rsp.body.resize(len, 0);
+        conn.read_exact(&mut rsp.body)?;
+    } else {
+        conn.read_to_end(&mut rsp.body)?;
+        rsp.keep_alive = false;
+    }
+
+    Ok(rsp)
+}
+
+// Parses `Content-Range: bytes start-end/total`, returns (start, total).
+fn parse_content_range(v: &str) -> Option<(usize, usize)> {
+    let v = v.trim();
+    if !v.starts_with("bytes ") {
+        return None;
+    }
+
+    let mut iter = v[6..].split('/');
+    let range = iter.next()?;
+    let total = iter.next()?.trim().parse().ok()?;
+    let start = range.split('-').next()?.trim().parse().ok()?;
+    Some((start, total))
+}
+
+struct Cache {
+    etag: PathBuf,
+    bytes: PathBuf,
+}
+
+impl Cache {
+    fn new(dir: &Path, url: &Url) -> Self {
+        let name = format!("{:016X}", hash64(&**url));
+
+        Cache {
+            etag: dir.join(format!("{}.etag", name)),
+            bytes: dir.join(format!("{}.bin", name)),
+        }
+    }
+
+    fn etag(&self) -> Option<String> {
+        if !self.bytes.exists() {
+            return None;
+        }
+
+        fs::read_to_string(&self.etag).ok()
+    }
+
+    fn bytes(&self) -> std::io::Result<Vec<u8>> {
+        fs::read(&self.bytes)
+    }
+
+    fn save(&self, etag: &str, bytes: &[u8]) -> std::io::Result<()> {
+        if let Some(dir) = self.bytes.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        // Removes the stale ETag first, so we will never treat a partial written file as valid.
+        let _ = fs::remove_file(&self.etag);
+        fs::write(&self.bytes, bytes)?;
+        fs::write(&self.etag, etag)
+    }
+}
@@ -9,9 +9,9 @@ use web_sys::{XmlHttpRequest, XmlHttpRequestResponseType};
 
 use crate::sched::prelude::LockLatch;
 
-use super::super::request::Response;
-use super::super::url::Url;
-use super::VFS;
+use super::super::super::request::Response;
+use super::super::super::url::Url;
+use super::super::VFS;
 
 #[derive(Debug, Clone, Copy)]
 pub struct Http {}
@@ -1,6 +1,5 @@
 #[cfg(not(target_arch = "wasm32"))]
 pub mod dir;
-#[cfg(target_arch = "wasm32")]
 pub mod http;
 
 use std::sync::Arc;
@@ -32,6 +31,13 @@ impl SchemaResolver {
         self.schemas.insert(schema.into(), Arc::new(vfs));
     }
 
+    /// Gets the names of supported schemas.
+    pub fn schemas(&self) -> Vec<String> {
+        let mut schemas: Vec<_> = self.schemas.keys().cloned().collect();
+        schemas.sort();
+        schemas
+    }
+
     #[inline]
     pub fn locate<T1: AsRef<str>>(&self, schema: T1) -> Result<Arc<VFS>, failure::Error> {
         let schema = schema.as_ref();
@@ -0,0 +1,212 @@
+//! A frame-driven executor of futures.
+//!
+//! Futures spawned with `sched::spawn_future` are polled on the main thread during the
+//! `on_pre_update` phase of every frame, so game code could `await` asset loads and timers
+//! without blocking the main thread.
+//!
+//! Most of the futures in crayon (`Request`, `LockLatch`, resource handles and `Delay`) are
+//! backed by polling instead of notifications. They wake themselves up when they are not
+//! ready, so they will be polled again in the next frame.
+//!
+//! ```rust,ignore
+//! crayon::sched::spawn_future(async {
+//!     let mesh = video::create_mesh_from("res:cube.obj#Cube").unwrap();
+//!     mesh.await.unwrap();
+//!
+//!     crayon::sched::delay(Duration::from_secs(1)).await;
+//!     info!("One second after the mesh is ready.");
+//! });
+//! ```
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Wake, Waker};
+use std::time::Duration;
+
+use crate::utils::time::Timestamp;
+
+use super::handle::JobHandle;
+use super::latch::{LatchProbe, LockLatch};
+
+type BoxFuture = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+struct Task {
+    future: Mutex<Option<BoxFuture>>,
+    woken: AtomicBool,
+    handle: JobHandle,
+}
+
+impl Wake for Task {
+    fn wake(self: Arc<Self>) {
+        self.woken.store(true, Ordering::Release);
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.woken.store(true, Ordering::Release);
+    }
+}
+
+impl Task {
+    // Polls the future if it has been woken, returns true if its finished.
+    fn poll(self: &Arc<Self>) -> bool {
+        if !self.woken.swap(false, Ordering::AcqRel) {
+            return false;
+        }
+
+        let waker = Waker::from(self.clone());
+        let mut cx = Context::from_waker(&waker);
+
+        let finished = {
+            let mut future = self.future.lock().unwrap();
+            let finished = match *future {
+                Some(ref mut v) => v.as_mut().poll(&mut cx).is_ready(),
+                None => true,
+            };
+
+            if finished {
+                *future = None;
+            }
+
+            finished
+        };
+
+        if finished {
+            self.handle.finish();
+        }
+
+        finished
+    }
+}
+
+/// The executor which polls futures once per frame.
+#[derive(Default)]
+pub struct Executor {
+    tasks: Mutex<Vec<Arc<Task>>>,
+    incoming: Mutex<Vec<Arc<Task>>>,
+}
+
+impl Executor {
+    pub fn new() -> Self {
+        Executor::default()
+    }
+
+    /// Spawns a future, which will be polled in the next `advance`.
+    pub fn spawn<F>(&self, future: F) -> JobHandle
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        let handle = JobHandle::new();
+        let task = Arc::new(Task {
+            future: Mutex::new(Some(Box::pin(future))),
+            woken: AtomicBool::new(true),
+            handle: handle.clone(),
+        });
+
+        self.incoming.lock().unwrap().push(task);
+        handle
+    }
+
+    /// Polls all the woken futures once.
+    pub fn advance(&self) {
+        let mut tasks: Vec<_> = self.tasks.lock().unwrap().drain(..).collect();
+        tasks.extend(self.incoming.lock().unwrap().drain(..));
+
+        // Futures are polled without holding the lock, so they could spawn new futures or
+        // query this executor.
+        tasks.retain(|v| !v.poll());
+        self.tasks.lock().unwrap().extend(tasks);
+    }
+
+    /// Returns the number of unfinished futures.
+    pub fn len(&self) -> usize {
+        self.tasks.lock().unwrap().len() + self.incoming.lock().unwrap().len()
+    }
+
+    /// Checks if there are no unfinished futures.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Returns `Poll::Pending` and asks to be polled again.
+#[inline]
+pub fn pending<T>(cx: &mut Context) -> Poll<T> {
+    cx.waker().wake_by_ref();
+    Poll::Pending
+}
+
+/// A future which resolves once the latch is set.
+pub struct LatchFuture<T: LatchProbe>(T);
+
+impl<T: LatchProbe> LatchFuture<T> {
+    #[inline]
+    pub fn new(latch: T) -> Self {
+        LatchFuture(latch)
+    }
+}
+
+impl<T: LatchProbe + Unpin> Future for LatchFuture<T> {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        if self.0.is_set() {
+            Poll::Ready(())
+        } else {
+            pending(cx)
+        }
+    }
+}
+
+impl<'a, T> Future for &'a LockLatch<T> {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        if self.is_set() {
+            Poll::Ready(())
+        } else {
+            pending(cx)
+        }
+    }
+}
+
+impl Future for JobHandle {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        if self.is_finished() {
+            Poll::Ready(())
+        } else {
+            pending(cx)
+        }
+    }
+}
+
+/// A future which resolves after a period of time.
+pub struct Delay {
+    start: Timestamp,
+    duration: Duration,
+}
+
+impl Delay {
+    #[inline]
+    pub fn new(duration: Duration) -> Self {
+        Delay {
+            start: Timestamp::now(),
+            duration,
+        }
+    }
+}
+
+impl Future for Delay {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        if self.start.elapsed() >= self.duration {
+            Poll::Ready(())
+        } else {
+            pending(cx)
+        }
+    }
+}
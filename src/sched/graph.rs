@@ -0,0 +1,182 @@
+//! A declarative task graph which runs per-frame system updates in parallel.
+//!
+//! Every task declares the resources it reads and writes with `Access`. Tasks with conflicting
+//! accesses (write-write or read-write on the same resource) are executed in the order they were
+//! added, while the others could be executed in parallel on the worker threads.
+//!
+//! ```rust,ignore
+//! let mut graph = TaskGraph::new();
+//!
+//! let input = graph.add("input", Access::new().write::<Input>(), || { ... });
+//! graph.add("physics", Access::new().read::<Input>().write::<Bodies>(), || { ... });
+//! graph.add("audio", Access::new().read::<Input>(), || { ... });
+//! graph.add("stats", Access::new().after(input), || { ... });
+//!
+//! // Physics and audio are executed in parallel after input.
+//! graph.run();
+//! ```
+//!
+//! Systems usually declare their tasks every frame with `LifecycleListener::on_update_tasks`
+//! instead, and the tasks of all the systems are executed in one graph after `on_update`.
+
+use std::any::TypeId;
+use std::borrow::Cow;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+use crate::prof::ProfileScope;
+
+use super::scope::Scope;
+
+/// The identifier of task in a `TaskGraph`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TaskId(usize);
+
+/// The declaration of the resources a task reads and writes.
+#[derive(Debug, Clone, Default)]
+pub struct Access {
+    reads: Vec<TypeId>,
+    writes: Vec<TypeId>,
+    after: Vec<TaskId>,
+}
+
+impl Access {
+    pub fn new() -> Self {
+        Access::default()
+    }
+
+    /// Declares that the task reads resource `T`.
+    #[inline]
+    pub fn read<T: 'static>(mut self) -> Self {
+        self.reads.push(TypeId::of::<T>());
+        self
+    }
+
+    /// Declares that the task writes resource `T`.
+    #[inline]
+    pub fn write<T: 'static>(mut self) -> Self {
+        self.writes.push(TypeId::of::<T>());
+        self
+    }
+
+    /// Declares that the task should be executed after `task` explicitly.
+    #[inline]
+    pub fn after(mut self, task: TaskId) -> Self {
+        self.after.push(task);
+        self
+    }
+
+    fn conflicts(&self, rhs: &Access) -> bool {
+        self.writes
+            .iter()
+            .any(|v| rhs.writes.contains(v) || rhs.reads.contains(v))
+            || self.reads.iter().any(|v| rhs.writes.contains(v))
+    }
+}
+
+struct Task<'a> {
+    name: Cow<'static, str>,
+    access: Access,
+    func: Mutex<Box<FnMut() + Send + 'a>>,
+    // The number of tasks this one depends on.
+    num_deps: usize,
+    // The tasks which depends on this one.
+    dependents: Vec<usize>,
+}
+
+/// A graph of tasks which could be executed every frame.
+#[derive(Default)]
+pub struct TaskGraph<'a> {
+    tasks: Vec<Task<'a>>,
+}
+
+impl<'a> TaskGraph<'a> {
+    pub fn new() -> Self {
+        TaskGraph { tasks: Vec::new() }
+    }
+
+    /// Adds a task into the graph. It will be executed after all the conflicting tasks that
+    /// were added before it.
+    pub fn add<T, F>(&mut self, name: T, access: Access, func: F) -> TaskId
+    where
+        T: Into<Cow<'static, str>>,
+        F: FnMut() + Send + 'a,
+    {
+        let index = self.tasks.len();
+        let mut num_deps = 0;
+
+        for (i, v) in self.tasks.iter_mut().enumerate() {
+            if access.after.contains(&TaskId(i)) || v.access.conflicts(&access) {
+                v.dependents.push(index);
+                num_deps += 1;
+            }
+        }
+
+        self.tasks.push(Task {
+            name: name.into(),
+            access,
+            func: Mutex::new(Box::new(func)),
+            num_deps,
+            dependents: Vec::new(),
+        });
+
+        TaskId(index)
+    }
+
+    /// Gets the name of task.
+    #[inline]
+    pub fn name(&self, task: TaskId) -> Option<&str> {
+        self.tasks.get(task.0).map(|v| v.name.as_ref())
+    }
+
+    /// Returns the number of tasks.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.tasks.len()
+    }
+
+    /// Checks if the graph is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.tasks.is_empty()
+    }
+
+    /// Executes all the tasks, and blocks until they are finished. Panics in tasks are
+    /// propagated to the caller.
+    pub fn run(&mut self) {
+        if self.tasks.is_empty() {
+            return;
+        }
+
+        let tasks = &self.tasks;
+        let counters: Vec<_> = tasks.iter().map(|v| AtomicUsize::new(v.num_deps)).collect();
+        let counters = &counters;
+
+        super::scope(|s| {
+            for (i, v) in tasks.iter().enumerate() {
+                if v.num_deps == 0 {
+                    s.spawn(move |s| execute(s, tasks, counters, i));
+                }
+            }
+        });
+    }
+}
+
+fn execute<'s, 'a: 's>(
+    s: &Scope<'s>,
+    tasks: &'s [Task<'a>],
+    counters: &'s [AtomicUsize],
+    i: usize,
+) {
+    {
+        let _s = ProfileScope::new("graph", tasks[i].name.clone());
+        let mut func = tasks[i].func.lock().unwrap();
+        (&mut **func)();
+    }
+
+    for &v in &tasks[i].dependents {
+        if counters[v].fetch_sub(1, Ordering::AcqRel) == 1 {
+            s.spawn(move |s| execute(s, tasks, counters, v));
+        }
+    }
+}
@@ -0,0 +1,122 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+use super::latch::{LatchProbe, LatchWaitProbe, LockLatch};
+
+type Continuation = Box<FnOnce() + Send>;
+
+/// A handle to an asynchronous job, which could be used to wait for its completion or
+/// to chain continuations and dependent jobs.
+///
+/// Notes that a job is considered as finished even if it panicked, the panic is handled by
+/// the `PanicHandler` of scheduler.
+#[derive(Clone)]
+pub struct JobHandle {
+    state: Arc<JobState>,
+}
+
+struct JobState {
+    latch: LockLatch<()>,
+    // None if the job has been finished.
+    continuations: Mutex<Option<Vec<Continuation>>>,
+}
+
+impl JobHandle {
+    pub(crate) fn new() -> Self {
+        JobHandle {
+            state: Arc::new(JobState {
+                latch: LockLatch::new(),
+                continuations: Mutex::new(Some(Vec::new())),
+            }),
+        }
+    }
+
+    /// Checks if the job has been finished.
+    #[inline]
+    pub fn is_finished(&self) -> bool {
+        self.state.latch.is_set()
+    }
+
+    /// Blocks current thread until the job is finished. Try to keep busy by popping and
+    /// stealing jobs if we are in a worker thread.
+    #[inline]
+    pub fn wait(&self) {
+        super::wait_until(self);
+    }
+
+    /// Spawns `func` as a continuation of this job, which will be executed after this
+    /// job is finished.
+    pub fn then<F>(&self, func: F) -> JobHandle
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        super::spawn_after(&[self], func)
+    }
+
+    /// Marks the job as finished, and invokes all the continuations.
+    pub(crate) fn finish(&self) {
+        let continuations = {
+            let mut guard = self.state.continuations.lock().unwrap();
+            self.state.latch.set(());
+            guard.take().unwrap_or_default()
+        };
+
+        for v in continuations {
+            v();
+        }
+    }
+
+    /// Registers a callback which will be invoked right after the job is finished. The
+    /// callback is invoked immediately if the job has been finished already.
+    pub(crate) fn on_finished(&self, func: Continuation) {
+        {
+            let mut guard = self.state.continuations.lock().unwrap();
+            if let Some(ref mut v) = *guard {
+                v.push(func);
+                return;
+            }
+        }
+
+        func();
+    }
+}
+
+impl LatchProbe for JobHandle {
+    #[inline]
+    fn is_set(&self) -> bool {
+        self.is_finished()
+    }
+}
+
+impl LatchWaitProbe for JobHandle {
+    #[inline]
+    fn wait(&self) {
+        self.state.latch.wait();
+    }
+}
+
+/// Counts the unfinished dependencies of a job, and kicks it off when all of them finished.
+pub(crate) struct Dependencies {
+    counter: AtomicUsize,
+    kick: Mutex<Option<Continuation>>,
+}
+
+impl Dependencies {
+    /// Creates a counter with `num` dependencies. An extra count is held by the creator,
+    /// which should be released with `release` after all the dependencies are registered.
+    pub fn new(num: usize, kick: Continuation) -> Arc<Self> {
+        Arc::new(Dependencies {
+            counter: AtomicUsize::new(num + 1),
+            kick: Mutex::new(Some(kick)),
+        })
+    }
+
+    pub fn release(&self) {
+        if self.counter.fetch_sub(1, Ordering::AcqRel) == 1 {
+            let kick = self.kick.lock().unwrap().take();
+            if let Some(kick) = kick {
+                kick();
+            }
+        }
+    }
+}
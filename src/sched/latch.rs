@@ -26,7 +26,7 @@ impl<T: LatchProbe> LatchProbe for std::sync::Arc<T> {
     }
 }
 
-pub(crate) trait LatchWaitProbe: LatchProbe {
+pub trait LatchWaitProbe: LatchProbe {
     /// Blocks thread until the latch is set.
     fn wait(&self);
 }
@@ -139,6 +139,8 @@ impl<T> LatchWaitProbe for LockLatch<T> {
 #[derive(Debug, Default)]
 pub struct CountLatch {
     counter: AtomicUsize,
+    m: Mutex<()>,
+    v: Condvar,
 }
 
 impl CountLatch {
@@ -146,6 +148,8 @@ impl CountLatch {
     pub fn new() -> CountLatch {
         CountLatch {
             counter: AtomicUsize::new(1),
+            m: Mutex::new(()),
+            v: Condvar::new(),
         }
     }
 
@@ -160,7 +164,10 @@ impl Latch for CountLatch {
     /// Set the latch to true, releasing all threads who are waiting.
     #[inline]
     fn set(&self) {
-        self.counter.fetch_sub(1, Ordering::SeqCst);
+        if self.counter.fetch_sub(1, Ordering::SeqCst) == 1 {
+            let _guard = self.m.lock().unwrap();
+            self.v.notify_all();
+        }
     }
 }
 
@@ -171,3 +178,12 @@ impl LatchProbe for CountLatch {
         self.counter.load(Ordering::SeqCst) == 0
     }
 }
+
+impl LatchWaitProbe for CountLatch {
+    fn wait(&self) {
+        let mut guard = self.m.lock().unwrap();
+        while !self.is_set() {
+            guard = self.v.wait(guard).unwrap();
+        }
+    }
+}
@@ -1,34 +1,142 @@
+pub mod future;
+pub mod graph;
+pub mod handle;
 pub mod latch;
+pub mod par;
 pub mod scope;
 mod system;
 
 mod job;
 mod scheduler;
+mod thread;
 mod unwind;
 
 pub mod prelude {
-    pub use super::latch::{CountLatch, Latch, LatchProbe, LockLatch, SpinLatch};
+    pub use super::future::{Delay, LatchFuture};
+    pub use super::graph::{Access, TaskGraph, TaskId};
+    pub use super::handle::JobHandle;
+    pub use super::latch::{CountLatch, Latch, LatchProbe, LatchWaitProbe, LockLatch, SpinLatch};
+    pub use super::par::{par_chunks, par_for, par_map};
     pub use super::system::PanicHandler;
+    pub use super::{QueueParams, QueuePriority, SchedulerParams};
 }
 
+use std::future::Future;
+use std::time::Duration;
+
+use self::future::Delay;
+use self::handle::JobHandle;
 use self::inside::{ctx, CTX};
+use self::latch::LatchWaitProbe;
 use self::scope::Scope;
 
+/// The name of default queue, which is used by `spawn` and `scope`.
+pub const DEFAULT_QUEUE: &str = "sched";
+
+/// The priority of job queue. Idle workers help the queues with higher priority than their
+/// own, so workers of low priority queues never steal time from the more critical jobs.
+///
+/// The priority is also applied to the worker threads as niceness (`Low` is 10, and `High`
+/// is -5) on Linux. Raising the priority requires `CAP_SYS_NICE`, the workers keep running
+/// with default priority if its not permitted. It's ignored on other platforms.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum QueuePriority {
+    Low,
+    Normal,
+    High,
+}
+
+/// The setup parameters of a named job queue.
+#[derive(Debug, Clone)]
+pub struct QueueParams {
+    /// The name of this queue.
+    pub name: String,
+    /// The number of workers dedicated to this queue.
+    pub workers: u32,
+    /// The priority of this queue.
+    pub priority: QueuePriority,
+    /// The CPU cores which the workers are pinned to in round-robin. Workers are not pinned
+    /// if its empty. Notes that the affinity is only supported on Linux.
+    pub affinity: Vec<usize>,
+}
+
+/// The setup parameters of sched system. It could not be constructed with struct literal,
+/// so new fields could be added without breaking changes.
+///
+/// ```rust,ignore
+/// let mut params = SchedulerParams::default();
+/// params.workers = 2;
+///
+/// let params = params.with_queue(QueueParams {
+///     name: "audio".to_owned(),
+///     workers: 1,
+///     priority: QueuePriority::High,
+///     affinity: Vec::new(),
+/// });
+/// ```
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct SchedulerParams {
+    /// The number of workers of the default queue, which has `QueuePriority::Normal`. The
+    /// jobs are executed on the calling thread directly if its zero.
+    pub workers: u32,
+    /// The stack size of worker threads.
+    pub stack_size: Option<usize>,
+    /// The named queues besides the default one.
+    pub queues: Vec<QueueParams>,
+}
+
+impl Default for SchedulerParams {
+    fn default() -> Self {
+        #[cfg(not(target_arch = "wasm32"))]
+        let workers = 4;
+        #[cfg(target_arch = "wasm32")]
+        let workers = 0;
+
+        SchedulerParams {
+            workers,
+            stack_size: None,
+            queues: vec![QueueParams {
+                name: "io".to_owned(),
+                workers: 2,
+                priority: QueuePriority::Low,
+                affinity: Vec::new(),
+            }],
+        }
+    }
+}
+
+impl SchedulerParams {
+    /// Adds a named queue besides the default one.
+    #[inline]
+    pub fn with_queue(mut self, queue: QueueParams) -> Self {
+        self.queues.push(queue);
+        self
+    }
+}
+
 /// Checks if the sched system is enabled.
 #[inline]
 pub fn valid() -> bool {
     unsafe { !CTX.is_null() }
 }
 
-// /// Blocks current thread until latch is set. Try to keep busy by popping and stealing jobs
-// /// as necessary.
-// #[inline]
-// pub fn wait_until<T>(latch: &T)
-// where
-//     T: LatchWaitProbe,
-// {
-//     ctx().wait_until(latch);
-// }
+/// Returns the number of worker threads, it's zero if jobs are executed on the calling
+/// thread directly.
+#[inline]
+pub fn num_workers() -> usize {
+    ctx().num_workers()
+}
+
+/// Blocks current thread until latch is set. Try to keep busy by popping and stealing jobs
+/// as necessary.
+#[inline]
+pub fn wait_until<T>(latch: &T)
+where
+    T: LatchWaitProbe,
+{
+    ctx().wait_until(latch);
+}
 
 /// Spawn an asynchronous job in the global `Scheduler.`
 pub fn spawn<F>(func: F)
@@ -38,6 +146,56 @@ where
     ctx().spawn(func);
 }
 
+/// Spawn an asynchronous job into the named queue, e.g. "io". The default queue is used
+/// if there is no queue named `queue`.
+pub fn spawn_on<F>(queue: &str, func: F)
+where
+    F: FnOnce() + Send + 'static,
+{
+    ctx().spawn_on(queue, func);
+}
+
+/// Spawn an asynchronous job in the global `Scheduler`, and returns a `JobHandle` which
+/// could be used to wait for its completion or to chain continuations.
+pub fn spawn_with_handle<F>(func: F) -> JobHandle
+where
+    F: FnOnce() + Send + 'static,
+{
+    ctx().spawn_on_with_handle(DEFAULT_QUEUE, func)
+}
+
+/// Spawn an asynchronous job into the named queue, and returns a `JobHandle`.
+pub fn spawn_on_with_handle<F>(queue: &str, func: F) -> JobHandle
+where
+    F: FnOnce() + Send + 'static,
+{
+    ctx().spawn_on_with_handle(queue, func)
+}
+
+/// Spawn an asynchronous job which will be executed after all the `deps` finished.
+pub fn spawn_after<F>(deps: &[&JobHandle], func: F) -> JobHandle
+where
+    F: FnOnce() + Send + 'static,
+{
+    ctx().spawn_after(deps, func)
+}
+
+/// Spawn a future into the frame-driven executor. It's polled on the main thread once per
+/// frame (before `on_update`) until completion, returns a `JobHandle` which will be finished
+/// along with the future.
+pub fn spawn_future<F>(future: F) -> JobHandle
+where
+    F: Future<Output = ()> + Send + 'static,
+{
+    ctx().spawn_future(future)
+}
+
+/// Creates a future which resolves after `duration`.
+#[inline]
+pub fn delay(duration: Duration) -> Delay {
+    Delay::new(duration)
+}
+
 /// Create a "fork-join" scope `s` and invokes the closure with a
 /// reference to `s`. This closure can then spawn asynchronous tasks
 /// into `s`. Those tasks may run asynchronously with respect to the
@@ -54,6 +212,7 @@ where
 
 pub(crate) mod inside {
     use super::system::{PanicHandler, SchedulerSystem};
+    use super::SchedulerParams;
 
     pub static mut CTX: *const SchedulerSystem = std::ptr::null();
 
@@ -69,15 +228,11 @@ pub(crate) mod inside {
     }
 
     /// Setup the sched system.
-    pub unsafe fn setup(
-        num: u32,
-        stack_size: Option<usize>,
-        panic_handler: Option<Box<PanicHandler>>,
-    ) {
+    pub unsafe fn setup(params: &SchedulerParams, panic_handler: Option<Box<PanicHandler>>) {
         debug_assert!(CTX.is_null(), "duplicated setup of sched system.");
 
-        CTX = Box::into_raw(Box::new(if num > 0 {
-            SchedulerSystem::new(num, stack_size, panic_handler)
+        CTX = Box::into_raw(Box::new(if params.workers > 0 {
+            SchedulerSystem::new(params, panic_handler)
         } else {
             SchedulerSystem::headless()
         }));
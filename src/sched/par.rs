@@ -0,0 +1,120 @@
+//! Parallel iteration helpers built on top of `sched::scope`.
+//!
+//! The items are split into halves recursively until the pieces are small enough, one
+//! half is spawned into the scope and the other one is processed on the current thread,
+//! so idle workers could steal the spawned halves. The pieces are never smaller than
+//! `MIN_LEN` items (or the chunk size), and nothing is spawned if the items fit into one
+//! piece or the sched system is not available.
+//!
+//! ```rust,ignore
+//! let mut positions = vec![Vector3::zero(); 4096];
+//! sched::par::par_for(&mut positions, |i, v| v.x = i as f32);
+//!
+//! let lengths = sched::par::par_map(&positions, |v| v.magnitude());
+//! ```
+
+use super::scope::Scope;
+
+/// The minimum number of items processed by one job.
+pub const MIN_LEN: usize = 64;
+
+// The number of pieces per worker, the more pieces there are, the better the load is
+// balanced between workers.
+const PIECES_PER_WORKER: usize = 4;
+
+/// Executes `func` for every item with its index in parallel.
+pub fn par_for<T, F>(items: &mut [T], func: F)
+where
+    T: Send,
+    F: Fn(usize, &mut T) + Sync,
+{
+    par_chunks(items, 1, |offset, chunk| {
+        for (i, v) in chunk.iter_mut().enumerate() {
+            func(offset + i, v);
+        }
+    });
+}
+
+/// Executes `func` for every `chunk_size` items in parallel. The last chunk might be
+/// shorter than `chunk_size`. The index of first item in chunk is passed as the first
+/// argument.
+pub fn par_chunks<T, F>(items: &mut [T], chunk_size: usize, func: F)
+where
+    T: Send,
+    F: Fn(usize, &mut [T]) + Sync,
+{
+    assert!(chunk_size > 0, "chunk size must be non-zero.");
+
+    let grain = grain(items.len(), chunk_size);
+    if items.len() <= grain {
+        execute(0, items, chunk_size, &func);
+        return;
+    }
+
+    let func = &func;
+    super::scope(|s| split(s, 0, items, grain, chunk_size, func));
+}
+
+/// Maps every item with `func` in parallel, and collects the results in order.
+pub fn par_map<T, R, F>(items: &[T], func: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    let mut results: Vec<Option<R>> = items.iter().map(|_| None).collect();
+    par_for(&mut results, |i, v| *v = Some(func(&items[i])));
+    results.into_iter().map(|v| v.unwrap()).collect()
+}
+
+// Returns the number of items per piece, which is a multiple of `chunk_size`.
+fn grain(len: usize, chunk_size: usize) -> usize {
+    let workers = if super::valid() {
+        super::num_workers()
+    } else {
+        0
+    };
+
+    if workers == 0 {
+        return len.max(chunk_size);
+    }
+
+    let pieces = workers * PIECES_PER_WORKER;
+    let grain = (len + pieces - 1) / pieces;
+    let grain = grain.max(MIN_LEN).max(chunk_size);
+    (grain + chunk_size - 1) / chunk_size * chunk_size
+}
+
+fn split<'s, T, F>(
+    s: &Scope<'s>,
+    offset: usize,
+    items: &'s mut [T],
+    grain: usize,
+    chunk_size: usize,
+    func: &'s F,
+) where
+    T: Send,
+    F: Fn(usize, &mut [T]) + Sync,
+{
+    if items.len() <= grain {
+        execute(offset, items, chunk_size, func);
+        return;
+    }
+
+    // Splits at the boundary of chunks.
+    let chunks = (items.len() + chunk_size - 1) / chunk_size;
+    let mid = (chunks / 2) * chunk_size;
+    let (lhs, rhs) = items.split_at_mut(mid);
+
+    s.spawn(move |s| split(s, offset + mid, rhs, grain, chunk_size, func));
+    split(s, offset, lhs, grain, chunk_size, func);
+}
+
+fn execute<T, F>(offset: usize, items: &mut [T], chunk_size: usize, func: &F)
+where
+    F: Fn(usize, &mut [T]),
+{
+    for (i, v) in items.chunks_mut(chunk_size).enumerate() {
+        func(offset + i * chunk_size, v);
+    }
+}
@@ -1,70 +1,129 @@
 use std::cell::Cell;
-use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 use std::sync::{Arc, Condvar, Mutex};
+use std::time::Instant;
 use std::{mem, thread};
 
 use crossbeam_deque as deque;
 
-use super::job::{JobRef, StackJob};
+use super::job::JobRef;
 use super::latch::{CountLatch, Latch, LatchProbe, LatchWaitProbe, LockLatch};
 use super::system::PanicHandler;
 use super::unwind::AbortIfPanic;
+use super::{QueuePriority, SchedulerParams, DEFAULT_QUEUE};
+
+/// The statistics of a worker thread.
+#[derive(Debug, Clone, Serialize)]
+pub struct WorkerStats {
+    pub name: String,
+    /// The name of queue that this worker dedicated to.
+    pub queue: String,
+    /// The number of jobs executed.
+    pub jobs: usize,
+    /// The time spent on executing jobs in milliseconds.
+    pub busy_ms: u64,
+    /// The ratio of busy time since the scheduler started.
+    pub utilization: f32,
+}
 
 pub struct Scheduler {
+    start: Instant,
     terminator: CountLatch,
     watcher: Watcher,
     threads: Vec<ThreadInfo>,
-
-    inject_stealer: deque::Stealer<JobRef>,
-    injector: Mutex<deque::Worker<JobRef>>,
+    // The first one is the default queue.
+    queues: Vec<Queue>,
 
     panic_handler: Option<Box<PanicHandler>>,
 }
 
+struct Queue {
+    name: String,
+    priority: QueuePriority,
+    stealer: deque::Stealer<JobRef>,
+    injector: Mutex<deque::Worker<JobRef>>,
+}
+
 impl Scheduler {
-    pub fn new(
-        num: u32,
-        stack_size: Option<usize>,
-        panic_handler: Option<Box<PanicHandler>>,
-    ) -> Arc<Self> {
+    pub fn new(params: &SchedulerParams, panic_handler: Option<Box<PanicHandler>>) -> Arc<Self> {
+        let mut queues = vec![(
+            DEFAULT_QUEUE.to_owned(),
+            params.workers,
+            QueuePriority::Normal,
+            Vec::new(),
+        )];
+
+        for v in &params.queues {
+            queues.push((v.name.clone(), v.workers, v.priority, v.affinity.clone()));
+        }
+
         let mut stealers = Vec::new();
         let mut workers = Vec::new();
 
-        for _ in 0..num {
-            let (w, s) = deque::fifo();
-            workers.push(w);
-            stealers.push(s);
+        for (i, &(ref name, num, priority, ref affinity)) in queues.iter().enumerate() {
+            for j in 0..num {
+                let (w, s) = deque::fifo();
+                let core = if affinity.is_empty() {
+                    None
+                } else {
+                    Some(affinity[j as usize % affinity.len()])
+                };
+
+                let thread_name = format!("crayon-{}-{}", name, j);
+                workers.push((i, thread_name, name.clone(), j, priority, core, w));
+                stealers.push(s);
+            }
         }
 
-        let (w, s) = deque::fifo();
-        let stealers = stealers
+        let threads = stealers
             .into_iter()
-            .map(|v| ThreadInfo {
+            .zip(workers.iter())
+            .map(|(v, &(queue, ref name, _, _, _, _, _))| ThreadInfo {
+                name: name.clone(),
+                queue,
                 stealer: v,
                 primed: LockLatch::new(),
                 terminated: LockLatch::new(),
+                jobs: AtomicUsize::new(0),
+                busy_us: AtomicU64::new(0),
+            })
+            .collect();
+
+        let queues = queues
+            .into_iter()
+            .map(|(name, _, priority, _)| {
+                let (w, s) = deque::fifo();
+                Queue {
+                    name,
+                    priority,
+                    stealer: s,
+                    injector: Mutex::new(w),
+                }
             })
             .collect();
 
         let scheduler = Arc::new(Scheduler {
-            threads: stealers,
-            injector: Mutex::new(w),
-            inject_stealer: s,
+            start: Instant::now(),
+            threads,
+            queues,
             panic_handler,
             terminator: CountLatch::new(),
             watcher: Watcher(Mutex::new(()), Condvar::new()),
         });
 
-        for (i, w) in workers.drain(..).enumerate() {
+        for (i, (queue, thread_name, name, j, priority, core, w)) in workers.drain(..).enumerate() {
             let sc = scheduler.clone();
-            let mut b = thread::Builder::new();
+            let mut b = thread::Builder::new().name(thread_name);
 
-            if let Some(stack_size) = stack_size {
+            if let Some(stack_size) = params.stack_size {
                 b = b.stack_size(stack_size);
             }
 
-            b.spawn(move || unsafe { Scheduler::main_loop(sc, i, w) })
-                .unwrap();
+            b.spawn(move || unsafe {
+                super::thread::setup(&name, j, priority, core);
+                Scheduler::main_loop(sc, i, queue, w)
+            })
+            .unwrap();
         }
 
         for v in &scheduler.threads {
@@ -74,17 +133,78 @@ impl Scheduler {
         scheduler
     }
 
+    /// Returns the number of worker threads.
+    #[inline]
+    pub fn num_threads(&self) -> usize {
+        self.threads.len()
+    }
+
+    /// Gets the statistics of worker threads.
+    pub fn workers(&self) -> Vec<WorkerStats> {
+        let elapsed = self.start.elapsed();
+        let elapsed_us = elapsed.as_secs() * 1_000_000 + u64::from(elapsed.subsec_micros());
+
+        self.threads
+            .iter()
+            .map(|v| {
+                let busy_us = v.busy_us.load(Ordering::Relaxed);
+                WorkerStats {
+                    name: v.name.clone(),
+                    queue: self.queues[v.queue].name.clone(),
+                    jobs: v.jobs.load(Ordering::Relaxed),
+                    busy_ms: busy_us / 1000,
+                    utilization: (busy_us as f64 / elapsed_us.max(1) as f64) as f32,
+                }
+            })
+            .collect()
+    }
+
+    /// Finds the index of queue by its name.
+    pub fn queue(&self, name: &str) -> Option<usize> {
+        self.queues.iter().position(|v| v.name == name)
+    }
+
+    // Returns the queues that workers of `queue` serve, in the order of preference. Workers
+    // serve their own queue first, and help the queues with higher priority when idle.
+    fn serves(&self, queue: usize) -> Vec<usize> {
+        let priority = self.queues[queue].priority;
+        let mut serves: Vec<_> = (0..self.queues.len())
+            .filter(|&i| i != queue && self.queues[i].priority > priority)
+            .collect();
+
+        serves.sort_by_key(|&i| ::std::cmp::Reverse(self.queues[i].priority));
+        serves.insert(0, queue);
+        serves
+    }
+
     /// Push a job into the "external jobs" queue; it will be taken by whatever
     /// worker has nothing to do.
     pub fn inject(&self, job: JobRef) {
         {
-            let injector = self.injector.lock().unwrap();
+            let injector = self.queues[0].injector.lock().unwrap();
             injector.push(job);
         }
 
         self.watcher.notify_one();
     }
 
+    /// Push a job into the named queue, it will be taken by the workers which serve
+    /// this queue.
+    pub fn inject_to(&self, queue: usize, job: JobRef) {
+        if queue == 0 {
+            self.inject_or_push(job);
+            return;
+        }
+
+        {
+            let injector = self.queues[queue].injector.lock().unwrap();
+            injector.push(job);
+        }
+
+        // Not all the workers serve this queue, so we have to wake up all of them.
+        self.watcher.notify_all();
+    }
+
     // /// Push a slice of jobs into the "external jobs" queue; it will be taken by
     // /// whatever worker has nothing to do.
     // fn inject_slice(&self, jobs: &[JobRef]) {
@@ -113,11 +233,11 @@ impl Scheduler {
     }
 
     /// If already in a worker-thread of this registry, just execute `op`.
-    /// Otherwise, inject `op` in this thread-pool. Either way, block until `op`
-    /// completes and return its return value. If `op` panics, that panic will
-    /// be propagated as well.  The second argument indicates `true` if injection
-    /// was performed, `false` if executed directly.
-    pub fn in_worker<OP, R>(&self, op: OP) -> R
+    /// Otherwise, the current thread joins as a guest worker and executes `op`. Either
+    /// way, block until `op` completes and return its return value. If `op` panics, that
+    /// panic will be propagated as well.  The second argument indicates `true` if the
+    /// current thread is a guest, `false` if it's a worker.
+    pub fn in_worker<OP, R>(scheduler: &Arc<Self>, op: OP) -> R
     where
         OP: FnOnce(&WorkerThread, bool) -> R + Send,
         R: Send,
@@ -125,18 +245,7 @@ impl Scheduler {
         unsafe {
             let worker_thread = WorkerThread::current();
             if worker_thread.is_null() {
-                let job = StackJob::new(
-                    |_| {
-                        let worker_thread = WorkerThread::current();
-                        op(&*worker_thread, true)
-                    },
-                    LockLatch::new(),
-                );
-
-                self.inject(job.as_job_ref());
-
-                job.latch.wait();
-                job.into_result()
+                Scheduler::as_guest(scheduler, |guest| op(guest, true))
             } else {
                 // Perfectly valid to give them a `&T`: this is the
                 // current thread, so we know the data structure won't be
@@ -146,6 +255,35 @@ impl Scheduler {
         }
     }
 
+    // Registers the current thread, which is not a worker (e.g. the main thread), as a guest
+    // worker during `op`. Guests serve the default queue only, and the jobs they push are
+    // injected, so they could be stolen after the guest leaves.
+    unsafe fn as_guest<OP, R>(scheduler: &Arc<Self>, op: OP) -> R
+    where
+        OP: FnOnce(&WorkerThread) -> R,
+    {
+        struct Leave;
+
+        impl Drop for Leave {
+            fn drop(&mut self) {
+                WORKER_THREAD_STATE.with(|t| t.set(std::ptr::null()));
+            }
+        }
+
+        let (worker, _) = deque::fifo();
+        let guest = WorkerThread {
+            scheduler: scheduler.clone(),
+            index: None,
+            serves: vec![0],
+            worker,
+            rand: XorShift64Star::new(),
+        };
+
+        WorkerThread::set_current(&guest);
+        let _leave = Leave;
+        op(&guest)
+    }
+
     /// Handles panic.
     pub fn handle_panic(&self, err: Box<::std::any::Any + Send>) {
         match self.panic_handler {
@@ -163,19 +301,23 @@ impl Scheduler {
         }
     }
 
-    // pub fn wait_until<T>(&self, latch: &T)
-    // where
-    //     T: LatchWaitProbe,
-    // {
-    //     unsafe {
-    //         let worker_thread = WorkerThread::current();
-    //         if worker_thread.is_null() {
-    //             latch.wait();
-    //         } else {
-    //             (*worker_thread).wait_until(latch);
-    //         }
-    //     }
-    // }
+    /// Blocks current thread until latch is set. Jobs are popped and stolen to keep busy. If
+    /// we are not in a worker thread (e.g. the main thread), the current thread helps the
+    /// default queue as a guest worker, so the jobs it waits for are not starved when all
+    /// the workers are busy.
+    pub fn wait_until<T>(scheduler: &Arc<Self>, latch: &T)
+    where
+        T: LatchWaitProbe,
+    {
+        unsafe {
+            let worker_thread = WorkerThread::current();
+            if worker_thread.is_null() {
+                Scheduler::as_guest(scheduler, |guest| guest.wait_until(latch));
+            } else {
+                (*worker_thread).wait_until(latch);
+            }
+        }
+    }
 
     #[inline]
     pub fn terminate_dec(&self) {
@@ -205,10 +347,17 @@ impl Scheduler {
         }
     }
 
-    unsafe fn main_loop(scheduler: Arc<Scheduler>, index: usize, worker: deque::Worker<JobRef>) {
+    unsafe fn main_loop(
+        scheduler: Arc<Scheduler>,
+        index: usize,
+        queue: usize,
+        worker: deque::Worker<JobRef>,
+    ) {
+        let serves = scheduler.serves(queue);
         let worker_thread = WorkerThread {
             scheduler,
-            index,
+            index: Some(index),
+            serves,
             worker,
             rand: XorShift64Star::new(),
         };
@@ -246,7 +395,9 @@ impl Watcher {
 
 pub struct WorkerThread {
     scheduler: Arc<Scheduler>,
-    index: usize,
+    // The index of worker, it's `None` if this is a guest.
+    index: Option<usize>,
+    serves: Vec<usize>,
     worker: deque::Worker<JobRef>,
     rand: XorShift64Star,
 }
@@ -279,10 +430,15 @@ impl WorkerThread {
 }
 
 impl WorkerThread {
-    /// Pushs a job to `local` queue.
+    /// Pushs a job to `local` queue. Guests inject it instead, since nobody could steal
+    /// from their queues.
     #[inline]
     pub unsafe fn push(&self, job: JobRef) {
-        self.worker.push(job);
+        if self.index.is_some() {
+            self.worker.push(job);
+        } else {
+            self.scheduler.inject(job);
+        }
     }
 
     pub unsafe fn wait_until<L: LatchProbe>(&self, latch: &L) {
@@ -290,12 +446,18 @@ impl WorkerThread {
         let mut ms = 1;
 
         while !latch.is_set() {
-            if let Some(job) = self
-                .steal_local()
-                .or_else(|| self.steal())
-                .or_else(|| self.scheduler.inject_stealer.steal())
-            {
+            if let Some(job) = self.find_work() {
+                let start = Instant::now();
                 job.execute();
+
+                if let Some(index) = self.index {
+                    let info = &self.scheduler.threads[index];
+                    let elapsed = start.elapsed();
+                    let us = elapsed.as_secs() * 1_000_000 + u64::from(elapsed.subsec_micros());
+                    info.jobs.fetch_add(1, Ordering::Relaxed);
+                    info.busy_us.fetch_add(us, Ordering::Relaxed);
+                }
+
                 self.scheduler.watcher.notify_all();
                 ms = 1;
             } else {
@@ -307,6 +469,29 @@ impl WorkerThread {
         mem::forget(abort_guard);
     }
 
+    /// Attempts to find a job from local queue, other workers and the queues this worker
+    /// serves. Jobs in local queues are always spawned into the default queue, so they are
+    /// only stolen by workers serving the default queue.
+    unsafe fn find_work(&self) -> Option<JobRef> {
+        if let Some(job) = self.steal_local() {
+            return Some(job);
+        }
+
+        for &v in &self.serves {
+            if v == 0 {
+                if let Some(job) = self.steal() {
+                    return Some(job);
+                }
+            }
+
+            if let Some(job) = self.scheduler.queues[v].stealer.steal() {
+                return Some(job);
+            }
+        }
+
+        None
+    }
+
     /// Attempts to obtain a "local" job.
     #[inline]
     unsafe fn steal_local(&self) -> Option<JobRef> {
@@ -316,23 +501,27 @@ impl WorkerThread {
     /// Try to steal a single job and return it.
     unsafe fn steal(&self) -> Option<JobRef> {
         let num_threads = self.scheduler.threads.len();
-        if num_threads <= 1 {
+        if num_threads == 0 || (num_threads == 1 && self.index.is_some()) {
             return None;
         }
 
         let start = self.rand.next_usize(num_threads);
         (start..num_threads)
             .chain(0..start)
-            .filter(|&i| i != self.index)
+            .filter(|&i| Some(i) != self.index)
             .filter_map(|i| self.scheduler.threads[i].stealer.steal())
             .next()
     }
 }
 
 struct ThreadInfo {
+    name: String,
+    queue: usize,
     stealer: deque::Stealer<JobRef>,
     primed: LockLatch<()>,
     terminated: LockLatch<()>,
+    jobs: AtomicUsize,
+    busy_us: AtomicU64,
 }
 
 /// [xorshift*] is a fast pseudorandom number generator which will even tolerate
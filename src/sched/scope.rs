@@ -4,6 +4,8 @@ use std::sync::atomic::{AtomicPtr, Ordering};
 use std::sync::Arc;
 use std::{mem, ptr};
 
+use crate::prof::ProfileScope;
+
 use super::job::HeapJob;
 use super::latch::{CountLatch, Latch};
 use super::scheduler::{Scheduler, WorkerThread};
@@ -42,6 +44,7 @@ impl<'s> Scope<'s> {
                 self.latch.increment();
 
                 let job = Box::new(HeapJob::new(move || {
+                    let _s = ProfileScope::new("sched", "scope");
                     let _v = self.execute(func);
                 }))
                 .transmute();
@@ -1,12 +1,60 @@
+use std::future::Future;
 use std::sync::Arc;
 
+use crate::application::ins::Inspectable;
+use crate::application::prelude::{LifecycleListener, LifecycleListenerHandle};
+use crate::prof::ProfileScope;
+
+use super::future::Executor;
+use super::handle::{Dependencies, JobHandle};
 use super::job::HeapJob;
+use super::latch::LatchWaitProbe;
 use super::scheduler::Scheduler;
 use super::scope::Scope;
 use super::unwind;
+use super::{SchedulerParams, DEFAULT_QUEUE};
 
 pub struct SchedulerSystem {
     scheduler: Option<Arc<Scheduler>>,
+    executor: Arc<Executor>,
+    lifecycle: LifecycleListenerHandle,
+}
+
+struct Lifecycle {
+    executor: Arc<Executor>,
+}
+
+impl LifecycleListener for Lifecycle {
+    fn on_pre_update(&mut self) -> crate::errors::Result<()> {
+        self.executor.advance();
+        Ok(())
+    }
+}
+
+struct Inspector {
+    scheduler: Option<Arc<Scheduler>>,
+    executor: Arc<Executor>,
+}
+
+impl Inspectable for Inspector {
+    fn inspect(&self) -> serde_json::Value {
+        let workers = self
+            .scheduler
+            .as_ref()
+            .map(|v| v.workers())
+            .unwrap_or_default();
+
+        json!({
+            "workers": workers,
+            "futures": self.executor.len(),
+        })
+    }
+}
+
+impl Drop for SchedulerSystem {
+    fn drop(&mut self) {
+        crate::application::detach(self.lifecycle);
+    }
 }
 
 /// The type for a panic handling closure. Note that this same closure
@@ -14,18 +62,38 @@ pub struct SchedulerSystem {
 pub type PanicHandler = Fn(Box<::std::any::Any + Send>) + Send + Sync;
 
 impl SchedulerSystem {
-    pub fn new(
-        num: u32,
-        stack_size: Option<usize>,
-        panic_handler: Option<Box<PanicHandler>>,
-    ) -> Self {
+    pub fn new(params: &SchedulerParams, panic_handler: Option<Box<PanicHandler>>) -> Self {
+        SchedulerSystem::with(Some(Scheduler::new(params, panic_handler)))
+    }
+
+    pub fn headless() -> Self {
+        SchedulerSystem::with(None)
+    }
+
+    fn with(scheduler: Option<Arc<Scheduler>>) -> Self {
+        let executor = Arc::new(Executor::new());
+        let lifecycle = crate::application::attach(Lifecycle {
+            executor: executor.clone(),
+        });
+
         SchedulerSystem {
-            scheduler: Some(Scheduler::new(num, stack_size, panic_handler)),
+            scheduler,
+            executor,
+            lifecycle,
         }
     }
 
-    pub fn headless() -> Self {
-        SchedulerSystem { scheduler: None }
+    /// Returns the number of worker threads.
+    #[inline]
+    pub fn num_workers(&self) -> usize {
+        self.scheduler.as_ref().map(|v| v.num_threads()).unwrap_or(0)
+    }
+
+    pub(crate) fn inspectable(&self) -> Arc<impl Inspectable> {
+        Arc::new(Inspector {
+            scheduler: self.scheduler.clone(),
+            executor: self.executor.clone(),
+        })
     }
 
     pub fn terminate(&self) {
@@ -35,26 +103,38 @@ impl SchedulerSystem {
         }
     }
 
-    // /// Blocks current thread until latch is set. Try to keep busy by popping and stealing jobs
-    // /// as necessary.
-    // #[inline]
-    // pub fn wait_until<T>(&self, latch: &T)
-    // where
-    //     T: LatchWaitProbe,
-    // {
-    //     if let Some(ref scheduler) = self.scheduler {
-    //         scheduler.wait_until(latch);
-    //     } else {
-    //         latch.wait();
-    //     }
-    // }
+    /// Blocks current thread until latch is set. Try to keep busy by popping and stealing jobs
+    /// as necessary.
+    #[inline]
+    pub fn wait_until<T>(&self, latch: &T)
+    where
+        T: LatchWaitProbe,
+    {
+        if let Some(ref scheduler) = self.scheduler {
+            Scheduler::wait_until(scheduler, latch);
+        } else {
+            latch.wait();
+        }
+    }
 
     /// Spawn an asynchronous job in the global `Scheduler.`
+    #[inline]
     pub fn spawn<F>(&self, func: F)
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        self.spawn_on(DEFAULT_QUEUE, func);
+    }
+
+    /// Spawn an asynchronous job into the named queue. The default queue is used if there
+    /// is no queue named `queue`.
+    pub fn spawn_on<F>(&self, queue: &str, func: F)
     where
         F: FnOnce() + Send + 'static,
     {
         if let Some(ref scheduler) = self.scheduler {
+            let queue = scheduler.queue(queue).unwrap_or(0);
+
             unsafe {
                 // Ensure that scheduler cannot terminate until this job has executed. This
                 // ref is decremented at the (*) below.
@@ -63,6 +143,7 @@ impl SchedulerSystem {
                 let job = Box::new(HeapJob::new({
                     let sched = scheduler.clone();
                     move || {
+                        let _s = ProfileScope::new("sched", "job");
                         match unwind::halt_unwinding(func) {
                             Ok(()) => {}
                             Err(err) => {
@@ -74,13 +155,63 @@ impl SchedulerSystem {
                     }
                 }));
 
-                scheduler.inject_or_push(HeapJob::transmute(job));
+                scheduler.inject_to(queue, HeapJob::transmute(job));
             }
         } else {
             func();
         }
     }
 
+    /// Spawn an asynchronous job into the named queue, and returns a handle to it.
+    pub fn spawn_on_with_handle<F>(&self, queue: &str, func: F) -> JobHandle
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        let handle = JobHandle::new();
+        let h2 = handle.clone();
+
+        self.spawn_on(queue, move || {
+            let _guard = FinishGuard(h2);
+            func();
+        });
+
+        handle
+    }
+
+    /// Spawn an asynchronous job which will be executed after all the `deps` finished.
+    pub fn spawn_after<F>(&'static self, deps: &[&JobHandle], func: F) -> JobHandle
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        let handle = JobHandle::new();
+        let h2 = handle.clone();
+
+        let kick = Box::new(move || {
+            self.spawn(move || {
+                let _guard = FinishGuard(h2);
+                func();
+            });
+        });
+
+        let counter = Dependencies::new(deps.len(), kick);
+        for v in deps {
+            let counter = counter.clone();
+            v.on_finished(Box::new(move || counter.release()));
+        }
+
+        counter.release();
+        handle
+    }
+
+    /// Spawn a future into the frame-driven executor.
+    #[inline]
+    pub fn spawn_future<F>(&self, future: F) -> JobHandle
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        self.executor.spawn(future)
+    }
+
     /// Create a "fork-join" scope `s` and invokes the closure with a
     /// reference to `s`. This closure can then spawn asynchronous tasks
     /// into `s`. Those tasks may run asynchronously with respect to the
@@ -94,7 +225,7 @@ impl SchedulerSystem {
     {
         unsafe {
             if let Some(ref scheduler) = self.scheduler {
-                scheduler.in_worker(|worker, _| {
+                Scheduler::in_worker(scheduler, |worker, _| {
                     let scope = Scope::new(Some(scheduler.clone()));
 
                     let result = scope.execute(func);
@@ -109,3 +240,12 @@ impl SchedulerSystem {
         }
     }
 }
+
+// Finishes the job even if it panicked, so the dependent jobs would not be blocked forever.
+struct FinishGuard(JobHandle);
+
+impl Drop for FinishGuard {
+    fn drop(&mut self) {
+        self.0.finish();
+    }
+}
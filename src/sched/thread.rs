@@ -0,0 +1,66 @@
+//! Platform specific setups of worker threads.
+
+use super::QueuePriority;
+
+/// Applies the priority and affinity to the calling worker thread, which is the `index`th
+/// worker of `queue`. Failures are logged and ignored, since the workers still function
+/// without them.
+pub fn setup(queue: &str, index: u32, priority: QueuePriority, core: Option<usize>) {
+    let nice = match priority {
+        QueuePriority::Low => 10,
+        QueuePriority::Normal => 0,
+        QueuePriority::High => -5,
+    };
+
+    // Raising the priority fails for unprivileged processes as usual, and it fails in the
+    // same way for all the workers of a queue, so we only report it once.
+    if nice != 0 && !platform::set_nice(nice) && index == 0 {
+        warn!(
+            "[Scheduler] Failed to set the niceness of workers of queue {} to {}.",
+            queue, nice
+        );
+    }
+
+    if let Some(core) = core {
+        if !platform::set_affinity(core) {
+            warn!("[Scheduler] Failed to pin the worker to CPU core {}.", core);
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod platform {
+    use std::mem;
+
+    pub fn set_nice(nice: i32) -> bool {
+        unsafe {
+            // The niceness is a per-thread attribute on Linux.
+            let tid = libc::syscall(libc::SYS_gettid);
+            libc::setpriority(libc::PRIO_PROCESS as _, tid as _, nice) == 0
+        }
+    }
+
+    pub fn set_affinity(core: usize) -> bool {
+        if core >= mem::size_of::<libc::cpu_set_t>() * 8 {
+            return false;
+        }
+
+        unsafe {
+            let mut set: libc::cpu_set_t = mem::zeroed();
+            libc::CPU_ZERO(&mut set);
+            libc::CPU_SET(core, &mut set);
+            libc::sched_setaffinity(0, mem::size_of::<libc::cpu_set_t>(), &set) == 0
+        }
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+mod platform {
+    pub fn set_nice(_: i32) -> bool {
+        true
+    }
+
+    pub fn set_affinity(_: usize) -> bool {
+        false
+    }
+}
@@ -8,6 +8,7 @@ pub mod handle_pool;
 pub mod hash;
 pub mod hash_value;
 pub mod object_pool;
+pub mod random;
 pub mod time;
 
 pub mod prelude {
@@ -18,5 +19,6 @@ pub mod prelude {
     pub use super::hash::{FastHashMap, FastHashSet};
     pub use super::hash_value::HashValue;
     pub use super::object_pool::ObjectPool;
+    pub use super::random::Random;
     pub use super::time::Timestamp;
 }
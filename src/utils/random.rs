@@ -0,0 +1,51 @@
+/// A small and fast pseudorandom number generator based on [xorshift*], which produces
+/// the same sequence with the same seed on all the platforms. It's not cryptographically
+/// secure.
+///
+/// [xorshift*]: https://en.wikipedia.org/wiki/Xorshift#xorshift*
+#[derive(Debug, Clone)]
+pub struct Random {
+    state: u64,
+}
+
+impl Random {
+    /// Creates a generator with `seed`.
+    pub fn new(seed: u64) -> Self {
+        // The state must be non-zero, and a poor seed should still give a good sequence.
+        let mut state = seed ^ 0x9e37_79b9_7f4a_7c15;
+        if state == 0 {
+            state = 0x9e37_79b9_7f4a_7c15;
+        }
+
+        Random { state }
+    }
+
+    /// Returns a random `u64`.
+    pub fn next_u64(&mut self) -> u64 {
+        let mut x = self.state;
+        x ^= x >> 12;
+        x ^= x << 25;
+        x ^= x >> 27;
+        self.state = x;
+        x.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+
+    /// Returns a random `u32`.
+    #[inline]
+    pub fn next_u32(&mut self) -> u32 {
+        (self.next_u64() >> 32) as u32
+    }
+
+    /// Returns a random `f32` in [0, 1).
+    #[inline]
+    pub fn next_f32(&mut self) -> f32 {
+        (self.next_u64() >> 40) as f32 / (1u64 << 24) as f32
+    }
+
+    /// Returns a random number in [min, max).
+    #[inline]
+    pub fn range(&mut self, min: u64, max: u64) -> u64 {
+        assert!(min < max, "the range must not be empty.");
+        min + self.next_u64() % (max - min)
+    }
+}
@@ -10,6 +10,11 @@ impl Timestamp {
         Timestamp(millis)
     }
 
+    #[inline]
+    pub fn as_millis(self) -> u64 {
+        self.0
+    }
+
     #[inline]
     pub fn now() -> Timestamp {
         crate::application::sys::timestamp()
@@ -6,10 +6,12 @@ use crate::math::prelude::{Color, Vector2};
 use crate::video::assets::texture::RenderTextureHandle;
 use crate::video::errors::{Error, Result};
 use crate::video::MAX_FRAMEBUFFER_ATTACHMENTS;
+use crate::window::prelude::WindowHandle;
 
 /// The setup data of `Surface` which wraps common rendering operations to a render-target.
 /// Likes clearing, MSAA resolves, etc.. The `RenderTarget` is the window framebuffer as
 /// default, but you can specify `RenderTarget` with `SurfaceParams::set_attachments`
+/// or the default framebuffer of an additional window with `SurfaceParams::set_window`
 /// manually also.
 #[derive(Debug, Copy, Clone)]
 pub struct SurfaceParams {
@@ -18,6 +20,7 @@ pub struct SurfaceParams {
     pub(crate) clear_color: Option<Color<f32>>,
     pub(crate) clear_depth: Option<f32>,
     pub(crate) clear_stencil: Option<i32>,
+    pub(crate) window: Option<WindowHandle>,
 }
 
 impl Default for SurfaceParams {
@@ -28,6 +31,7 @@ impl Default for SurfaceParams {
             clear_color: Some(Color::black()),
             clear_depth: Some(1.0),
             clear_stencil: None,
+            window: None,
         }
     }
 }
@@ -64,6 +68,13 @@ impl SurfaceParams {
         Ok(())
     }
 
+    /// Sets the additional window whose default framebuffer will be used, if there is no
+    /// attachments assigned. `None` represents the main window.
+    #[inline]
+    pub fn set_window<T: Into<Option<WindowHandle>>>(&mut self, window: T) {
+        self.window = window.into();
+    }
+
     /// Sets the clear flags for this surface.A
     #[inline]
     pub fn set_clear<C, D, S>(&mut self, color: C, depth: D, stentil: S)
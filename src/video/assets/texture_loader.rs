@@ -1,9 +1,13 @@
 use bincode;
+use std::future::Future;
 use std::io::Cursor;
+use std::pin::Pin;
 use std::sync::Arc;
+use std::task::{Context, Poll};
+use uuid::Uuid;
 
 use crate::errors::*;
-use crate::res::utils::prelude::ResourceLoader;
+use crate::res::utils::prelude::{poll_resource, ResourceHandle, ResourceLoader, ResourceState};
 use crate::utils::double_buf::DoubleBuf;
 
 use super::super::backends::frame::{Command, Frame};
@@ -61,4 +65,33 @@ impl ResourceLoader for TextureLoader {
         let cmd = Command::DeleteTexture(handle);
         self.frames.write().cmds.push(cmd);
     }
+
+    fn size(&self, params: &Self::Resource) -> usize {
+        params.format.size(params.dimensions) as usize
+    }
+}
+
+impl ResourceHandle for TextureHandle {
+    #[inline]
+    fn create_from_uuid(uuid: Uuid) -> Result<Self> {
+        crate::video::create_texture_from_uuid(uuid)
+    }
+
+    #[inline]
+    fn state(self) -> ResourceState {
+        crate::video::texture_state(self)
+    }
+
+    #[inline]
+    fn delete(self) {
+        crate::video::delete_texture(self)
+    }
+}
+
+impl Future for TextureHandle {
+    type Output = Result<Self>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        poll_resource(*self, cx)
+    }
 }
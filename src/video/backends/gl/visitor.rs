@@ -8,6 +8,7 @@ use crate::errors::*;
 use crate::math::prelude::{Aabb2, Color, Vector2};
 use crate::utils::hash::{FastHashMap, FastHashSet};
 use crate::utils::hash_value::HashValue;
+use crate::window::prelude::WindowHandle;
 
 use super::super::super::assets::prelude::*;
 use super::super::utils::DataVec;
@@ -110,10 +111,55 @@ struct GLMutableState {
     binded_vao: Option<(ShaderHandle, MeshHandle)>,
     binded_texture_index: usize,
     binded_textures: SmallVec<[Option<Sampler>; 8]>,
+    // The `VertexArrayObject`s which should be deleted once this context becomes current.
+    garbage_vaos: Vec<GLuint>,
+}
+
+impl GLMutableState {
+    fn new() -> Self {
+        GLMutableState {
+            render_state: RenderState::default(),
+            scissor: SurfaceScissor::Disable,
+            view: SurfaceViewport {
+                position: Vector2::new(0, 0),
+                size: Vector2::new(0, 0),
+            },
+            cleared_surfaces: FastHashSet::default(),
+            vaos: FastHashMap::default(),
+            binded_surface: None,
+            binded_shader: None,
+            binded_vao: None,
+            binded_texture_index: 0,
+            binded_textures: SmallVec::new(),
+            garbage_vaos: Vec::new(),
+        }
+    }
+
+    // Moves the `VertexArrayObject`s matching the predicate into garbages, since they could
+    // only be deleted when this context is current.
+    fn discard_vaos<P>(&mut self, predicate: P)
+    where
+        P: Fn(ShaderHandle, MeshHandle) -> bool,
+    {
+        let garbage_vaos = &mut self.garbage_vaos;
+        self.vaos.retain(|&(shader, mesh), vao| {
+            if predicate(shader, mesh) {
+                garbage_vaos.push(*vao);
+                false
+            } else {
+                true
+            }
+        });
+    }
 }
 
 pub struct GLVisitor {
+    // The mutable state of current context, the others are stored in `contexts`. Objects
+    // like textures, buffers and programs are shared between the contexts of windows, but
+    // framebuffers and vertex array objects are not.
     state: GLMutableState,
+    current: Option<WindowHandle>,
+    contexts: FastHashMap<Option<WindowHandle>, GLMutableState>,
     capabilities: Capabilities,
     surfaces: DataVec<GLSurfaceData>,
     shaders: DataVec<GLShaderData>,
@@ -128,24 +174,10 @@ impl GLVisitor {
         info!("GLVisitor {:#?}", capabilities);
         check_capabilities(&capabilities)?;
 
-        let state = GLMutableState {
-            render_state: RenderState::default(),
-            scissor: SurfaceScissor::Disable,
-            view: SurfaceViewport {
-                position: Vector2::new(0, 0),
-                size: Vector2::new(0, 0),
-            },
-            cleared_surfaces: FastHashSet::default(),
-            vaos: FastHashMap::default(),
-            binded_surface: None,
-            binded_shader: None,
-            binded_vao: None,
-            binded_texture_index: 0,
-            binded_textures: SmallVec::new(),
-        };
-
         let mut visitor = GLVisitor {
-            state,
+            state: GLMutableState::new(),
+            current: None,
+            contexts: FastHashMap::default(),
             capabilities,
             surfaces: DataVec::new(),
             shaders: DataVec::new(),
@@ -157,12 +189,51 @@ impl GLVisitor {
         Self::reset_render_state(&mut visitor.state)?;
         Ok(visitor)
     }
+
+    /// Makes the context of window current, and swaps in its mutable state.
+    unsafe fn switch_context(&mut self, window: Option<WindowHandle>) -> Result<()> {
+        if self.current == window {
+            return Ok(());
+        }
+
+        crate::window::inside::make_current(window)?;
+
+        let (mut state, fresh) = match self.contexts.remove(&window) {
+            Some(state) => (state, false),
+            None => (GLMutableState::new(), true),
+        };
+
+        ::std::mem::swap(&mut self.state, &mut state);
+        self.contexts.insert(self.current, state);
+        self.current = window;
+
+        if fresh {
+            Self::reset_render_state(&mut self.state)?;
+        }
+
+        for vao in self.state.garbage_vaos.drain(..) {
+            gl::DeleteVertexArrays(1, &vao);
+        }
+
+        check()
+    }
 }
 
 impl Visitor for GLVisitor {
     unsafe fn advance(&mut self) -> Result<()> {
+        self.switch_context(None)?;
         self.state.cleared_surfaces.clear();
         self.state.binded_surface = None;
+
+        // The states of deleted windows are dropped along with their contexts.
+        self.contexts
+            .retain(|k, _| k.map(crate::window::alive).unwrap_or(true));
+
+        for v in self.contexts.values_mut() {
+            v.cleared_surfaces.clear();
+            v.binded_surface = None;
+        }
+
         Ok(())
     }
 
@@ -171,6 +242,10 @@ impl Visitor for GLVisitor {
         handle: SurfaceHandle,
         params: SurfaceParams,
     ) -> Result<()> {
+        // Framebuffers are not shared between contexts, so we always create them in the
+        // context of main window.
+        self.switch_context(None)?;
+
         let mut data = GLSurfaceData {
             handle,
             params,
@@ -286,7 +361,14 @@ impl Visitor for GLVisitor {
             self.state.binded_surface = None;
         }
 
+        for v in self.contexts.values_mut() {
+            if v.binded_surface == Some(handle) {
+                v.binded_surface = None;
+            }
+        }
+
         if let Some(id) = surface.id {
+            self.switch_context(None)?;
             gl::DeleteFramebuffers(1, &id);
             check()?;
         }
@@ -360,6 +442,13 @@ impl Visitor for GLVisitor {
             self.state.binded_shader = None;
         }
 
+        for v in self.contexts.values_mut() {
+            v.discard_vaos(|h, _| h == shader.handle);
+            if v.binded_shader == Some(handle) {
+                v.binded_shader = None;
+            }
+        }
+
         gl::DeleteProgram(shader.id);
         check()
     }
@@ -704,24 +793,47 @@ impl Visitor for GLVisitor {
             }
         });
 
+        for v in self.contexts.values_mut() {
+            v.discard_vaos(|_, h| h == mesh.handle);
+        }
+
         gl::DeleteBuffers(1, &mesh.vbo);
         gl::DeleteBuffers(1, &mesh.ibo);
         check()
     }
 
     unsafe fn bind(&mut self, handle: SurfaceHandle, dimensions: Vector2<u32>) -> Result<()> {
+        let (window, id, dimensions) = {
+            let surface = self
+                .surfaces
+                .get(handle)
+                .ok_or_else(|| format_err!("{:?} is invalid.", handle))?;
+
+            match (surface.id, surface.params.window) {
+                (Some(id), _) => (None, id, surface.dimensions.unwrap_or(dimensions)),
+                (None, Some(window)) => {
+                    let dims = crate::window::window_dimensions(window)
+                        .ok_or_else(|| format_err!("Window {:?} has been deleted.", window))?;
+                    let dpr = crate::window::window_device_pixel_ratio(window).unwrap_or(1.0);
+                    let dims = Vector2::new(
+                        (dims.x as f32 * dpr) as u32,
+                        (dims.y as f32 * dpr) as u32,
+                    );
+
+                    (Some(window), 0, dims)
+                }
+                (None, None) => (None, 0, dimensions),
+            }
+        };
+
+        self.switch_context(window)?;
         if self.state.binded_surface == Some(handle) {
             return Ok(());
         }
 
-        let surface = self
-            .surfaces
-            .get(handle)
-            .ok_or_else(|| format_err!("{:?} is invalid.", handle))?;
+        let surface = self.surfaces.get(handle).unwrap();
 
         // Bind frame buffer.
-        let id = surface.id.unwrap_or(0);
-        let dimensions = surface.dimensions.unwrap_or(dimensions);
         gl::BindFramebuffer(gl::FRAMEBUFFER, id);
 
         // Reset the viewport and scissor box.
@@ -885,6 +997,7 @@ impl Visitor for GLVisitor {
     }
 
     unsafe fn flush(&mut self) -> Result<()> {
+        self.switch_context(None)?;
         if self.state.cleared_surfaces.is_empty() {
             Self::clear(Color::black(), None, None)?;
         }
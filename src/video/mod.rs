@@ -212,7 +212,7 @@ use uuid::Uuid;
 
 use crate::math::prelude::Aabb2;
 use crate::prelude::CrResult;
-use crate::res::utils::prelude::ResourceState;
+use crate::res::utils::prelude::{ResourcePoolStats, ResourceState};
 
 use self::assets::prelude::*;
 use self::errors::*;
@@ -322,6 +322,25 @@ pub fn delete_mesh(handle: MeshHandle) {
     ctx().delete_mesh(handle);
 }
 
+/// Marks the mesh as cached, which allows it to be evicted when the memory usage of
+/// meshes exceeds the budget. It will be reloaded transparently when accessed again.
+#[inline]
+pub fn set_mesh_cached(handle: MeshHandle, cached: bool) -> CrResult<()> {
+    ctx().set_mesh_cached(handle, cached)
+}
+
+/// Sets the memory budget in bytes of meshes.
+#[inline]
+pub fn set_mesh_budget(budget: Option<usize>) {
+    ctx().set_mesh_budget(budget);
+}
+
+/// Gets the statistics of meshes.
+#[inline]
+pub fn mesh_stats() -> ResourcePoolStats {
+    ctx().mesh_stats()
+}
+
 /// Create texture object. A texture is an image loaded in video memory,
 /// which can be sampled in shaders.
 #[inline]
@@ -362,6 +381,25 @@ pub fn delete_texture(handle: TextureHandle) {
     ctx().delete_texture(handle);
 }
 
+/// Marks the texture as cached, which allows it to be evicted when the memory usage of
+/// textures exceeds the budget. It will be reloaded transparently when accessed again.
+#[inline]
+pub fn set_texture_cached(handle: TextureHandle, cached: bool) -> CrResult<()> {
+    ctx().set_texture_cached(handle, cached)
+}
+
+/// Sets the memory budget in bytes of textures.
+#[inline]
+pub fn set_texture_budget(budget: Option<usize>) {
+    ctx().set_texture_budget(budget);
+}
+
+/// Gets the statistics of textures.
+#[inline]
+pub fn texture_stats() -> ResourcePoolStats {
+    ctx().texture_stats()
+}
+
 /// Create render texture object, which could be attached with a framebuffer.
 #[inline]
 pub fn create_render_texture(params: RenderTextureParams) -> Result<RenderTextureHandle> {
@@ -1,10 +1,11 @@
 use std::sync::{Arc, RwLock};
 use uuid::Uuid;
 
+use crate::application::ins::Inspectable;
 use crate::application::prelude::{LifecycleListener, LifecycleListenerHandle};
 use crate::math::prelude::{Aabb2, Vector2};
 use crate::prelude::CrResult;
-use crate::res::utils::prelude::{ResourcePool, ResourceState};
+use crate::res::utils::prelude::{ResourcePool, ResourcePoolStats, ResourceState};
 use crate::utils::prelude::{DoubleBuf, ObjectPool};
 
 use super::assets::mesh_loader::MeshLoader;
@@ -45,6 +46,52 @@ impl VideoState {
             frames,
         }
     }
+
+    /// Marks the meshes and textures referenced by the frame as recently used, so the
+    /// resources being rendered would never be evicted from pools.
+    fn touch(&self, frame: &Frame) {
+        let meshes = self.meshes.read().unwrap();
+        let textures = self.textures.read().unwrap();
+
+        for v in &frame.cmds {
+            match *v {
+                Command::Draw(_, mesh, _, ptr) => {
+                    meshes.touch(mesh);
+
+                    for &(_, variable) in frame.bufs.as_slice(ptr) {
+                        if let UniformVariable::Texture(texture) = variable {
+                            textures.touch(texture);
+                        }
+                    }
+                }
+                Command::UpdateTexture(texture, _, _) => textures.touch(texture),
+                Command::UpdateVertexBuffer(mesh, _, _) => meshes.touch(mesh),
+                Command::UpdateIndexBuffer(mesh, _, _) => meshes.touch(mesh),
+                _ => {}
+            }
+        }
+    }
+}
+
+impl Inspectable for VideoState {
+    fn inspect(&self) -> serde_json::Value {
+        let meshes = self.meshes.read().unwrap();
+        let textures = self.textures.read().unwrap();
+
+        json!({
+            "surfaces": self.surfaces.read().unwrap().len(),
+            "shaders": self.shaders.read().unwrap().len(),
+            "meshes": {
+                "stats": meshes.stats(),
+                "resources": meshes.infos(),
+            },
+            "textures": {
+                "stats": textures.stats(),
+                "resources": textures.infos(),
+            },
+            "render_textures": self.render_textures.read().unwrap().len(),
+        })
+    }
 }
 
 struct Lifecycle {
@@ -58,6 +105,9 @@ impl LifecycleListener for Lifecycle {
         // Swap internal commands frame.
         self.state.frames.swap();
         self.state.frames.write().clear();
+
+        // The commands in back buffer will be dispatched at the end of this frame.
+        self.state.touch(&self.state.frames.read_back_buf());
         self.state.meshes.write().unwrap().advance()?;
         self.state.textures.write().unwrap().advance()?;
         Ok(())
@@ -121,6 +171,10 @@ impl VideoSystem {
     pub(crate) fn frames(&self) -> Arc<DoubleBuf<Frame>> {
         self.state.frames.clone()
     }
+
+    pub(crate) fn inspectable(&self) -> Arc<impl Inspectable> {
+        self.state.clone()
+    }
 }
 
 impl VideoSystem {
@@ -291,6 +345,25 @@ impl VideoSystem {
     pub fn delete_mesh(&self, handle: MeshHandle) {
         self.state.meshes.write().unwrap().delete(handle);
     }
+
+    /// Marks the mesh as cached, which allows it to be evicted when the memory usage of
+    /// meshes exceeds the budget. It will be reloaded transparently when accessed again.
+    #[inline]
+    pub fn set_mesh_cached(&self, handle: MeshHandle, cached: bool) -> CrResult<()> {
+        self.state.meshes.write().unwrap().set_cached(handle, cached)
+    }
+
+    /// Sets the memory budget in bytes of meshes.
+    #[inline]
+    pub fn set_mesh_budget(&self, budget: Option<usize>) {
+        self.state.meshes.write().unwrap().set_budget(budget);
+    }
+
+    /// Gets the statistics of meshes.
+    #[inline]
+    pub fn mesh_stats(&self) -> ResourcePoolStats {
+        self.state.meshes.read().unwrap().stats()
+    }
 }
 
 impl VideoSystem {
@@ -345,6 +418,25 @@ impl VideoSystem {
     pub fn delete_texture(&self, handle: TextureHandle) {
         self.state.textures.write().unwrap().delete(handle);
     }
+
+    /// Marks the texture as cached, which allows it to be evicted when the memory usage of
+    /// textures exceeds the budget. It will be reloaded transparently when accessed again.
+    #[inline]
+    pub fn set_texture_cached(&self, handle: TextureHandle, cached: bool) -> CrResult<()> {
+        self.state.textures.write().unwrap().set_cached(handle, cached)
+    }
+
+    /// Sets the memory budget in bytes of textures.
+    #[inline]
+    pub fn set_texture_budget(&self, budget: Option<usize>) {
+        self.state.textures.write().unwrap().set_budget(budget);
+    }
+
+    /// Gets the statistics of textures.
+    #[inline]
+    pub fn texture_stats(&self) -> ResourcePoolStats {
+        self.state.textures.read().unwrap().stats()
+    }
 }
 
 impl VideoSystem {
@@ -1,6 +1,7 @@
 use glutin;
 
 use super::super::super::events::{Event, WindowEvent};
+use super::super::super::CursorIcon;
 
 use crate::input::events::InputEvent;
 use crate::input::keyboard::Key;
@@ -21,10 +22,36 @@ pub fn from_event(source: glutin::Event, dimensions: Vector2<u32>) -> Option<Eve
             Some(Event::Window(WindowEvent::Resumed))
         },
 
+        glutin::Event::DeviceEvent {
+            event: glutin::DeviceEvent::MouseMotion { delta },
+            ..
+        } => Some(Event::InputDevice(InputEvent::MouseMotion {
+            delta: (delta.0 as f32, -delta.1 as f32),
+        })),
+
         glutin::Event::DeviceEvent { .. } => None,
     }
 }
 
+pub fn to_mouse_cursor(cursor: CursorIcon) -> glutin::MouseCursor {
+    match cursor {
+        CursorIcon::Default => glutin::MouseCursor::Default,
+        CursorIcon::Crosshair => glutin::MouseCursor::Crosshair,
+        CursorIcon::Hand => glutin::MouseCursor::Hand,
+        CursorIcon::Arrow => glutin::MouseCursor::Arrow,
+        CursorIcon::Move => glutin::MouseCursor::Move,
+        CursorIcon::Text => glutin::MouseCursor::Text,
+        CursorIcon::Wait => glutin::MouseCursor::Wait,
+        CursorIcon::Help => glutin::MouseCursor::Help,
+        CursorIcon::Progress => glutin::MouseCursor::Progress,
+        CursorIcon::NotAllowed => glutin::MouseCursor::NotAllowed,
+        CursorIcon::EwResize => glutin::MouseCursor::EwResize,
+        CursorIcon::NsResize => glutin::MouseCursor::NsResize,
+        CursorIcon::NeswResize => glutin::MouseCursor::NeswResize,
+        CursorIcon::NwseResize => glutin::MouseCursor::NwseResize,
+    }
+}
+
 fn from_window_event(source: &glutin::WindowEvent, dimensions: Vector2<u32>) -> Option<Event> {
     match *source {
         glutin::WindowEvent::CloseRequested => Some(Event::Window(WindowEvent::Closed)),
@@ -1,49 +1,89 @@
+use std::sync::Mutex;
+
+use clipboard::{ClipboardContext, ClipboardProvider};
 use gl;
 use glutin;
 use glutin::GlContext;
 
 use crate::errors::*;
 use crate::math::prelude::Vector2;
+use crate::utils::hash::FastHashMap;
 
-use super::super::super::events::Event;
-use super::super::super::WindowParams;
+use super::super::super::events::{Event, WindowEvent};
+use super::super::super::system::WindowHandle;
+use super::super::super::{CursorIcon, WindowMode, WindowParams};
 use super::super::Visitor;
 use super::types;
 
+// The position and size of window in windowed mode.
+type Geometry = (glutin::dpi::LogicalPosition, glutin::dpi::LogicalSize);
+
 pub struct GlutinVisitor {
     window: glutin::GlWindow,
+    windows: FastHashMap<WindowHandle, glutin::GlWindow>,
     events_loop: glutin::EventsLoop,
+    clipboard: Mutex<Option<ClipboardContext>>,
+    geometries: Mutex<FastHashMap<Option<WindowHandle>, Geometry>>,
+}
+
+fn build(
+    params: WindowParams,
+    events_loop: &glutin::EventsLoop,
+    shared: Option<&glutin::GlWindow>,
+) -> Result<glutin::GlWindow> {
+    let builder = glutin::WindowBuilder::new()
+        .with_title(params.title)
+        .with_dimensions(glutin::dpi::LogicalSize::new(
+            f64::from(params.size.x),
+            f64::from(params.size.y),
+        ))
+        .with_multitouch();
+
+    let mut context = glutin::ContextBuilder::new()
+        .with_multisampling(params.multisample as u16)
+        .with_gl_profile(glutin::GlProfile::Core)
+        .with_gl(glutin::GlRequest::Latest)
+        .with_vsync(params.vsync);
+
+    // Shares the textures, buffers and programs with the main window.
+    if let Some(shared) = shared {
+        context = context.with_shared_lists(shared.context());
+    }
+
+    glutin::GlWindow::new(builder, context, events_loop)
+        .map_err(|err| format_err!("Failed to create window: {}", err))
+}
+
+// Resizes the context to the physical size of window.
+fn resize_to_fit(window: &glutin::GlWindow) {
+    if let Some(size) = window.get_inner_size() {
+        window.resize(size.to_physical(window.get_hidpi_factor()));
+    }
 }
 
 impl GlutinVisitor {
     pub fn from(params: WindowParams) -> Result<Self> {
-        let builder = glutin::WindowBuilder::new()
-            .with_title(params.title)
-            .with_dimensions(glutin::dpi::LogicalSize::new(
-                f64::from(params.size.x),
-                f64::from(params.size.y),
-            ))
-            .with_multitouch();
-
-        let context = glutin::ContextBuilder::new()
-            .with_multisampling(params.multisample as u16)
-            .with_gl_profile(glutin::GlProfile::Core)
-            .with_gl(glutin::GlRequest::Latest)
-            .with_vsync(params.vsync);
-
+        let mode = params.mode;
         let events_loop = glutin::EventsLoop::new();
-        let window = glutin::GlWindow::new(builder, context, &events_loop).unwrap();
+        let window = build(params, &events_loop, None)?;
         let mut visitor = GlutinVisitor {
             window,
+            windows: FastHashMap::default(),
             events_loop,
+            clipboard: Mutex::new(None),
+            geometries: Mutex::new(FastHashMap::default()),
         };
 
-        let size = visitor.dimensions();
-        let dpr = visitor.device_pixel_ratio();
+        if mode != WindowMode::Windowed {
+            visitor.set_mode(None, mode)?;
+        }
+
+        let size = visitor.dimensions(None).unwrap();
+        let dpr = visitor.device_pixel_ratio(None).unwrap();
         let dims = Vector2::new((size.x as f32 * dpr) as u32, (size.y as f32 * dpr) as u32);
 
         visitor.events_loop.poll_events(|_| {});
-        visitor.resize(dims);
+        visitor.resize(None, dims);
 
         unsafe {
             visitor.window.make_current()?;
@@ -52,68 +92,301 @@ impl GlutinVisitor {
 
         Ok(visitor)
     }
+
+    #[inline]
+    fn window(&self, handle: Option<WindowHandle>) -> Option<&glutin::GlWindow> {
+        match handle {
+            Some(handle) => self.windows.get(&handle),
+            None => Some(&self.window),
+        }
+    }
 }
 
 impl Visitor for GlutinVisitor {
-    #[inline]
-    fn show(&self) {
-        self.window.show();
+    fn create_window(&mut self, handle: WindowHandle, params: WindowParams) -> Result<()> {
+        let mode = params.mode;
+        let window = build(params, &self.events_loop, Some(&self.window))?;
+
+        // Creating a context might change the current one.
+        unsafe {
+            self.window.make_current()?;
+        }
+
+        self.windows.insert(handle, window);
+        if mode != WindowMode::Windowed {
+            self.set_mode(Some(handle), mode)?;
+        }
+
+        resize_to_fit(&self.windows[&handle]);
+        Ok(())
+    }
+
+    fn delete_window(&mut self, handle: WindowHandle) {
+        self.geometries.lock().unwrap().remove(&Some(handle));
+
+        if let Some(window) = self.windows.remove(&handle) {
+            let current = window.is_current();
+            drop(window);
+
+            if current {
+                unsafe {
+                    let _ = self.window.make_current();
+                }
+            }
+        }
     }
 
     #[inline]
-    fn hide(&self) {
-        self.window.hide();
+    fn show(&self, handle: Option<WindowHandle>) {
+        if let Some(window) = self.window(handle) {
+            window.show();
+        }
     }
 
     #[inline]
-    fn position(&self) -> Vector2<i32> {
-        let pos = self.window.get_position().unwrap();
-        Vector2::new(pos.x as i32, pos.y as i32)
+    fn hide(&self, handle: Option<WindowHandle>) {
+        if let Some(window) = self.window(handle) {
+            window.hide();
+        }
     }
 
     #[inline]
-    fn dimensions(&self) -> Vector2<u32> {
-        let size = self.window.get_inner_size().unwrap();
-        Vector2::new(size.width as u32, size.height as u32)
+    fn position(&self, handle: Option<WindowHandle>) -> Option<Vector2<i32>> {
+        let pos = self.window(handle)?.get_position()?;
+        Some(Vector2::new(pos.x as i32, pos.y as i32))
     }
 
     #[inline]
-    fn device_pixel_ratio(&self) -> f32 {
-        self.window.get_hidpi_factor() as f32
+    fn dimensions(&self, handle: Option<WindowHandle>) -> Option<Vector2<u32>> {
+        let size = self.window(handle)?.get_inner_size()?;
+        Some(Vector2::new(size.width as u32, size.height as u32))
     }
 
     #[inline]
-    fn resize(&self, dimensions: Vector2<u32>) {
-        let size = glutin::dpi::PhysicalSize::new(f64::from(dimensions.x), f64::from(dimensions.y));
-        self.window.resize(size)
+    fn device_pixel_ratio(&self, handle: Option<WindowHandle>) -> Option<f32> {
+        Some(self.window(handle)?.get_hidpi_factor() as f32)
     }
 
     #[inline]
-    fn poll_events(&mut self, events: &mut Vec<Event>) {
-        let dims = self.dimensions();
+    fn resize(&self, handle: Option<WindowHandle>, dimensions: Vector2<u32>) {
+        if let Some(window) = self.window(handle) {
+            let size =
+                glutin::dpi::PhysicalSize::new(f64::from(dimensions.x), f64::from(dimensions.y));
+            window.resize(size)
+        }
+    }
+
+    fn poll_events(&mut self, events: &mut Vec<(Option<WindowHandle>, Event)>) {
+        let zero = Vector2::new(0, 0);
+        let mut windows = Vec::with_capacity(self.windows.len() + 1);
+        windows.push((self.window.id(), None, self.dimensions(None).unwrap_or(zero)));
+
+        for (&handle, v) in &self.windows {
+            let dims = self.dimensions(Some(handle)).unwrap_or(zero);
+            windows.push((v.id(), Some(handle), dims));
+        }
+
         self.events_loop.poll_events(|v| {
+            let (handle, dims) = match v {
+                glutin::Event::WindowEvent { window_id, .. } => {
+                    match windows.iter().find(|w| w.0 == window_id) {
+                        Some(w) => (w.1, w.2),
+                        None => return,
+                    }
+                }
+                _ => (None, windows[0].2),
+            };
+
             if let Some(e) = types::from_event(v, dims) {
-                events.push(e);
+                events.push((handle, e));
             }
         });
+
+        // The main window is resized by the video system, while the additional ones are
+        // resized here.
+        for &(handle, ref e) in events.iter() {
+            if let (Some(handle), Event::Window(WindowEvent::Resized(_, _))) = (handle, e) {
+                if let Some(window) = self.windows.get(&handle) {
+                    resize_to_fit(window);
+                }
+            }
+        }
     }
 
     #[inline]
-    fn is_current(&self) -> bool {
-        self.window.is_current()
+    fn is_current(&self, handle: Option<WindowHandle>) -> bool {
+        self.window(handle).map(|v| v.is_current()).unwrap_or(false)
     }
 
     #[inline]
-    fn make_current(&self) -> Result<()> {
+    fn make_current(&self, handle: Option<WindowHandle>) -> Result<()> {
+        let window = self
+            .window(handle)
+            .ok_or_else(|| format_err!("{:?} is invalid.", handle))?;
+
         unsafe {
-            self.window.make_current()?;
+            if !window.is_current() {
+                window.make_current()?;
+            }
+
             Ok(())
         }
     }
 
     #[inline]
-    fn swap_buffers(&self) -> Result<()> {
-        self.window.swap_buffers()?;
+    fn swap_buffers(&self, handle: Option<WindowHandle>) -> Result<()> {
+        let window = self
+            .window(handle)
+            .ok_or_else(|| format_err!("{:?} is invalid.", handle))?;
+
+        window.swap_buffers()?;
+        Ok(())
+    }
+
+    fn set_mode(&self, handle: Option<WindowHandle>, mode: WindowMode) -> Result<()> {
+        let window = self
+            .window(handle)
+            .ok_or_else(|| format_err!("{:?} is invalid.", handle))?;
+
+        let mut geometries = self.geometries.lock().unwrap();
+        match mode {
+            WindowMode::Windowed => {
+                window.set_fullscreen(None);
+                window.set_decorations(true);
+
+                // Restores the geometry before entering borderless mode.
+                if let Some((position, size)) = geometries.remove(&handle) {
+                    window.set_position(position);
+                    window.set_inner_size(size);
+                }
+            }
+            WindowMode::Fullscreen => {
+                window.set_fullscreen(Some(window.get_current_monitor()));
+            }
+            WindowMode::Borderless => {
+                if !geometries.contains_key(&handle) {
+                    if let (Some(position), Some(size)) =
+                        (window.get_position(), window.get_inner_size())
+                    {
+                        geometries.insert(handle, (position, size));
+                    }
+                }
+
+                let monitor = window.get_current_monitor();
+                let dpr = monitor.get_hidpi_factor();
+
+                window.set_fullscreen(None);
+                window.set_decorations(false);
+                window.set_position(monitor.get_position().to_logical(dpr));
+                window.set_inner_size(monitor.get_dimensions().to_logical(dpr));
+            }
+        }
+
+        Ok(())
+    }
+
+    #[inline]
+    fn set_size(&self, handle: Option<WindowHandle>, size: Vector2<u32>) {
+        if let Some(window) = self.window(handle) {
+            let size = glutin::dpi::LogicalSize::new(f64::from(size.x), f64::from(size.y));
+            window.set_inner_size(size);
+        }
+    }
+
+    #[inline]
+    fn set_title(&self, handle: Option<WindowHandle>, title: &str) {
+        if let Some(window) = self.window(handle) {
+            window.set_title(title);
+        }
+    }
+
+    fn set_icon(
+        &self,
+        handle: Option<WindowHandle>,
+        icon: Option<(Vector2<u32>, Vec<u8>)>,
+    ) -> Result<()> {
+        let window = self
+            .window(handle)
+            .ok_or_else(|| format_err!("{:?} is invalid.", handle))?;
+
+        let icon = match icon {
+            Some((dims, rgba)) => Some(
+                glutin::Icon::from_rgba(rgba, dims.x, dims.y)
+                    .map_err(|err| format_err!("Failed to create window icon: {}", err))?,
+            ),
+            None => None,
+        };
+
+        window.set_window_icon(icon);
+        Ok(())
+    }
+
+    #[inline]
+    fn set_cursor(&self, handle: Option<WindowHandle>, cursor: CursorIcon) {
+        if let Some(window) = self.window(handle) {
+            window.set_cursor(types::to_mouse_cursor(cursor));
+        }
+    }
+
+    #[inline]
+    fn set_cursor_visible(&self, handle: Option<WindowHandle>, visible: bool) {
+        if let Some(window) = self.window(handle) {
+            window.hide_cursor(!visible);
+        }
+    }
+
+    fn set_cursor_grab(&self, handle: Option<WindowHandle>, grab: bool) -> Result<()> {
+        let window = self
+            .window(handle)
+            .ok_or_else(|| format_err!("{:?} is invalid.", handle))?;
+
+        window
+            .grab_cursor(grab)
+            .map_err(|err| format_err!("Failed to grab cursor: {}", err))?;
+
+        Ok(())
+    }
+
+    fn set_ime_position(&self, handle: Option<WindowHandle>, position: Vector2<f32>) {
+        if let Some(window) = self.window(handle) {
+            // Converts into the coordinates relative to the top-left corner.
+            let height = window.get_inner_size().map(|v| v.height).unwrap_or(0.0);
+            window.set_ime_spot(glutin::dpi::LogicalPosition::new(
+                f64::from(position.x),
+                height - f64::from(position.y),
+            ));
+        }
+    }
+
+    #[inline]
+    fn ime_composition_supported(&self) -> bool {
+        // The preedit text is drawn by the input method itself, glutin never reports it.
+        false
+    }
+
+    fn clipboard_text(&self) -> Option<String> {
+        let mut clipboard = self.clipboard.lock().unwrap();
+        if clipboard.is_none() {
+            *clipboard = ClipboardContext::new().ok();
+        }
+
+        clipboard.as_mut().and_then(|v| v.get_contents().ok())
+    }
+
+    fn set_clipboard_text(&self, text: &str) -> Result<()> {
+        let mut clipboard = self.clipboard.lock().unwrap();
+        if clipboard.is_none() {
+            let v = ClipboardContext::new()
+                .map_err(|err| format_err!("Failed to open clipboard: {}", err))?;
+            *clipboard = Some(v);
+        }
+
+        clipboard
+            .as_mut()
+            .unwrap()
+            .set_contents(text.to_owned())
+            .map_err(|err| format_err!("Failed to set clipboard: {}", err))?;
+
         Ok(())
     }
 }
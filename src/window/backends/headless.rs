@@ -1,51 +1,120 @@
+use std::sync::Mutex;
+
 use crate::errors::*;
 use crate::math::prelude::Vector2;
 
 use super::super::events::Event;
+use super::super::system::WindowHandle;
+use super::super::{CursorIcon, WindowMode, WindowParams};
 use super::Visitor;
 
-pub struct HeadlessVisitor {}
+pub struct HeadlessVisitor {
+    // The clipboard is emulated in memory.
+    clipboard: Mutex<Option<String>>,
+}
+
+impl HeadlessVisitor {
+    pub fn new() -> Self {
+        HeadlessVisitor {
+            clipboard: Mutex::new(None),
+        }
+    }
+}
 
 impl Visitor for HeadlessVisitor {
     #[inline]
-    fn show(&self) {}
+    fn create_window(&mut self, _: WindowHandle, _: WindowParams) -> Result<()> {
+        Ok(())
+    }
+
+    #[inline]
+    fn delete_window(&mut self, _: WindowHandle) {}
+
+    #[inline]
+    fn show(&self, _: Option<WindowHandle>) {}
 
     #[inline]
-    fn hide(&self) {}
+    fn hide(&self, _: Option<WindowHandle>) {}
 
     #[inline]
-    fn position(&self) -> Vector2<i32> {
-        (0, 0).into()
+    fn position(&self, _: Option<WindowHandle>) -> Option<Vector2<i32>> {
+        Some((0, 0).into())
     }
 
     #[inline]
-    fn dimensions(&self) -> Vector2<u32> {
-        (0, 0).into()
+    fn dimensions(&self, _: Option<WindowHandle>) -> Option<Vector2<u32>> {
+        Some((0, 0).into())
     }
 
     #[inline]
-    fn device_pixel_ratio(&self) -> f32 {
-        1.0
+    fn device_pixel_ratio(&self, _: Option<WindowHandle>) -> Option<f32> {
+        Some(1.0)
     }
 
     #[inline]
-    fn resize(&self, _: Vector2<u32>) {}
+    fn resize(&self, _: Option<WindowHandle>, _: Vector2<u32>) {}
 
     #[inline]
-    fn poll_events(&mut self, _: &mut Vec<Event>) {}
+    fn poll_events(&mut self, _: &mut Vec<(Option<WindowHandle>, Event)>) {}
 
     #[inline]
-    fn is_current(&self) -> bool {
+    fn is_current(&self, _: Option<WindowHandle>) -> bool {
         true
     }
 
     #[inline]
-    fn make_current(&self) -> Result<()> {
+    fn make_current(&self, _: Option<WindowHandle>) -> Result<()> {
         Ok(())
     }
 
     #[inline]
-    fn swap_buffers(&self) -> Result<()> {
+    fn swap_buffers(&self, _: Option<WindowHandle>) -> Result<()> {
+        Ok(())
+    }
+
+    #[inline]
+    fn set_mode(&self, _: Option<WindowHandle>, _: WindowMode) -> Result<()> {
+        Ok(())
+    }
+
+    #[inline]
+    fn set_size(&self, _: Option<WindowHandle>, _: Vector2<u32>) {}
+
+    #[inline]
+    fn set_title(&self, _: Option<WindowHandle>, _: &str) {}
+
+    #[inline]
+    fn set_icon(&self, _: Option<WindowHandle>, _: Option<(Vector2<u32>, Vec<u8>)>) -> Result<()> {
+        Ok(())
+    }
+
+    #[inline]
+    fn set_cursor(&self, _: Option<WindowHandle>, _: CursorIcon) {}
+
+    #[inline]
+    fn set_cursor_visible(&self, _: Option<WindowHandle>, _: bool) {}
+
+    #[inline]
+    fn set_cursor_grab(&self, _: Option<WindowHandle>, _: bool) -> Result<()> {
+        Ok(())
+    }
+
+    #[inline]
+    fn set_ime_position(&self, _: Option<WindowHandle>, _: Vector2<f32>) {}
+
+    #[inline]
+    fn ime_composition_supported(&self) -> bool {
+        false
+    }
+
+    #[inline]
+    fn clipboard_text(&self) -> Option<String> {
+        self.clipboard.lock().unwrap().clone()
+    }
+
+    #[inline]
+    fn set_clipboard_text(&self, text: &str) -> Result<()> {
+        *self.clipboard.lock().unwrap() = Some(text.to_owned());
         Ok(())
     }
 }
@@ -4,22 +4,48 @@ use crate::errors::*;
 use crate::math::prelude::Vector2;
 
 use super::events::Event;
+use super::system::WindowHandle;
+use super::{CursorIcon, WindowMode, WindowParams};
 
+/// The visitor of windows. The main window is specified with `None`, and the additional
+/// windows with their handles.
 pub trait Visitor {
-    fn show(&self);
-    fn hide(&self);
-    fn position(&self) -> Vector2<i32>;
-    fn dimensions(&self) -> Vector2<u32>;
-    fn device_pixel_ratio(&self) -> f32;
-    fn resize(&self, dimensions: Vector2<u32>);
-    fn poll_events(&mut self, events: &mut Vec<Event>);
-    fn is_current(&self) -> bool;
-    fn make_current(&self) -> Result<()>;
-    fn swap_buffers(&self) -> Result<()>;
+    /// Creates an additional window which shares GL objects with the main window.
+    fn create_window(&mut self, handle: WindowHandle, params: WindowParams) -> Result<()>;
+    fn delete_window(&mut self, handle: WindowHandle);
+    fn show(&self, handle: Option<WindowHandle>);
+    fn hide(&self, handle: Option<WindowHandle>);
+    fn position(&self, handle: Option<WindowHandle>) -> Option<Vector2<i32>>;
+    fn dimensions(&self, handle: Option<WindowHandle>) -> Option<Vector2<u32>>;
+    fn device_pixel_ratio(&self, handle: Option<WindowHandle>) -> Option<f32>;
+    fn resize(&self, handle: Option<WindowHandle>, dimensions: Vector2<u32>);
+    fn poll_events(&mut self, events: &mut Vec<(Option<WindowHandle>, Event)>);
+    fn is_current(&self, handle: Option<WindowHandle>) -> bool;
+    fn make_current(&self, handle: Option<WindowHandle>) -> Result<()>;
+    fn swap_buffers(&self, handle: Option<WindowHandle>) -> Result<()>;
+    fn set_mode(&self, handle: Option<WindowHandle>, mode: WindowMode) -> Result<()>;
+    fn set_size(&self, handle: Option<WindowHandle>, size: Vector2<u32>);
+    fn set_title(&self, handle: Option<WindowHandle>, title: &str);
+    /// Sets the window icon with its dimensions and `RGBA8` pixels.
+    fn set_icon(
+        &self,
+        handle: Option<WindowHandle>,
+        icon: Option<(Vector2<u32>, Vec<u8>)>,
+    ) -> Result<()>;
+    fn set_cursor(&self, handle: Option<WindowHandle>, cursor: CursorIcon);
+    fn set_cursor_visible(&self, handle: Option<WindowHandle>, visible: bool);
+    fn set_cursor_grab(&self, handle: Option<WindowHandle>, grab: bool) -> Result<()>;
+    /// Sets the position of IME candidate window in points, relative to the bottom-left
+    /// corner of window.
+    fn set_ime_position(&self, handle: Option<WindowHandle>, position: Vector2<f32>);
+    /// Checks if the composition of IME is reported with `ImeEvent`s.
+    fn ime_composition_supported(&self) -> bool;
+    fn clipboard_text(&self) -> Option<String>;
+    fn set_clipboard_text(&self, text: &str) -> Result<()>;
 }
 
 pub fn new_headless() -> Box<Visitor> {
-    Box::new(self::headless::HeadlessVisitor {})
+    Box::new(self::headless::HeadlessVisitor::new())
 }
 
 #[cfg(not(target_arch = "wasm32"))]
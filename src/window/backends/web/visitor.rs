@@ -3,11 +3,15 @@ use std::sync::{Arc, Mutex};
 use wasm_bindgen::prelude::*;
 use wasm_bindgen::JsCast;
 use web_sys::{
-    self, Document, Element, HtmlCanvasElement, KeyboardEvent, MouseEvent, Node, UiEvent, Window,
+    self, ClipboardEvent, CompositionEvent, Document, Element, HtmlCanvasElement, HtmlElement,
+    HtmlInputElement, KeyboardEvent, MouseEvent, Node, UiEvent, Window,
 };
 
+use crate::input::events::ImeEvent;
 use crate::input::prelude::{InputEvent, MouseButton};
-use crate::window::prelude::{Event, WindowEvent, WindowParams};
+use crate::window::prelude::{
+    CursorIcon, Event, WindowEvent, WindowHandle, WindowMode, WindowParams,
+};
 
 use crate::math::prelude::Vector2;
 use crate::errors::*;
@@ -28,6 +32,17 @@ pub struct WebVisitor {
     on_resize: Closure<FnMut(UiEvent)>,
     on_focus: Closure<FnMut(UiEvent)>,
     on_lost_focus: Closure<FnMut(UiEvent)>,
+    // Browsers only deliver composition events to editable elements, so we use a hidden
+    // text input for IME.
+    ime_input: HtmlInputElement,
+    on_composition_start: Closure<FnMut(CompositionEvent)>,
+    on_composition_update: Closure<FnMut(CompositionEvent)>,
+    on_composition_end: Closure<FnMut(CompositionEvent)>,
+    // Browsers do not allow accessing clipboard directly, so we keep a copy of it which is
+    // synchronized in the copy and paste events.
+    clipboard: Arc<Mutex<Option<String>>>,
+    on_copy: Closure<FnMut(ClipboardEvent)>,
+    on_paste: Closure<FnMut(ClipboardEvent)>,
 }
 
 impl WebVisitor {
@@ -119,6 +134,10 @@ impl WebVisitor {
 
                 let evt = Event::InputDevice(InputEvent::MouseMoved { position });
                 clone.lock().unwrap().push(evt);
+
+                let delta = (v.movement_x() as f32, -v.movement_y() as f32);
+                let evt = Event::InputDevice(InputEvent::MouseMotion { delta });
+                clone.lock().unwrap().push(evt);
             }) as Box<FnMut(_)>)
         };
 
@@ -193,6 +212,110 @@ impl WebVisitor {
             .add_event_listener_with_callback("resize", on_resize.as_ref().unchecked_ref())
             .unwrap();
 
+        let ime_input = document
+            .create_element("input")
+            .unwrap()
+            .dyn_into::<HtmlInputElement>()
+            .map_err(|_| ())
+            .unwrap();
+
+        ime_input
+            .unchecked_ref::<Element>()
+            .set_attribute(
+                "style",
+                "position: fixed; opacity: 0; width: 1px; height: 1px; left: 0px; top: 0px;",
+            ).unwrap();
+
+        AsRef::<Node>::as_ref(&body)
+            .append_child(ime_input.as_ref())
+            .unwrap();
+
+        let on_composition_start = {
+            let clone = events.clone();
+            Closure::wrap(Box::new(move |_: CompositionEvent| {
+                let evt = Event::Ime(ImeEvent::CompositionStarted);
+                clone.lock().unwrap().push(evt);
+            }) as Box<FnMut(_)>)
+        };
+
+        ime_input
+            .add_event_listener_with_callback(
+                "compositionstart",
+                on_composition_start.as_ref().unchecked_ref(),
+            ).unwrap();
+
+        let on_composition_update = {
+            let clone = events.clone();
+            Closure::wrap(Box::new(move |v: CompositionEvent| {
+                let text = v.data().unwrap_or_default();
+                let cursor = Some((text.len(), text.len()));
+                let evt = Event::Ime(ImeEvent::CompositionUpdated { text, cursor });
+                clone.lock().unwrap().push(evt);
+            }) as Box<FnMut(_)>)
+        };
+
+        ime_input
+            .add_event_listener_with_callback(
+                "compositionupdate",
+                on_composition_update.as_ref().unchecked_ref(),
+            ).unwrap();
+
+        let on_composition_end = {
+            let clone = events.clone();
+            let input = ime_input.clone();
+            Closure::wrap(Box::new(move |v: CompositionEvent| {
+                let mut events = clone.lock().unwrap();
+                events.push(Event::Ime(ImeEvent::CompositionEnded));
+
+                for character in v.data().unwrap_or_default().chars() {
+                    let evt = InputEvent::ReceivedCharacter { character };
+                    events.push(Event::InputDevice(evt));
+                }
+
+                input.set_value("");
+            }) as Box<FnMut(_)>)
+        };
+
+        ime_input
+            .add_event_listener_with_callback(
+                "compositionend",
+                on_composition_end.as_ref().unchecked_ref(),
+            ).unwrap();
+
+        let clipboard = Arc::new(Mutex::new(None));
+
+        let on_copy = {
+            let clone = clipboard.clone();
+            Closure::wrap(Box::new(move |v: ClipboardEvent| {
+                if let Some(text) = clone.lock().unwrap().as_ref() {
+                    if let Some(data) = v.clipboard_data() {
+                        if data.set_data("text/plain", text).is_ok() {
+                            v.prevent_default();
+                        }
+                    }
+                }
+            }) as Box<FnMut(_)>)
+        };
+
+        document
+            .add_event_listener_with_callback("copy", on_copy.as_ref().unchecked_ref())
+            .unwrap();
+
+        let on_paste = {
+            let clone = clipboard.clone();
+            Closure::wrap(Box::new(move |v: ClipboardEvent| {
+                if let Some(data) = v.clipboard_data() {
+                    if let Ok(text) = data.get_data("text/plain") {
+                        *clone.lock().unwrap() = Some(text);
+                    }
+                }
+            }) as Box<FnMut(_)>)
+        };
+
+        document
+            .add_event_listener_with_callback("paste", on_paste.as_ref().unchecked_ref())
+            .unwrap();
+
         let visitor = WebVisitor {
             window: window,
             document: document,
@@ -206,55 +329,84 @@ impl WebVisitor {
             on_focus: on_focus,
             on_lost_focus: on_lost_focus,
             on_resize: on_resize,
+            ime_input: ime_input,
+            on_composition_start: on_composition_start,
+            on_composition_update: on_composition_update,
+            on_composition_end: on_composition_end,
+            clipboard: clipboard,
+            on_copy: on_copy,
+            on_paste: on_paste,
         };
 
-        let dpr = visitor.device_pixel_ratio();
+        let dpr = visitor.window.device_pixel_ratio() as f32;
         let dims = Vector2::new(
             (params.size.x as f32 * dpr) as u32,
             (params.size.y as f32 * dpr) as u32,
         );
 
-        visitor.resize(dims);
+        visitor.resize(None, dims);
         Ok(visitor)
     }
 }
 
 impl Visitor for WebVisitor {
     #[inline]
-    fn show(&self) {
+    fn create_window(&mut self, _: WindowHandle, _: WindowParams) -> Result<()> {
+        bail!("Multiple windows are not supported in browser.");
+    }
+
+    #[inline]
+    fn delete_window(&mut self, _: WindowHandle) {}
+
+    #[inline]
+    fn show(&self, _: Option<WindowHandle>) {
         warn!("It does not make sense to `show` window in browser.")
     }
 
     #[inline]
-    fn hide(&self) {
+    fn hide(&self, _: Option<WindowHandle>) {
         warn!("It does not make sense to `hide` window in browser.")
     }
 
     #[inline]
-    fn position(&self) -> Vector2<i32> {
-        (0, 0).into()
+    fn position(&self, handle: Option<WindowHandle>) -> Option<Vector2<i32>> {
+        match handle {
+            Some(_) => None,
+            None => Some((0, 0).into()),
+        }
     }
 
     #[inline]
-    fn dimensions(&self) -> Vector2<u32> {
+    fn dimensions(&self, handle: Option<WindowHandle>) -> Option<Vector2<u32>> {
+        if handle.is_some() {
+            return None;
+        }
+
         let dpr = self.window.device_pixel_ratio() as f32;
-        Vector2::new(
+        Some(Vector2::new(
             (self.canvas.width() as f32 / dpr) as u32,
             (self.canvas.height() as f32 / dpr) as u32,
-        )
+        ))
     }
 
     #[inline]
-    fn device_pixel_ratio(&self) -> f32 {
-        self.window.device_pixel_ratio() as f32
+    fn device_pixel_ratio(&self, handle: Option<WindowHandle>) -> Option<f32> {
+        match handle {
+            Some(_) => None,
+            None => Some(self.window.device_pixel_ratio() as f32),
+        }
     }
 
     #[inline]
-    fn resize(&self, dims: Vector2<u32>) {
+    fn resize(&self, handle: Option<WindowHandle>, dims: Vector2<u32>) {
+        if handle.is_some() {
+            return;
+        }
+
         self.canvas.set_width(dims.x);
         self.canvas.set_height(dims.y);
 
-        let dpr = self.device_pixel_ratio();
+        let dpr = self.window.device_pixel_ratio() as f32;
         self.canvas
             .unchecked_ref::<Element>()
             .set_attribute(
@@ -268,24 +420,109 @@ impl Visitor for WebVisitor {
     }
 
     #[inline]
-    fn poll_events(&mut self, v: &mut Vec<Event>) {
+    fn poll_events(&mut self, v: &mut Vec<(Option<WindowHandle>, Event)>) {
         let mut events = self.events.lock().unwrap();
-        v.extend(events.drain(..));
+        v.extend(events.drain(..).map(|e| (None, e)));
     }
 
     #[inline]
-    fn is_current(&self) -> bool {
-        self.document.has_focus().unwrap_or(false)
+    fn is_current(&self, handle: Option<WindowHandle>) -> bool {
+        handle.is_none() && self.document.has_focus().unwrap_or(false)
     }
 
     #[inline]
-    fn make_current(&self) -> Result<()> {
+    fn make_current(&self, _: Option<WindowHandle>) -> Result<()> {
         warn!("You can not `make_current` in browser.");
         Ok(())
     }
 
     #[inline]
-    fn swap_buffers(&self) -> Result<()> {
+    fn swap_buffers(&self, _: Option<WindowHandle>) -> Result<()> {
+        Ok(())
+    }
+
+    #[inline]
+    fn set_mode(&self, handle: Option<WindowHandle>, mode: WindowMode) -> Result<()> {
+        if handle.is_some() || mode != WindowMode::Windowed {
+            bail!("{:?} is not supported in browser.", mode);
+        }
+
+        Ok(())
+    }
+
+    #[inline]
+    fn set_size(&self, handle: Option<WindowHandle>, size: Vector2<u32>) {
+        let dpr = self.window.device_pixel_ratio() as f32;
+        let dims = Vector2::new((size.x as f32 * dpr) as u32, (size.y as f32 * dpr) as u32);
+        self.resize(handle, dims);
+    }
+
+    #[inline]
+    fn set_title(&self, handle: Option<WindowHandle>, title: &str) {
+        if handle.is_none() {
+            self.document.set_title(title);
+        }
+    }
+
+    #[inline]
+    fn set_icon(&self, _: Option<WindowHandle>, _: Option<(Vector2<u32>, Vec<u8>)>) -> Result<()> {
+        warn!("It does not make sense to `set_icon` in browser.");
+        Ok(())
+    }
+
+    #[inline]
+    fn set_cursor(&self, _: Option<WindowHandle>, _: CursorIcon) {
+        warn!("You can not `set_cursor` in browser.");
+    }
+
+    #[inline]
+    fn set_cursor_visible(&self, _: Option<WindowHandle>, _: bool) {
+        warn!("You can not `set_cursor_visible` in browser.");
+    }
+
+    #[inline]
+    fn set_cursor_grab(&self, _: Option<WindowHandle>, _: bool) -> Result<()> {
+        bail!("Cursor grabbing is not supported in browser.");
+    }
+
+    fn set_ime_position(&self, handle: Option<WindowHandle>, position: Vector2<f32>) {
+        if handle.is_some() {
+            return;
+        }
+
+        // Converts into the page coordinates relative to the top-left corner.
+        let dpr = self.window.device_pixel_ratio() as f32;
+        let height = self.canvas.height() as f32 / dpr;
+        let rect = self.canvas.get_bounding_client_rect();
+        let x = rect.x() as f32 + position.x;
+        let y = rect.y() as f32 + height - position.y;
+
+        self.ime_input
+            .unchecked_ref::<Element>()
+            .set_attribute(
+                "style",
+                &format!(
+                    "position: fixed; opacity: 0; width: 1px; height: 1px; left: {}px; top: {}px;",
+                    x, y
+                ),
+            ).unwrap();
+
+        self.ime_input.unchecked_ref::<HtmlElement>().focus().ok();
+    }
+
+    #[inline]
+    fn ime_composition_supported(&self) -> bool {
+        true
+    }
+
+    #[inline]
+    fn clipboard_text(&self) -> Option<String> {
+        self.clipboard.lock().unwrap().clone()
+    }
+
+    #[inline]
+    fn set_clipboard_text(&self, text: &str) -> Result<()> {
+        *self.clipboard.lock().unwrap() = Some(text.to_owned());
         Ok(())
     }
 }
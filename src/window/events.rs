@@ -1,4 +1,6 @@
-use crate::input::events::InputEvent;
+use crate::input::events::{ImeEvent, InputEvent};
+
+use super::WindowMode;
 
 /// The status of application.
 #[derive(Debug, Clone, Copy)]
@@ -19,11 +21,18 @@ pub enum WindowEvent {
     Resized(u32, u32),
     /// The position of window has changed.
     Moved(u32, u32),
+    /// The display mode of window has changed.
+    ModeChanged(WindowMode),
+    /// The mouse cursor has been shown or hidden.
+    CursorVisibilityChanged(bool),
+    /// The mouse cursor has been grabbed or released.
+    CursorGrabChanged(bool),
 }
 
 /// The enumerations of all events that come from various kinds of user input.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub enum Event {
     Window(WindowEvent),
     InputDevice(InputEvent),
+    Ime(ImeEvent),
 }
@@ -3,18 +3,49 @@ pub mod events;
 
 pub mod prelude {
     pub use super::events::{Event, WindowEvent};
-    pub use super::system::{EventListener, EventListenerHandle};
-    pub use super::WindowParams;
+    pub use super::system::{EventListener, EventListenerHandle, WindowHandle};
+    pub use super::{CursorIcon, WindowMode, WindowParams};
 }
 
 mod backends;
 mod system;
 
 use self::inside::ctx;
-use self::system::{EventListener, EventListenerHandle};
+use self::system::{EventListener, EventListenerHandle, WindowHandle};
 
 use crate::errors::*;
 use crate::math::prelude::Vector2;
+use crate::video::assets::texture::{TextureData, TextureFormat, TextureParams};
+
+/// The display mode of window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WindowMode {
+    /// A decorated window with title bar and borders.
+    Windowed,
+    /// Exclusive fullscreen on the current monitor.
+    Fullscreen,
+    /// A window without decorations which covers the whole current monitor.
+    Borderless,
+}
+
+/// The appearance of mouse cursor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CursorIcon {
+    Default,
+    Crosshair,
+    Hand,
+    Arrow,
+    Move,
+    Text,
+    Wait,
+    Help,
+    Progress,
+    NotAllowed,
+    EwResize,
+    NsResize,
+    NeswResize,
+    NwseResize,
+}
 
 #[derive(Debug, Clone)]
 pub struct WindowParams {
@@ -27,6 +58,8 @@ pub struct WindowParams {
     pub multisample: u16,
     /// Specifies whether should we have vsync.
     pub vsync: bool,
+    /// Sets the initial display mode of window.
+    pub mode: WindowMode,
 }
 
 impl Default for WindowParams {
@@ -36,6 +69,7 @@ impl Default for WindowParams {
             size: Vector2::new(640, 320),
             multisample: 2,
             vsync: false,
+            mode: WindowMode::Windowed,
         }
     }
 }
@@ -50,6 +84,13 @@ pub fn detach(handle: EventListenerHandle) {
     ctx().remove_event_listener(handle)
 }
 
+/// Gets the timestamp in microseconds (`sys::precise_timestamp`) when the events of this
+/// frame were polled. Notes that all the events in one poll share it.
+#[inline]
+pub(crate) fn polled_timestamp() -> u64 {
+    ctx().polled_timestamp()
+}
+
 /// Shows the window if it was hidden.
 ///
 /// # Platform-specific
@@ -57,7 +98,7 @@ pub fn detach(handle: EventListenerHandle) {
 /// Has no effect on mobile platform.
 #[inline]
 pub fn show() {
-    ctx().show();
+    ctx().show(None);
 }
 
 /// Hides the window if it was visible.
@@ -67,13 +108,13 @@ pub fn show() {
 /// Has no effect on mobile platform.
 #[inline]
 pub fn hide() {
-    ctx().hide();
+    ctx().hide(None);
 }
 
 /// Set the context as the active context in this thread.
 #[inline]
 pub fn make_current() -> Result<()> {
-    ctx().make_current()
+    ctx().make_current(None)
 }
 
 /// Returns true if this context is the current one in this thread.
@@ -92,7 +133,7 @@ pub fn is_current() -> bool {
 /// the visible screen region.
 #[inline]
 pub fn position() -> Vector2<i32> {
-    ctx().position()
+    ctx().position(None).unwrap()
 }
 
 /// Returns the size in *points* of the client area of the window.
@@ -101,21 +142,231 @@ pub fn position() -> Vector2<i32> {
 /// the size of the frame buffer.
 #[inline]
 pub fn dimensions() -> Vector2<u32> {
-    ctx().dimensions()
+    ctx().dimensions(None).unwrap()
 }
 
 /// Returns the ratio between the backing framebuffer resolution and the window size in
 /// screen pixels. This is typically one for a normal display and two for a retina display.
 #[inline]
 pub fn device_pixel_ratio() -> f32 {
-    ctx().device_pixel_ratio()
+    ctx().device_pixel_ratio(None).unwrap()
+}
+
+/// Sets the display mode of window. A `WindowEvent::ModeChanged` will be sent in the next
+/// frame.
+///
+/// # Platform-specific
+///
+/// Only `WindowMode::Windowed` is supported in browser.
+#[inline]
+pub fn set_mode(mode: WindowMode) -> Result<()> {
+    ctx().set_mode(mode)
+}
+
+/// Gets the display mode of window.
+#[inline]
+pub fn mode() -> WindowMode {
+    ctx().mode()
+}
+
+/// Sets the size in *points* of the client area of the window.
+#[inline]
+pub fn set_size<T: Into<Vector2<u32>>>(size: T) {
+    ctx().set_size(size.into());
+}
+
+/// Sets the title of window.
+#[inline]
+pub fn set_title<T: AsRef<str>>(title: T) {
+    ctx().set_title(title.as_ref());
+}
+
+/// Sets the icon of window from an uncompressed `RGBA8` or `RGB8` image.
+///
+/// # Platform-specific
+///
+/// Has no effect in browser and on macOS.
+pub fn set_icon(params: &TextureParams, data: &TextureData) -> Result<()> {
+    let (w, h) = (params.dimensions.x as usize, params.dimensions.y as usize);
+    let bytes = data
+        .bytes
+        .get(0)
+        .ok_or_else(|| format_err!("The icon image is empty."))?;
+
+    let rgba = match params.format {
+        TextureFormat::RGBA8 if bytes.len() >= w * h * 4 => bytes[..w * h * 4].to_vec(),
+        TextureFormat::RGB8 if bytes.len() >= w * h * 3 => bytes[..w * h * 3]
+            .chunks(3)
+            .flat_map(|v| vec![v[0], v[1], v[2], 255])
+            .collect(),
+        TextureFormat::RGBA8 | TextureFormat::RGB8 => {
+            bail!("The icon image is smaller than its dimensions.")
+        }
+        _ => bail!("{:?} is not supported as window icon.", params.format),
+    };
+
+    ctx().set_icon(Some((params.dimensions, rgba)))
+}
+
+/// Removes the icon of window.
+#[inline]
+pub fn clear_icon() -> Result<()> {
+    ctx().set_icon(None)
+}
+
+/// Sets the appearance of mouse cursor when it's inside the window.
+#[inline]
+pub fn set_cursor(cursor: CursorIcon) {
+    ctx().set_cursor(cursor);
+}
+
+/// Gets the appearance of mouse cursor.
+#[inline]
+pub fn cursor() -> CursorIcon {
+    ctx().cursor()
+}
+
+/// Shows or hides the mouse cursor when it's inside the window. A
+/// `WindowEvent::CursorVisibilityChanged` will be sent in the next frame.
+#[inline]
+pub fn set_cursor_visible(visible: bool) {
+    ctx().set_cursor_visible(visible);
+}
+
+/// Checks if the mouse cursor is visible.
+#[inline]
+pub fn cursor_visible() -> bool {
+    ctx().cursor_visible()
+}
+
+/// Grabs the mouse cursor, preventing it from leaving the window. Combines with a hidden
+/// cursor and `input::mouse_motion` for FPS-style camera controls. A
+/// `WindowEvent::CursorGrabChanged` will be sent in the next frame.
+///
+/// # Platform-specific
+///
+/// Not supported in browser.
+#[inline]
+pub fn set_cursor_grab(grab: bool) -> Result<()> {
+    ctx().set_cursor_grab(grab)
+}
+
+/// Checks if the mouse cursor is grabbed.
+#[inline]
+pub fn cursor_grab() -> bool {
+    ctx().cursor_grab()
+}
+
+/// Sets the position of IME candidate window in points, relative to the bottom-left corner
+/// of window. Text fields should call it with the position of caret when focused.
+///
+/// # Platform-specific
+///
+/// Only works on X11 and in browser. In browser, it also moves the keyboard focus to a hidden
+/// text input, which is required to receive `ImeEvent`s. Desktop composition is unsupported,
+/// so on X11 it only positions the candidate window and no `ImeEvent`s are emitted.
+#[inline]
+pub fn set_ime_position<T: Into<Vector2<f32>>>(position: T) {
+    ctx().set_ime_position(position.into());
+}
+
+/// Checks if the composition of IME is reported with `ImeEvent`s. If not, the composing
+/// text is drawn by the input method editor itself, and text fields should not wait for
+/// `input::ime_composition`.
+///
+/// # Platform-specific
+///
+/// Only supported in browser. glutin does not expose the XIM preedit callbacks, so the
+/// desktop composition is unsupported.
+#[inline]
+pub fn ime_composition_supported() -> bool {
+    ctx().ime_composition_supported()
+}
+
+/// Gets the text content of clipboard.
+///
+/// # Platform-specific
+///
+/// Browsers do not allow reading clipboard directly, it returns the text of the last paste
+/// event or `set_clipboard_text` instead.
+#[inline]
+pub fn clipboard_text() -> Option<String> {
+    ctx().clipboard_text()
+}
+
+/// Sets the text content of clipboard.
+///
+/// # Platform-specific
+///
+/// Browsers do not allow writing clipboard directly, the text is written when user copies
+/// (e.g. pressing Ctrl+C) in the page.
+#[inline]
+pub fn set_clipboard_text<T: AsRef<str>>(text: T) -> Result<()> {
+    ctx().set_clipboard_text(text.as_ref())
+}
+
+/// Creates an additional window, which shares textures, meshes and shaders with the main
+/// window. Its events are delivered to `EventListener::on_window`.
+///
+/// Surfaces could be drawn into the default framebuffer of this window with
+/// `SurfaceParams::set_window`.
+///
+/// # Platform-specific
+///
+/// Multiple windows are not supported in browser.
+#[inline]
+pub fn create(params: WindowParams) -> Result<WindowHandle> {
+    ctx().create(params)
+}
+
+/// Deletes an additional window.
+#[inline]
+pub fn delete(handle: WindowHandle) {
+    ctx().delete(handle);
+}
+
+/// Checks if the additional window is alive.
+#[inline]
+pub fn alive(handle: WindowHandle) -> bool {
+    ctx().alive(Some(handle))
+}
+
+/// Shows the additional window if it was hidden.
+#[inline]
+pub fn show_window(handle: WindowHandle) {
+    ctx().show(Some(handle));
+}
+
+/// Hides the additional window if it was visible.
+#[inline]
+pub fn hide_window(handle: WindowHandle) {
+    ctx().hide(Some(handle));
+}
+
+/// Returns the position of the additional window, or `None` if it has been deleted.
+#[inline]
+pub fn window_position(handle: WindowHandle) -> Option<Vector2<i32>> {
+    ctx().position(Some(handle))
+}
+
+/// Returns the size in *points* of the client area of the additional window, or `None` if
+/// it has been deleted.
+#[inline]
+pub fn window_dimensions(handle: WindowHandle) -> Option<Vector2<u32>> {
+    ctx().dimensions(Some(handle))
+}
+
+/// Returns the device pixel ratio of the additional window, or `None` if it has been deleted.
+#[inline]
+pub fn window_device_pixel_ratio(handle: WindowHandle) -> Option<f32> {
+    ctx().device_pixel_ratio(Some(handle))
 }
 
 pub(crate) mod inside {
     use crate::errors::*;
     use crate::math::prelude::Vector2;
 
-    use super::system::WindowSystem;
+    use super::system::{WindowHandle, WindowSystem};
     use super::WindowParams;
 
     pub static mut CTX: *const WindowSystem = std::ptr::null();
@@ -154,6 +405,13 @@ pub(crate) mod inside {
         ctx().resize(dimensions);
     }
 
+    /// Set the context of window as the active context in this thread. `None` represents
+    /// the main window.
+    #[inline]
+    pub fn make_current(handle: Option<WindowHandle>) -> Result<()> {
+        ctx().make_current(handle)
+    }
+
     /// Discard the window system.
     pub unsafe fn discard() {
         if CTX.is_null() {
@@ -1,18 +1,27 @@
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, Mutex, RwLock};
 
 use crate::application::prelude::{LifecycleListener, LifecycleListenerHandle};
 use crate::errors::*;
 use crate::math::prelude::Vector2;
+use crate::utils::handle_pool::HandlePool;
 use crate::utils::object_pool::ObjectPool;
 
 use super::backends::{self, Visitor};
-use super::events::Event;
-use super::WindowParams;
+use super::events::{Event, WindowEvent};
+use super::{CursorIcon, WindowMode, WindowParams};
 
 impl_handle!(EventListenerHandle);
+impl_handle!(WindowHandle);
 
 pub trait EventListener {
+    /// Receives the events of main window.
     fn on(&mut self, v: &Event) -> Result<()>;
+
+    /// Receives the events of additional windows created with `window::create`.
+    fn on_window(&mut self, _: WindowHandle, _: &Event) -> Result<()> {
+        Ok(())
+    }
 }
 
 /// Represents an OpenGL context and the window or environment around it.
@@ -21,9 +30,34 @@ pub struct WindowSystem {
     state: Arc<WindowState>,
 }
 
+#[derive(Debug, Clone, Copy)]
+struct Attributes {
+    mode: WindowMode,
+    cursor: CursorIcon,
+    cursor_visible: bool,
+    cursor_grab: bool,
+}
+
+impl Attributes {
+    fn new(mode: WindowMode) -> Self {
+        Attributes {
+            mode,
+            cursor: CursorIcon::Default,
+            cursor_visible: true,
+            cursor_grab: false,
+        }
+    }
+}
+
 struct WindowState {
     visitor: RwLock<Box<dyn Visitor>>,
-    events: Mutex<Vec<Event>>,
+    attributes: RwLock<Attributes>,
+    // Events of the changes made by ourself, which are sent in the next frame.
+    pending_events: Mutex<Vec<(Option<WindowHandle>, Event)>>,
+    windows: RwLock<HandlePool<WindowHandle>>,
+    events: Mutex<Vec<(Option<WindowHandle>, Event)>>,
+    // The timestamp when `events` were polled, in microseconds.
+    polled: AtomicU64,
     last_frame_listeners: Mutex<Vec<Arc<Mutex<dyn EventListener>>>>,
     listeners: Mutex<ObjectPool<EventListenerHandle, Arc<Mutex<dyn EventListener>>>>,
 }
@@ -35,7 +69,10 @@ impl LifecycleListener for Arc<WindowState> {
         events.clear();
 
         let mut visitor = self.visitor.write().unwrap();
+        let now = crate::application::sys::precise_timestamp();
+        self.polled.store(now, Ordering::Release);
         visitor.poll_events(&mut events);
+        events.extend(self.pending_events.lock().unwrap().drain(..));
 
         let mut last_frame_listeners = self.last_frame_listeners.lock().unwrap();
 
@@ -47,8 +84,11 @@ impl LifecycleListener for Arc<WindowState> {
 
         for lis in last_frame_listeners.iter() {
             let mut lis = lis.lock().unwrap();
-            for v in events.iter() {
-                lis.on(v)?;
+            for &(handle, ref v) in events.iter() {
+                match handle {
+                    Some(handle) => lis.on_window(handle, v)?,
+                    None => lis.on(v)?,
+                }
             }
         }
 
@@ -61,7 +101,18 @@ impl LifecycleListener for Arc<WindowState> {
         // **Warning**: if you enabled vsync, this function will block until the next time the screen
         // is refreshed. However drivers can choose to override your vsync settings, which means that
         // you can't know in advance whether swap_buffers will block or not.
-        self.visitor.read().unwrap().swap_buffers()?;
+        //
+        // The handles are collected before locking visitor, since `create` locks windows and
+        // visitor in that order.
+        let handles: Vec<_> = self.windows.read().unwrap().iter().collect();
+        let visitor = self.visitor.read().unwrap();
+        for handle in handles {
+            visitor.make_current(Some(handle))?;
+            visitor.swap_buffers(Some(handle))?;
+        }
+
+        visitor.make_current(None)?;
+        visitor.swap_buffers(None)?;
         Ok(())
     }
 }
@@ -75,10 +126,15 @@ impl Drop for WindowSystem {
 impl WindowSystem {
     /// Creates a new `WindowSystem` and initalize OpenGL context.
     pub fn from(params: WindowParams) -> Result<Self> {
+        let attributes = Attributes::new(params.mode);
         let state = Arc::new(WindowState {
+            attributes: RwLock::new(attributes),
+            pending_events: Mutex::new(Vec::new()),
             last_frame_listeners: Mutex::new(Vec::new()),
             listeners: Mutex::new(ObjectPool::new()),
             events: Mutex::new(Vec::new()),
+            polled: AtomicU64::new(0),
+            windows: RwLock::new(HandlePool::new()),
             visitor: RwLock::new(backends::new(params)?),
         });
 
@@ -93,9 +149,13 @@ impl WindowSystem {
     /// Creates a new `Window` with headless context.
     pub fn headless() -> Self {
         let state = Arc::new(WindowState {
+            attributes: RwLock::new(Attributes::new(WindowMode::Windowed)),
+            pending_events: Mutex::new(Vec::new()),
             last_frame_listeners: Mutex::new(Vec::new()),
             listeners: Mutex::new(ObjectPool::new()),
             events: Mutex::new(Vec::new()),
+            polled: AtomicU64::new(0),
+            windows: RwLock::new(HandlePool::new()),
             visitor: RwLock::new(backends::new_headless()),
         });
 
@@ -116,14 +176,45 @@ impl WindowSystem {
         self.state.listeners.lock().unwrap().free(handle);
     }
 
+    /// Creates an additional window, which shares GL objects with the main window.
+    pub fn create(&self, params: WindowParams) -> Result<WindowHandle> {
+        let mut windows = self.state.windows.write().unwrap();
+        let handle = windows.create();
+
+        if let Err(err) = self.state.visitor.write().unwrap().create_window(handle, params) {
+            windows.free(handle);
+            return Err(err);
+        }
+
+        Ok(handle)
+    }
+
+    /// Deletes an additional window.
+    pub fn delete(&self, handle: WindowHandle) {
+        if self.state.windows.write().unwrap().free(handle) {
+            self.state.visitor.write().unwrap().delete_window(handle);
+        }
+    }
+
+    /// Checks if the window is alive. `None` represents the main window.
+    #[inline]
+    pub fn alive(&self, handle: Option<WindowHandle>) -> bool {
+        match handle {
+            Some(handle) => self.state.windows.read().unwrap().contains(handle),
+            None => true,
+        }
+    }
+
     /// Shows the window if it was hidden.
     ///
     /// # Platform-specific
     ///
     /// Has no effect on mobile platform.
     #[inline]
-    pub fn show(&self) {
-        self.state.visitor.read().unwrap().show();
+    pub fn show(&self, handle: Option<WindowHandle>) {
+        if self.alive(handle) {
+            self.state.visitor.read().unwrap().show(handle);
+        }
     }
 
     /// Hides the window if it was visible.
@@ -132,20 +223,26 @@ impl WindowSystem {
     ///
     /// Has no effect on mobile platform.
     #[inline]
-    pub fn hide(&self) {
-        self.state.visitor.read().unwrap().hide();
+    pub fn hide(&self, handle: Option<WindowHandle>) {
+        if self.alive(handle) {
+            self.state.visitor.read().unwrap().hide(handle);
+        }
     }
 
-    /// Set the context as the active context in this thread.
+    /// Set the context of window as the active context in this thread.
     #[inline]
-    pub fn make_current(&self) -> Result<()> {
-        self.state.visitor.read().unwrap().make_current()
+    pub fn make_current(&self, handle: Option<WindowHandle>) -> Result<()> {
+        if !self.alive(handle) {
+            bail!("Window {:?} has been deleted.", handle);
+        }
+
+        self.state.visitor.read().unwrap().make_current(handle)
     }
 
-    /// Returns true if this context is the current one in this thread.
+    /// Returns true if the context of main window is the current one in this thread.
     #[inline]
     pub fn is_current(&self) -> bool {
-        self.state.visitor.read().unwrap().is_current()
+        self.state.visitor.read().unwrap().is_current(None)
     }
 
     /// Returns the position of the lower-left hand corner of the window relative to the lower-left
@@ -157,8 +254,12 @@ impl WindowSystem {
     /// The coordinates can be negative if the lower-left hand corner of the window is outside of
     /// the visible screen region.
     #[inline]
-    pub fn position(&self) -> Vector2<i32> {
-        self.state.visitor.read().unwrap().position()
+    pub fn position(&self, handle: Option<WindowHandle>) -> Option<Vector2<i32>> {
+        if !self.alive(handle) {
+            return None;
+        }
+
+        self.state.visitor.read().unwrap().position(handle)
     }
 
     /// Returns the size in *points* of the client area of the window.
@@ -166,20 +267,162 @@ impl WindowSystem {
     /// The client area is the content of the window, excluding the title bar and borders. These are
     /// the size of the frame buffer.
     #[inline]
-    pub fn dimensions(&self) -> Vector2<u32> {
-        self.state.visitor.read().unwrap().dimensions()
+    pub fn dimensions(&self, handle: Option<WindowHandle>) -> Option<Vector2<u32>> {
+        if !self.alive(handle) {
+            return None;
+        }
+
+        self.state.visitor.read().unwrap().dimensions(handle)
     }
 
     /// Returns the ratio between the backing framebuffer resolution and the window size in
     /// screen pixels. This is typically one for a normal display and two for a retina display.
     #[inline]
-    pub fn device_pixel_ratio(&self) -> f32 {
-        self.state.visitor.read().unwrap().device_pixel_ratio()
+    pub fn device_pixel_ratio(&self, handle: Option<WindowHandle>) -> Option<f32> {
+        if !self.alive(handle) {
+            return None;
+        }
+
+        self.state.visitor.read().unwrap().device_pixel_ratio(handle)
+    }
+
+    /// Sets the display mode of main window.
+    pub fn set_mode(&self, mode: WindowMode) -> Result<()> {
+        self.state.visitor.read().unwrap().set_mode(None, mode)?;
+
+        let mut attributes = self.state.attributes.write().unwrap();
+        if attributes.mode != mode {
+            attributes.mode = mode;
+            self.notify(WindowEvent::ModeChanged(mode));
+        }
+
+        Ok(())
+    }
+
+    /// Gets the display mode of main window.
+    #[inline]
+    pub fn mode(&self) -> WindowMode {
+        self.state.attributes.read().unwrap().mode
+    }
+
+    /// Sets the size in *points* of the client area of main window.
+    #[inline]
+    pub fn set_size(&self, size: Vector2<u32>) {
+        self.state.visitor.read().unwrap().set_size(None, size);
+    }
+
+    /// Sets the title of main window.
+    #[inline]
+    pub fn set_title(&self, title: &str) {
+        self.state.visitor.read().unwrap().set_title(None, title);
+    }
+
+    /// Sets the icon of main window with its dimensions and `RGBA8` pixels.
+    #[inline]
+    pub fn set_icon(&self, icon: Option<(Vector2<u32>, Vec<u8>)>) -> Result<()> {
+        self.state.visitor.read().unwrap().set_icon(None, icon)
+    }
+
+    /// Sets the appearance of mouse cursor.
+    #[inline]
+    pub fn set_cursor(&self, cursor: CursorIcon) {
+        self.state.visitor.read().unwrap().set_cursor(None, cursor);
+        self.state.attributes.write().unwrap().cursor = cursor;
+    }
+
+    /// Gets the appearance of mouse cursor.
+    #[inline]
+    pub fn cursor(&self) -> CursorIcon {
+        self.state.attributes.read().unwrap().cursor
+    }
+
+    /// Shows or hides the mouse cursor.
+    pub fn set_cursor_visible(&self, visible: bool) {
+        self.state
+            .visitor
+            .read()
+            .unwrap()
+            .set_cursor_visible(None, visible);
+
+        let mut attributes = self.state.attributes.write().unwrap();
+        if attributes.cursor_visible != visible {
+            attributes.cursor_visible = visible;
+            self.notify(WindowEvent::CursorVisibilityChanged(visible));
+        }
+    }
+
+    /// Checks if the mouse cursor is visible.
+    #[inline]
+    pub fn cursor_visible(&self) -> bool {
+        self.state.attributes.read().unwrap().cursor_visible
+    }
+
+    /// Grabs or releases the mouse cursor.
+    pub fn set_cursor_grab(&self, grab: bool) -> Result<()> {
+        self.state.visitor.read().unwrap().set_cursor_grab(None, grab)?;
+
+        let mut attributes = self.state.attributes.write().unwrap();
+        if attributes.cursor_grab != grab {
+            attributes.cursor_grab = grab;
+            self.notify(WindowEvent::CursorGrabChanged(grab));
+        }
+
+        Ok(())
+    }
+
+    /// Checks if the mouse cursor is grabbed.
+    #[inline]
+    pub fn cursor_grab(&self) -> bool {
+        self.state.attributes.read().unwrap().cursor_grab
+    }
+
+    /// Sets the position of IME candidate window in points.
+    #[inline]
+    pub fn set_ime_position(&self, position: Vector2<f32>) {
+        self.state
+            .visitor
+            .read()
+            .unwrap()
+            .set_ime_position(None, position);
+    }
+
+    /// Gets the timestamp when the events of this frame were polled, in microseconds.
+    #[inline]
+    pub fn polled_timestamp(&self) -> u64 {
+        self.state.polled.load(Ordering::Acquire)
+    }
+
+    /// Checks if the composition of IME is reported with `ImeEvent`s.
+    #[inline]
+    pub fn ime_composition_supported(&self) -> bool {
+        self.state
+            .visitor
+            .read()
+            .unwrap()
+            .ime_composition_supported()
+    }
+
+    /// Gets the text content of clipboard.
+    #[inline]
+    pub fn clipboard_text(&self) -> Option<String> {
+        self.state.visitor.read().unwrap().clipboard_text()
+    }
+
+    /// Sets the text content of clipboard.
+    #[inline]
+    pub fn set_clipboard_text(&self, text: &str) -> Result<()> {
+        self.state.visitor.read().unwrap().set_clipboard_text(text)
+    }
+
+    #[inline]
+    fn notify(&self, v: WindowEvent) {
+        let mut events = self.state.pending_events.lock().unwrap();
+        events.push((None, Event::Window(v)));
     }
 
-    /// Resize the GL context.
+    /// Resize the GL context of main window.
     #[inline]
     pub fn resize(&self, dimensions: Vector2<u32>) {
-        self.state.visitor.read().unwrap().resize(dimensions);
+        self.state.visitor.read().unwrap().resize(None, dimensions);
     }
 }
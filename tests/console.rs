@@ -0,0 +1,73 @@
+extern crate crayon;
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use crayon::application;
+use crayon::application::prelude::*;
+use crayon::console::{self, CVarValue};
+
+fn execute() {
+    // The values in config are restored on registration.
+    console::register_cvar("speed", "The speed of player.", 1.0);
+    assert_eq!(console::cvar("speed"), Some(CVarValue::Float(2.5)));
+    assert_eq!(console::cvar("max_fps"), Some(CVarValue::Int(45)));
+
+    console::execute("time_scale 0.5").unwrap();
+    assert_eq!(application::time_scale(), 0.5);
+    assert_eq!(console::execute("time_scale").unwrap(), "time_scale = 0.5");
+
+    let god = Arc::new(AtomicBool::new(false));
+    let clone = god.clone();
+    console::register_cvar_with_callback("god", "Invincible.", false, move |v| {
+        clone.store(v.as_bool().unwrap(), Ordering::SeqCst);
+    });
+
+    console::execute("god on").unwrap();
+    assert!(god.load(Ordering::SeqCst));
+    console::set_cvar("god", false).unwrap();
+    assert!(!god.load(Ordering::SeqCst));
+
+    console::register_command("add", "Adds numbers.", |args| {
+        let mut sum = 0;
+        for v in args {
+            sum += v.parse::<i32>()?;
+        }
+
+        Ok(sum.to_string())
+    });
+
+    let output = console::execute("add 1 2; echo \"a b\" // comment").unwrap();
+    assert_eq!(output, "3\na b");
+
+    assert!(console::execute("undefined").is_err());
+    assert!(console::execute("god maybe").is_err());
+    assert!(console::execute("add 1 a").is_err());
+    assert!(console::set_cvar("speed", true).is_err());
+
+    // Unregistered cvars are kept for later.
+    console::execute("set volume 0.8").unwrap();
+    console::register_cvar("volume", "The master volume.", 1.0);
+    assert_eq!(console::cvar("volume"), Some(CVarValue::Float(0.8)));
+
+    console::set_visible(true);
+    assert!(console::overlay().visible);
+}
+
+#[test]
+fn console() {
+    let config = std::env::temp_dir().join("crayon-console-test.cfg");
+    std::fs::write(&config, "set max_fps 45\nset speed \"2.5\"\n").unwrap();
+
+    let mut params = Params::default();
+    params.console.config = Some(config.clone());
+    application::setup_headless(params).unwrap();
+    execute();
+
+    console::save_config().unwrap();
+    let saved = std::fs::read_to_string(&config).unwrap();
+    assert!(saved.contains("set speed 2.5"));
+    assert!(saved.contains("set volume 0.8"));
+    assert!(saved.contains("set max_fps 45"));
+    std::fs::remove_file(config).unwrap();
+}
@@ -0,0 +1,77 @@
+extern crate crayon;
+extern crate env_logger;
+extern crate log;
+
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use crayon::application;
+use crayon::application::prelude::*;
+use crayon::crash::{self, CrashLogger, CrashReport};
+
+fn report() -> CrashReport {
+    let reports = Arc::new(Mutex::new(Vec::new()));
+    let clone = reports.clone();
+    crash::set_callback(move |v| clone.lock().unwrap().push(v.clone()));
+
+    application::run_frames(3).unwrap();
+    assert_eq!(crash::frame(), 3);
+    log::info!("Before crash.");
+
+    let handle = thread::Builder::new()
+        .name("Crasher".into())
+        .spawn(|| panic!("Boom!"))
+        .unwrap();
+
+    assert!(handle.join().is_err());
+
+    let mut reports = reports.lock().unwrap();
+    assert_eq!(reports.len(), 1);
+    reports.pop().unwrap()
+}
+
+#[test]
+fn crash() {
+    let logger = env_logger::Builder::new()
+        .filter(None, log::LevelFilter::Info)
+        .build();
+
+    log::set_max_level(logger.filter());
+    log::set_boxed_logger(Box::new(CrashLogger::new(logger))).unwrap();
+
+    let dir = std::env::temp_dir().join("crayon-crash-test");
+    let mut params = Params::default();
+    params.crash.dir = Some(dir.clone());
+    params.crash.max_phases = 4;
+    params.fixed_timestep = Duration::from_secs(0);
+    application::setup_headless(params).unwrap();
+
+    let report = report();
+    assert_eq!(report.message, "Boom!");
+    assert_eq!(report.thread, "Crasher");
+    assert!(report.location.unwrap().contains("crash.rs"));
+    assert_eq!(report.frame, 3);
+    assert_eq!(
+        report.phases,
+        vec![
+            (3, "on_pre_update"),
+            (3, "on_update"),
+            (3, "on_render"),
+            (3, "on_post_update")
+        ]
+    );
+
+    assert!(report.logs.iter().any(|v| v.contains("Before crash.")));
+    assert!(report.params.contains("max_phases: 4"));
+
+    let path = report.path.unwrap();
+    let text = std::fs::read_to_string(&path).unwrap();
+    assert!(text.contains("Message: Boom!"));
+    assert!(text.contains("Frame: 3"));
+    std::fs::remove_dir_all(dir).unwrap();
+
+    let report = crash::capture("Fatal error.");
+    assert_eq!(report.message, "Fatal error.");
+    assert!(report.path.is_none());
+}
@@ -0,0 +1,87 @@
+extern crate crayon;
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crayon::application;
+use crayon::application::prelude::*;
+use crayon::utils::random::Random;
+
+struct Counter {
+    fixed_updates: Arc<AtomicUsize>,
+}
+
+impl LifecycleListener for Counter {
+    fn on_fixed_update(&mut self) -> crayon::errors::Result<()> {
+        self.fixed_updates.fetch_add(1, Ordering::SeqCst);
+        Ok(())
+    }
+}
+
+fn clock() {
+    let fixed_updates = Arc::new(AtomicUsize::new(0));
+    let lis = application::attach(Counter {
+        fixed_updates: fixed_updates.clone(),
+    });
+
+    let fired = Arc::new(AtomicUsize::new(0));
+    let f2 = fired.clone();
+    let timer = Timer::repeat(Duration::from_millis(100));
+    application::add_timer("tick", timer, move || {
+        f2.fetch_add(1, Ordering::SeqCst);
+    });
+
+    application::run_frames(100).unwrap();
+
+    assert_eq!(application::game_time(), Duration::from_secs(1));
+    assert_eq!(application::real_time(), Duration::from_secs(1));
+    assert_eq!(application::frame_duration(), Duration::from_millis(10));
+    assert_eq!(fixed_updates.load(Ordering::SeqCst), 50);
+    assert_eq!(fired.load(Ordering::SeqCst), 10);
+
+    // The game time stops when paused, but the real time does not.
+    application::pause();
+    application::run_frames(10).unwrap();
+    assert_eq!(application::game_time(), Duration::from_secs(1));
+    assert_eq!(application::real_time(), Duration::from_millis(1100));
+    assert_eq!(fixed_updates.load(Ordering::SeqCst), 50);
+    assert_eq!(fired.load(Ordering::SeqCst), 10);
+
+    application::resume();
+    application::set_time_scale(0.5);
+    application::run_frames(40).unwrap();
+    assert_eq!(application::game_time(), Duration::from_millis(1200));
+    assert_eq!(application::game_frame_duration(), Duration::from_millis(5));
+    assert_eq!(fixed_updates.load(Ordering::SeqCst), 60);
+    assert_eq!(fired.load(Ordering::SeqCst), 12);
+
+    application::detach(lis);
+}
+
+fn seed() {
+    assert_eq!(application::seed(), 42);
+
+    let mut lhs = Random::new(application::seed());
+    let mut rhs = Random::new(application::seed());
+    for _ in 0..16 {
+        assert_eq!(lhs.next_u64(), rhs.next_u64());
+    }
+
+    let v = lhs.next_f32();
+    assert!(v >= 0.0 && v < 1.0);
+    assert!(lhs.range(3, 5) >= 3);
+}
+
+#[test]
+fn deterministic() {
+    let mut params = Params::default();
+    params.fake_clock = Some(Duration::from_millis(10));
+    params.fixed_timestep = Duration::from_millis(20);
+    params.seed = 42;
+
+    application::setup_headless(params).unwrap();
+
+    clock();
+    seed();
+}
@@ -0,0 +1,97 @@
+extern crate crayon;
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crayon::application;
+use crayon::application::prelude::*;
+use crayon::input;
+use crayon::input::prelude::*;
+
+// Events are cleared at the end of every frame, so we collect them during updates.
+struct Collector(Arc<Mutex<Vec<Vec<TimedInputEvent>>>>);
+
+impl LifecycleListener for Collector {
+    fn on_update(&mut self) -> crayon::errors::Result<()> {
+        self.0.lock().unwrap().push(input::events().collect());
+        Ok(())
+    }
+}
+
+fn recorded(frame: u64, event: InputEvent) -> RecordedEvent {
+    RecordedEvent {
+        frame,
+        time: Duration::from_millis(0),
+        event,
+    }
+}
+
+fn events() {
+    let frames = Arc::new(Mutex::new(Vec::new()));
+    let lis = application::attach(Collector(frames.clone()));
+
+    let mut recording = InputRecording::default();
+    recording.frames = 2;
+    recording.events = vec![
+        recorded(0, InputEvent::KeyboardPressed { key: Key::LShift }),
+        recorded(0, InputEvent::KeyboardPressed { key: Key::A }),
+        recorded(0, InputEvent::ReceivedCharacter { character: 'A' }),
+        recorded(0, InputEvent::KeyboardReleased { key: Key::A }),
+        recorded(0, InputEvent::KeyboardReleased { key: Key::LShift }),
+        recorded(0, InputEvent::KeyboardPressed { key: Key::B }),
+        recorded(1, InputEvent::KeyboardPressed { key: Key::RControl }),
+        recorded(
+            1,
+            InputEvent::MousePressed {
+                button: MouseButton::Left,
+            },
+        ),
+    ];
+
+    input::play_recording(recording);
+    application::run_frames(3).unwrap();
+    application::detach(lis);
+
+    let frames = frames.lock().unwrap();
+    assert_eq!(frames.len(), 3);
+
+    // Fast key sequences are preserved in order.
+    let events: Vec<_> = frames[0].iter().map(|v| v.event).collect();
+    assert_eq!(
+        &events[..],
+        &[
+            InputEvent::KeyboardPressed { key: Key::LShift },
+            InputEvent::KeyboardPressed { key: Key::A },
+            InputEvent::ReceivedCharacter { character: 'A' },
+            InputEvent::KeyboardReleased { key: Key::A },
+            InputEvent::KeyboardReleased { key: Key::LShift },
+            InputEvent::KeyboardPressed { key: Key::B },
+        ]
+    );
+
+    let shift: Vec<_> = frames[0].iter().map(|v| v.modifiers.shift).collect();
+    assert_eq!(&shift[..], &[true, true, true, true, false, false]);
+    assert!(frames[0].windows(2).all(|v| v[0].time <= v[1].time));
+
+    // Modifiers are attached to mouse events too.
+    assert_eq!(frames[1].len(), 2);
+    assert_eq!(
+        frames[1][1].modifiers,
+        Modifiers {
+            ctrl: true,
+            ..Modifiers::default()
+        }
+    );
+
+    assert!(frames[1][0].time >= frames[0][5].time);
+    assert!(frames[2].is_empty());
+    assert_eq!(input::events().count(), 0);
+}
+
+#[test]
+fn queue() {
+    let mut params = Params::default();
+    params.fake_clock = Some(Duration::from_millis(10));
+    application::setup_headless(params).unwrap();
+    events();
+}
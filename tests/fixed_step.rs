@@ -0,0 +1,65 @@
+extern crate crayon;
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crayon::application;
+use crayon::application::prelude::*;
+
+struct Counter {
+    fixed_updates: Arc<AtomicUsize>,
+}
+
+impl LifecycleListener for Counter {
+    fn on_fixed_update(&mut self) -> crayon::errors::Result<()> {
+        self.fixed_updates.fetch_add(1, Ordering::SeqCst);
+        Ok(())
+    }
+}
+
+#[test]
+fn fixed_step() {
+    let mut params = Params::default();
+    params.fake_clock = Some(Duration::from_millis(10));
+    params.fixed_timestep = Duration::from_millis(4);
+    params.max_fixed_steps = 0;
+    application::setup_headless(params).unwrap();
+
+    let fixed_updates = Arc::new(AtomicUsize::new(0));
+    let lis = application::attach(Counter {
+        fixed_updates: fixed_updates.clone(),
+    });
+
+    // The remaining 2ms is accumulated into the next frame.
+    application::run_frames(1).unwrap();
+    assert_eq!(application::fixed_steps(), 2);
+    assert_eq!(application::fixed_alpha(), 0.5);
+    assert_eq!(fixed_updates.load(Ordering::SeqCst), 2);
+
+    application::run_frames(1).unwrap();
+    assert_eq!(application::fixed_steps(), 3);
+    assert_eq!(application::fixed_alpha(), 0.0);
+    assert_eq!(fixed_updates.load(Ordering::SeqCst), 5);
+
+    // The time we could not catch up with is dropped.
+    application::set_max_fixed_steps(1);
+    application::run_frames(1).unwrap();
+    assert_eq!(application::fixed_steps(), 1);
+    assert_eq!(application::fixed_alpha(), 0.5);
+    assert_eq!(fixed_updates.load(Ordering::SeqCst), 6);
+
+    application::run_frames(1).unwrap();
+    assert_eq!(application::fixed_steps(), 1);
+    assert_eq!(application::fixed_alpha(), 0.0);
+    assert_eq!(fixed_updates.load(Ordering::SeqCst), 7);
+
+    // The fixed update is disabled if the timestep is zero.
+    application::set_fixed_timestep(Duration::from_secs(0));
+    application::run_frames(3).unwrap();
+    assert_eq!(application::fixed_steps(), 0);
+    assert_eq!(application::fixed_alpha(), 0.0);
+    assert_eq!(fixed_updates.load(Ordering::SeqCst), 7);
+
+    application::detach(lis);
+}
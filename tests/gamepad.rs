@@ -0,0 +1,76 @@
+extern crate crayon;
+
+use std::time::Duration;
+
+use crayon::application;
+use crayon::input;
+use crayon::input::evdev::codes::*;
+use crayon::input::evdev::VirtualGamepad;
+use crayon::input::prelude::*;
+
+fn gamepads() {
+    let pad = VirtualGamepad::new("Virtual Pad");
+    input::attach_virtual_gamepad(pad.clone());
+    assert!(!input::has_gamepad_attached());
+
+    application::run_frames(1).unwrap();
+    assert_eq!(input::gamepads(), vec![GamepadId(0)]);
+
+    let id = GamepadId(0);
+    assert_eq!(input::gamepad_name(id), Some("Virtual Pad".to_owned()));
+
+    // Buttons.
+    pad.press(BTN_SOUTH);
+    pad.move_axis(ABS_HAT0Y, -1);
+    application::run_frames(1).unwrap();
+    assert!(input::is_gamepad_button_down(id, GamepadButton::South));
+    assert!(input::is_gamepad_button_down(id, GamepadButton::DPadUp));
+
+    pad.release(BTN_SOUTH);
+    pad.move_axis(ABS_HAT0Y, 1);
+    application::run_frames(1).unwrap();
+    assert!(!input::is_gamepad_button_down(id, GamepadButton::South));
+    assert!(!input::is_gamepad_button_down(id, GamepadButton::DPadUp));
+    assert!(input::is_gamepad_button_down(id, GamepadButton::DPadDown));
+
+    // Axes with dead zones.
+    pad.move_axis(ABS_X, 3000);
+    pad.move_axis(ABS_Y, -32768);
+    pad.move_axis(ABS_RZ, 255);
+    application::run_frames(1).unwrap();
+
+    let stick = input::gamepad_left_stick(id);
+    assert!(stick.y > 0.99);
+    assert!(stick.x > 0.0 && stick.x < 0.1);
+    assert!((input::gamepad_axis(id, GamepadAxis::RightTrigger) - 1.0).abs() < 1e-4);
+
+    pad.move_axis(ABS_Y, 0);
+    application::run_frames(1).unwrap();
+    assert_eq!(input::gamepad_axis(id, GamepadAxis::LeftStickX), 0.0);
+
+    // Rumble.
+    input::gamepad_rumble(id, 2.0, 0.5, Duration::from_millis(100)).unwrap();
+    assert_eq!(pad.rumble(), Some((1.0, 0.5, Duration::from_millis(100))));
+
+    // Identifiers are reused after disconnection.
+    let pad2 = VirtualGamepad::new("Virtual Pad 2");
+    input::attach_virtual_gamepad(pad2.clone());
+    application::run_frames(1).unwrap();
+    assert_eq!(input::gamepads(), vec![GamepadId(0), GamepadId(1)]);
+
+    pad.disconnect();
+    application::run_frames(1).unwrap();
+    assert_eq!(input::gamepads(), vec![GamepadId(1)]);
+    assert!(!input::is_gamepad_connected(id));
+    assert!(input::gamepad_rumble(id, 1.0, 1.0, Duration::from_millis(1)).is_err());
+
+    input::attach_virtual_gamepad(VirtualGamepad::new("Virtual Pad 3"));
+    application::run_frames(1).unwrap();
+    assert_eq!(input::gamepads(), vec![GamepadId(0), GamepadId(1)]);
+}
+
+#[test]
+fn gamepad() {
+    application::oneshot().unwrap();
+    gamepads();
+}
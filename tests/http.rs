@@ -0,0 +1,251 @@
+#![cfg(not(target_arch = "wasm32"))]
+
+extern crate crayon;
+
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use crayon::res::request::Response;
+use crayon::res::url::Url;
+use crayon::res::vfs::http::{Http, HttpParams};
+use crayon::res::vfs::VFS;
+use crayon::sched::prelude::LockLatch;
+
+const BODY: &[u8] = b"0123456789";
+const ETAG: &str = "\"v1\"";
+
+#[derive(Default)]
+struct Counters {
+    connections: AtomicUsize,
+    requests: AtomicUsize,
+    // Drops the next N connections without response.
+    drops: AtomicUsize,
+    // Responses the next N partial requests with empty body.
+    empties: AtomicUsize,
+}
+
+// A stand-in asset server which serves `BODY` at any path, except the empty file at `/empty`.
+fn serve(counters: Arc<Counters>) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let stream = stream.unwrap();
+            counters.connections.fetch_add(1, Ordering::SeqCst);
+
+            if counters.drops.load(Ordering::SeqCst) > 0 {
+                counters.drops.fetch_sub(1, Ordering::SeqCst);
+                continue;
+            }
+
+            let counters = counters.clone();
+            thread::spawn(move || handle(stream, &counters));
+        }
+    });
+
+    format!("http://{}", addr)
+}
+
+fn handle(stream: TcpStream, counters: &Counters) {
+    let mut reader = BufReader::new(stream);
+
+    loop {
+        let mut headers = Vec::new();
+        loop {
+            let mut line = String::new();
+            if reader.read_line(&mut line).unwrap_or(0) == 0 {
+                return;
+            }
+
+            if line.trim_end().is_empty() {
+                break;
+            }
+
+            headers.push(line.trim_end().to_owned());
+        }
+
+        counters.requests.fetch_add(1, Ordering::SeqCst);
+
+        let header = |name: &str| {
+            headers
+                .iter()
+                .find(|v| v.to_lowercase().starts_with(name))
+                .map(|v| v[name.len()..].trim().to_owned())
+        };
+
+        let empty = headers
+            .first()
+            .map_or(false, |v| v.starts_with("GET /empty "));
+        let rsp = if empty {
+            if header("range:").is_some() {
+                b"HTTP/1.1 416 Range Not Satisfiable\r\nContent-Range: bytes */0\r\nContent-Length: 0\r\n\r\n".to_vec()
+            } else {
+                b"HTTP/1.1 200 OK\r\nContent-Length: 0\r\n\r\n".to_vec()
+            }
+        } else if header("if-none-match:").as_ref().map(|v| v.as_str()) == Some(ETAG) {
+            format!("HTTP/1.1 304 Not Modified\r\nETag: {}\r\n\r\n", ETAG).into_bytes()
+        } else if let Some(range) = header("range:") {
+            let mut iter = range["bytes=".len()..].split('-');
+            let start: usize = iter.next().unwrap().parse().unwrap();
+            let end = iter
+                .next()
+                .and_then(|v| v.parse::<usize>().ok())
+                .unwrap_or(BODY.len() - 1)
+                .min(BODY.len() - 1);
+
+            if counters.empties.load(Ordering::SeqCst) > 0 {
+                counters.empties.fetch_sub(1, Ordering::SeqCst);
+                let rsp = format!(
+                    "HTTP/1.1 206 Partial Content\r\nETag: {}\r\nContent-Range: bytes {}-{}/{}\r\nContent-Length: 0\r\n\r\n",
+                    ETAG,
+                    start,
+                    end,
+                    BODY.len()
+                );
+
+                if reader.get_mut().write_all(rsp.as_bytes()).is_err() {
+                    return;
+                }
+
+                continue;
+            }
+
+            let mut rsp = format!(
+                "HTTP/1.1 206 Partial Content\r\nETag: {}\r\nContent-Range: bytes {}-{}/{}\r\nContent-Length: {}\r\n\r\n",
+                ETAG,
+                start,
+                end,
+                BODY.len(),
+                end + 1 - start
+            )
+            .into_bytes();
+            rsp.extend_from_slice(&BODY[start..=end]);
+            rsp
+        } else {
+            let mut rsp = format!(
+                "HTTP/1.1 200 OK\r\nETag: {}\r\nContent-Length: {}\r\n\r\n",
+                ETAG,
+                BODY.len()
+            )
+            .into_bytes();
+            rsp.extend_from_slice(BODY);
+            rsp
+        };
+
+        if reader.get_mut().write_all(&rsp).is_err() {
+            return;
+        }
+    }
+}
+
+fn fetch(http: &Http, url: &str) -> Response {
+    let latch = Arc::new(LockLatch::new());
+    http.request(&Url::new(url).unwrap(), latch.clone());
+    latch.take()
+}
+
+#[test]
+fn keep_alive() {
+    let counters = Arc::new(Counters::default());
+    let host = serve(counters.clone());
+    let http = Http::new();
+
+    for _ in 0..3 {
+        let bytes = fetch(&http, &format!("{}/a.bin", host)).unwrap();
+        assert_eq!(&*bytes, BODY);
+    }
+
+    assert_eq!(counters.requests.load(Ordering::SeqCst), 3);
+    assert_eq!(counters.connections.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn range() {
+    let counters = Arc::new(Counters::default());
+    let host = serve(counters.clone());
+
+    let mut params = HttpParams::default();
+    params.chunk_size = Some(4);
+    let http = Http::with_params(params);
+
+    let bytes = fetch(&http, &format!("{}/a.bin", host)).unwrap();
+    assert_eq!(&*bytes, BODY);
+    assert_eq!(counters.requests.load(Ordering::SeqCst), 3);
+}
+
+#[test]
+fn retries() {
+    let counters = Arc::new(Counters::default());
+    counters.drops.store(2, Ordering::SeqCst);
+    let host = serve(counters.clone());
+
+    let bytes = fetch(&Http::new(), &format!("{}/a.bin", host)).unwrap();
+    assert_eq!(&*bytes, BODY);
+    assert_eq!(counters.connections.load(Ordering::SeqCst), 3);
+
+    let mut params = HttpParams::default();
+    params.retries = 0;
+    counters.drops.store(1, Ordering::SeqCst);
+    assert!(fetch(&Http::with_params(params), &format!("{}/a.bin", host)).is_err());
+}
+
+#[test]
+fn empty_partial_content() {
+    let counters = Arc::new(Counters::default());
+    counters.empties.store(2, Ordering::SeqCst);
+    let host = serve(counters.clone());
+
+    let mut params = HttpParams::default();
+    params.chunk_size = Some(4);
+    params.retry_delay = Duration::from_millis(10);
+
+    let bytes = fetch(&Http::with_params(params.clone()), &format!("{}/a.bin", host)).unwrap();
+    assert_eq!(&*bytes, BODY);
+    assert_eq!(counters.requests.load(Ordering::SeqCst), 5);
+
+    params.retries = 1;
+    counters.empties.store(2, Ordering::SeqCst);
+    assert!(fetch(&Http::with_params(params), &format!("{}/a.bin", host)).is_err());
+}
+
+#[test]
+fn empty_file() {
+    let counters = Arc::new(Counters::default());
+    let host = serve(counters.clone());
+
+    let bytes = fetch(&Http::new(), &format!("{}/empty", host)).unwrap();
+    assert!(bytes.is_empty());
+
+    let mut params = HttpParams::default();
+    params.chunk_size = Some(4);
+    let bytes = fetch(&Http::with_params(params), &format!("{}/empty", host)).unwrap();
+    assert!(bytes.is_empty());
+    assert_eq!(counters.requests.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn cache() {
+    let counters = Arc::new(Counters::default());
+    let host = serve(counters.clone());
+
+    let dir = std::env::temp_dir().join(format!("crayon-http-cache-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+
+    let mut params = HttpParams::default();
+    params.cache = Some(dir.clone());
+
+    let bytes = fetch(&Http::with_params(params.clone()), &format!("{}/a.bin", host)).unwrap();
+    assert_eq!(&*bytes, BODY);
+
+    // The second request is validated with ETag, and the server responses 304 without body.
+    let bytes = fetch(&Http::with_params(params), &format!("{}/a.bin", host)).unwrap();
+    assert_eq!(&*bytes, BODY);
+    assert_eq!(counters.requests.load(Ordering::SeqCst), 2);
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
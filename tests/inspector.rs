@@ -0,0 +1,163 @@
+extern crate crayon;
+extern crate failure;
+extern crate serde_json;
+
+use std::net::TcpStream;
+use std::sync::{Arc, Mutex};
+
+use crayon::application;
+use crayon::application::ins::{self, Inspectable, InspectableInfo, Request, Response};
+use crayon::application::prelude::*;
+use crayon::errors::*;
+
+use serde_json::{json, Value};
+
+#[derive(Default)]
+struct Tunables {
+    speed: Mutex<f64>,
+}
+
+impl Inspectable for Tunables {
+    fn inspect(&self) -> Value {
+        json!({ "speed": *self.speed.lock().unwrap() })
+    }
+
+    fn commands(&self) -> Vec<String> {
+        vec!["set_speed".to_owned()]
+    }
+
+    fn command(&self, name: &str, args: &Value) -> Result<Value> {
+        match (name, args.as_f64()) {
+            ("set_speed", Some(v)) => {
+                *self.speed.lock().unwrap() = v;
+                Ok(Value::Null)
+            }
+            _ => Err(failure::format_err!("Invalid command {}.", name)),
+        }
+    }
+}
+
+fn request(stream: &mut TcpStream, request: Request) -> Response {
+    ins::write_message(stream, &request).unwrap();
+    ins::read_message(stream).unwrap()
+}
+
+fn snapshot(speed: f64) -> Response {
+    Response::Snapshot {
+        name: "tunables".to_owned(),
+        value: json!({ "speed": speed }),
+    }
+}
+
+fn inspect() {
+    let tunables = Arc::new(Tunables::default());
+    application::attach_inspectable("tunables", tunables.clone());
+
+    let addr = application::inspector_addr().unwrap();
+    let mut stream = TcpStream::connect(addr).unwrap();
+
+    let inspectables = match request(&mut stream, Request::List) {
+        Response::List { inspectables } => inspectables,
+        v => panic!("{:?}", v),
+    };
+
+    assert!(inspectables.contains(&InspectableInfo {
+        name: "tunables".to_owned(),
+        commands: vec!["set_speed".to_owned()],
+    }));
+
+    // The core systems are inspectable by default.
+    for name in &["input", "res", "sched", "time", "video"] {
+        assert!(inspectables.iter().any(|v| v.name == *name));
+    }
+
+    match request(
+        &mut stream,
+        Request::Inspect {
+            name: "time".to_owned(),
+        },
+    ) {
+        Response::Snapshot { value, .. } => assert!(value["time_scale"].is_number()),
+        v => panic!("{:?}", v),
+    }
+
+    let name = "tunables".to_owned();
+    assert_eq!(
+        request(&mut stream, Request::Inspect { name: name.clone() }),
+        snapshot(0.0)
+    );
+
+    // Commands are executed with JSON arguments.
+    let rsp = request(
+        &mut stream,
+        Request::Command {
+            name: name.clone(),
+            command: "set_speed".to_owned(),
+            args: json!(2.5),
+        },
+    );
+
+    match rsp {
+        Response::CommandResult { result, .. } => assert_eq!(result, Ok(Value::Null)),
+        v => panic!("{:?}", v),
+    }
+
+    assert_eq!(*tunables.speed.lock().unwrap(), 2.5);
+
+    let rsp = request(
+        &mut stream,
+        Request::Command {
+            name: name.clone(),
+            command: "toggle".to_owned(),
+            args: Value::Null,
+        },
+    );
+
+    match rsp {
+        Response::CommandResult { result, .. } => assert!(result.is_err()),
+        v => panic!("{:?}", v),
+    }
+
+    // Snapshots are sent periodically after subscribing.
+    let subscribe = Request::Subscribe {
+        name: name.clone(),
+        interval_ms: 10,
+    };
+
+    assert_eq!(request(&mut stream, subscribe), snapshot(2.5));
+    *tunables.speed.lock().unwrap() = 5.0;
+
+    loop {
+        let rsp: Response = ins::read_message(&mut stream).unwrap();
+        if rsp == snapshot(5.0) {
+            break;
+        }
+
+        assert_eq!(rsp, snapshot(2.5));
+    }
+
+    ins::write_message(&mut stream, &Request::Unsubscribe { name: name.clone() }).unwrap();
+    loop {
+        match ins::read_message(&mut stream).unwrap() {
+            Response::Unsubscribed { name } => {
+                assert_eq!(name, "tunables");
+                break;
+            }
+            v => assert_eq!(v, snapshot(5.0)),
+        }
+    }
+
+    application::detach_inspectable("tunables");
+    match request(&mut stream, Request::Inspect { name }) {
+        Response::Error { .. } => {}
+        v => panic!("{:?}", v),
+    }
+}
+
+#[test]
+fn inspector() {
+    let mut params = Params::default();
+    params.inspector = Some(0);
+    application::setup_headless(params).unwrap();
+    inspect();
+}
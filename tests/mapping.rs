@@ -0,0 +1,107 @@
+extern crate crayon;
+
+use crayon::application;
+use crayon::input;
+use crayon::input::evdev::codes::*;
+use crayon::input::evdev::VirtualGamepad;
+use crayon::input::prelude::*;
+
+fn profile() -> BindingProfile {
+    let mut gameplay = BindingSet::new("gameplay");
+    gameplay
+        .bind_action("jump", ActionBinding::Key(Key::Space))
+        .bind_action("jump", ActionBinding::Gamepad(GamepadButton::South))
+        .bind_axis("move_x", AxisBinding::keys(Key::A, Key::D))
+        .bind_axis(
+            "move_x",
+            AxisBinding::new(AxisSource::Gamepad(GamepadAxis::LeftStickX)),
+        );
+
+    let mut menu = BindingSet::new("menu");
+    menu.bind_action("jump", ActionBinding::Gamepad(GamepadButton::East))
+        .bind_action("back", ActionBinding::Gamepad(GamepadButton::East));
+
+    let mut profile = BindingProfile::default();
+    profile.contexts.push(gameplay);
+    profile.contexts.push(menu);
+    profile
+}
+
+fn serialization() {
+    let profile = profile();
+    let json = profile.to_json().unwrap();
+    assert_eq!(BindingProfile::from_json(&json).unwrap(), profile);
+
+    // Scales are optional.
+    let json = r#"{
+        "contexts": [{
+            "name": "gameplay",
+            "axes": { "move_y": [{ "source": "WheelY" }] }
+        }]
+    }"#;
+
+    let profile = BindingProfile::from_json(json).unwrap();
+    let bindings = &profile.context("gameplay").unwrap().axes["move_y"];
+    assert_eq!(bindings, &[AxisBinding::new(AxisSource::WheelY)]);
+    assert!(profile.gamepad.is_none());
+}
+
+fn actions_and_axes() {
+    let pad = VirtualGamepad::new("Virtual Pad");
+    input::attach_virtual_gamepad(pad.clone());
+    input::set_binding_profile(profile());
+    application::run_frames(1).unwrap();
+
+    // Nothing is bound until a context is activated.
+    assert!(input::push_binding_context("undefined").is_err());
+    pad.press(BTN_SOUTH);
+    application::run_frames(1).unwrap();
+    assert!(!input::is_action_down("jump"));
+
+    input::push_binding_context("gameplay").unwrap();
+    assert!(input::is_action_down("jump"));
+    assert!(!input::is_action_down("back"));
+
+    pad.move_axis(ABS_X, -32768);
+    application::run_frames(1).unwrap();
+    assert!(input::axis("move_x") < -0.99);
+
+    // The top-most context which binds the name wins.
+    input::push_binding_context("menu").unwrap();
+    assert!(!input::is_action_down("jump"));
+    assert!(input::axis("move_x") < -0.99);
+
+    pad.press(BTN_EAST);
+    application::run_frames(1).unwrap();
+    assert!(input::is_action_down("jump"));
+    assert!(input::is_action_down("back"));
+
+    assert!(input::pop_binding_context("menu"));
+    assert!(!input::pop_binding_context("menu"));
+    assert!(!input::is_action_down("back"));
+
+    // Rebinds at runtime.
+    input::update_binding_profile(|profile| {
+        let gameplay = profile.context_mut("gameplay").unwrap();
+        gameplay.unbind_axis("move_x", AxisSource::Gamepad(GamepadAxis::LeftStickX));
+        gameplay.bind_axis(
+            "move_x",
+            AxisBinding::new(AxisSource::Gamepad(GamepadAxis::LeftStickX)).with_scale(-0.5),
+        );
+    });
+
+    assert!((input::axis("move_x") - 0.5).abs() < 1e-4);
+    assert!(input::binding_profile().context("gameplay").is_some());
+
+    // Profiles are bound to specified gamepad.
+    input::update_binding_profile(|profile| profile.gamepad = Some(GamepadId(1)));
+    assert!(!input::is_action_down("jump"));
+    assert_eq!(input::axis("move_x"), 0.0);
+}
+
+#[test]
+fn mapping() {
+    application::oneshot().unwrap();
+    serialization();
+    actions_and_axes();
+}
@@ -0,0 +1,75 @@
+#[macro_use]
+extern crate crayon;
+extern crate serde_json;
+
+use crayon::prof;
+use crayon::sched;
+use crayon::sched::prelude::*;
+
+fn events() -> Vec<serde_json::Value> {
+    let mut bytes = Vec::new();
+    prof::write_chrome_trace(&mut bytes).unwrap();
+
+    let trace: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+    trace["traceEvents"].as_array().unwrap().clone()
+}
+
+fn find<'a>(events: &'a [serde_json::Value], name: &str) -> Option<&'a serde_json::Value> {
+    events.iter().find(|v| v["name"] == name)
+}
+
+#[test]
+fn chrome_trace() {
+    crayon::application::oneshot().unwrap();
+
+    // Nothing is recorded until enabled.
+    {
+        profile_scope!("disabled");
+    }
+    assert_eq!(prof::len(), 0);
+
+    prof::set_enabled(true);
+    assert!(prof::enabled());
+
+    {
+        profile_scope!("outer");
+        let _inner = prof::scope(format!("inner-{}", 1));
+        prof::instant("marker");
+    }
+
+    sched::spawn_with_handle(|| {
+        profile_scope!("in-job");
+    })
+    .wait();
+
+    let mut graph = TaskGraph::new();
+    graph.add("physics", Access::new(), || {});
+    graph.run();
+
+    let events = events();
+    let outer = find(&events, "outer").unwrap();
+    let inner = find(&events, "inner-1").unwrap();
+    assert_eq!(outer["ph"], "X");
+    assert_eq!(outer["cat"], "user");
+    assert!(inner["ts"].as_u64() >= outer["ts"].as_u64());
+    assert!(outer["dur"].as_u64().is_some());
+
+    let marker = find(&events, "marker").unwrap();
+    assert_eq!(marker["ph"], "i");
+
+    let job = find(&events, "in-job").unwrap();
+    assert_ne!(job["tid"], outer["tid"]);
+    assert_eq!(find(&events, "job").unwrap()["cat"], "sched");
+    assert_eq!(find(&events, "physics").unwrap()["cat"], "graph");
+
+    // Every thread is named with a metadata event.
+    let threads: Vec<_> = events.iter().filter(|v| v["ph"] == "M").collect();
+    assert!(threads.len() >= 2);
+    let worker = threads.iter().find(|v| v["tid"] == job["tid"]).unwrap();
+    let name = worker["args"]["name"].as_str().unwrap();
+    assert!(name.starts_with("crayon-sched-"));
+
+    prof::set_enabled(false);
+    prof::clear();
+    assert_eq!(prof::len(), 0);
+}
@@ -0,0 +1,83 @@
+extern crate crayon;
+
+use crayon::application;
+use crayon::input;
+use crayon::input::evdev::codes::*;
+use crayon::input::evdev::VirtualGamepad;
+use crayon::input::prelude::*;
+
+fn record(pad: &VirtualGamepad) -> InputRecording {
+    input::start_recording();
+    assert!(input::is_recording());
+
+    pad.press(BTN_SOUTH);
+    pad.move_axis(ABS_RZ, 255);
+    application::run_frames(1).unwrap();
+
+    pad.release(BTN_SOUTH);
+    application::run_frames(2).unwrap();
+
+    let recording = input::stop_recording().unwrap();
+    assert!(!input::is_recording());
+    assert!(input::stop_recording().is_none());
+    recording
+}
+
+fn playback() {
+    let pad = VirtualGamepad::new("Virtual Pad");
+    input::attach_virtual_gamepad(pad.clone());
+    application::run_frames(1).unwrap();
+
+    let id = GamepadId(0);
+    let recording = record(&pad);
+    assert_eq!(recording.frames, 3);
+    assert_eq!(recording.events.len(), 4);
+    assert_eq!(
+        recording.events[0].event,
+        InputEvent::GamepadConnected { id }
+    );
+    assert_eq!(recording.events[3].frame, 1);
+
+    let json = recording.to_json().unwrap();
+    let recording = InputRecording::from_json(&json).unwrap();
+
+    // Input states are reset, and live inputs are ignored during playback.
+    input::play_recording(recording.clone());
+    assert!(input::is_playing());
+    assert!(!input::is_gamepad_connected(id));
+
+    pad.press(BTN_EAST);
+    application::run_frames(1).unwrap();
+    assert!(input::is_gamepad_button_down(id, GamepadButton::South));
+    assert!(!input::is_gamepad_button_down(id, GamepadButton::East));
+    assert!((input::gamepad_axis(id, GamepadAxis::RightTrigger) - 1.0).abs() < 1e-4);
+
+    application::run_frames(1).unwrap();
+    assert!(!input::is_gamepad_button_down(id, GamepadButton::South));
+    assert!(input::is_playing());
+
+    // Live inputs are resumed after the playback is finished.
+    application::run_frames(1).unwrap();
+    assert!(!input::is_playing());
+    assert!(input::is_gamepad_connected(id));
+    assert_eq!(input::gamepad_axis(id, GamepadAxis::RightTrigger), 0.0);
+
+    pad.release(BTN_EAST);
+    pad.press(BTN_NORTH);
+    application::run_frames(1).unwrap();
+    assert!(input::is_gamepad_button_down(id, GamepadButton::North));
+
+    // Stops the playback manually.
+    input::play_recording(recording);
+    application::run_frames(1).unwrap();
+    assert!(input::stop_playback());
+    assert!(!input::stop_playback());
+    assert!(!input::is_gamepad_button_down(id, GamepadButton::South));
+    assert!(input::is_gamepad_connected(id));
+}
+
+#[test]
+fn record_and_playback() {
+    application::oneshot().unwrap();
+    playback();
+}
@@ -0,0 +1,124 @@
+#[macro_use]
+extern crate crayon;
+
+use std::cell::RefCell;
+
+use crayon::bincode;
+use crayon::errors::Result;
+use crayon::res::manifest::{ManfiestResolver, Manifest, ManifestItem, MAGIC};
+use crayon::res::utils::prelude::*;
+use crayon::sched::prelude::LatchProbe;
+use crayon::utils::prelude::{FastHashMap, HandleLike};
+use crayon::uuid::Uuid;
+
+impl_handle!(DummyHandle);
+
+thread_local! {
+    static STATES: RefCell<FastHashMap<DummyHandle, ResourceState>> = RefCell::new(FastHashMap::default());
+}
+
+impl ResourceHandle for DummyHandle {
+    fn create_from_uuid(_: Uuid) -> Result<Self> {
+        STATES.with(|v| {
+            let mut v = v.borrow_mut();
+            let handle = DummyHandle::new(v.len() as u32 + 1, 1);
+            v.insert(handle, ResourceState::NotReady);
+            Ok(handle)
+        })
+    }
+
+    fn state(self) -> ResourceState {
+        STATES.with(|v| {
+            v.borrow()
+                .get(&self)
+                .cloned()
+                .unwrap_or(ResourceState::NotReady)
+        })
+    }
+
+    fn delete(self) {
+        STATES.with(|v| v.borrow_mut().remove(&self));
+    }
+}
+
+fn set_state(handle: DummyHandle, state: ResourceState) {
+    STATES.with(|v| v.borrow_mut().insert(handle, state));
+}
+
+fn alive() -> usize {
+    STATES.with(|v| v.borrow().len())
+}
+
+#[test]
+fn basic() {
+    let group = ResourceGroup::new();
+    assert!(group.is_empty());
+    assert!(group.is_set());
+    assert_eq!(group.state(), ResourceState::Ok);
+    assert_eq!(group.progress(), 1.0);
+
+    let h1: DummyHandle = group.add_uuid(Uuid::nil()).unwrap();
+    let h2: DummyHandle = group.add_uuid(Uuid::nil()).unwrap();
+    assert_eq!(group.len(), 2);
+    assert!(group.contains(Uuid::nil()));
+    assert!(!group.is_set());
+    assert_eq!(group.state(), ResourceState::NotReady);
+    assert_eq!(group.progress(), 0.0);
+
+    set_state(h1, ResourceState::Ok);
+    assert!(!group.is_set());
+    assert_eq!(group.progress(), 0.5);
+
+    set_state(h2, ResourceState::Err);
+    assert!(group.is_set());
+    assert_eq!(group.state(), ResourceState::Err);
+    assert_eq!(group.progress(), 1.0);
+
+    group.release();
+    assert!(group.is_empty());
+    assert_eq!(alive(), 0);
+}
+
+#[test]
+fn release_on_drop() {
+    {
+        let group = ResourceGroup::new();
+        let _: DummyHandle = group.add_uuid(Uuid::nil()).unwrap();
+        let _: DummyHandle = group.add_uuid(Uuid::nil()).unwrap();
+        assert_eq!(alive(), 2);
+    }
+
+    assert_eq!(alive(), 0);
+}
+
+#[test]
+fn find_with_prefix() {
+    let mut manifest = Manifest::new();
+    let names = [
+        "levels/1/a.png",
+        "levels/1/b.obj",
+        "levels/10/c.png",
+        "levels/2/d.png",
+    ];
+    for (i, name) in names.iter().enumerate() {
+        manifest.items.push(ManifestItem {
+            filename: manifest.buf.extend_from_str(*name),
+            dependencies: manifest.buf.extend_from_slice::<usize>(&[]),
+            uuid: Uuid::from_bytes([i as u8 + 1; 16]),
+        });
+    }
+
+    let mut bytes = MAGIC.to_vec();
+    bincode::serialize_into(&mut bytes, &manifest).unwrap();
+
+    let mut resolver = ManfiestResolver::new();
+    resolver.add("res:", &mut bytes.as_slice()).unwrap();
+
+    let items = resolver.find_with_prefix("res:levels/1/");
+    let names: Vec<_> = items.iter().map(|v| v.0.as_str()).collect();
+    assert_eq!(names, vec!["res:levels/1/a.png", "res:levels/1/b.obj"]);
+    assert_eq!(items[1].1, Uuid::from_bytes([2; 16]));
+
+    assert_eq!(resolver.find_with_prefix("res:").len(), 4);
+    assert!(resolver.find_with_prefix("res:levels/3").is_empty());
+}
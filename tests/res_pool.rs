@@ -0,0 +1,183 @@
+#[macro_use]
+extern crate crayon;
+
+use std::fs;
+use std::path::PathBuf;
+use std::thread;
+use std::time::Duration;
+
+use crayon::application;
+use crayon::application::prelude::*;
+use crayon::bincode;
+use crayon::errors::Result;
+use crayon::res::manifest::{Manifest, ManifestItem, MAGIC, NAME};
+use crayon::res::utils::prelude::*;
+use crayon::utils::prelude::HandleLike;
+use crayon::uuid::Uuid;
+use crayon::video;
+use crayon::video::assets::mesh_loader;
+use crayon::video::prelude::*;
+
+impl_handle!(DummyHandle);
+
+#[derive(Clone)]
+struct DummyLoader;
+
+impl ResourceLoader for DummyLoader {
+    type Handle = DummyHandle;
+    type Intermediate = usize;
+    type Resource = usize;
+
+    fn load(&self, _: Self::Handle, bytes: &[u8]) -> Result<Self::Intermediate> {
+        Ok(bytes.len())
+    }
+
+    fn create(&self, _: Self::Handle, item: Self::Intermediate) -> Result<Self::Resource> {
+        Ok(item)
+    }
+
+    fn delete(&self, _: Self::Handle, _: Self::Resource) {}
+
+    fn size(&self, item: &Self::Resource) -> usize {
+        *item
+    }
+}
+
+#[test]
+fn stats() {
+    let mut pool = ResourcePool::new(DummyLoader);
+    assert_eq!(pool.stats(), ResourcePoolStats::default());
+
+    let h1 = pool.create(128).unwrap();
+    let h2 = pool.create(256).unwrap();
+    pool.set_budget(Some(256));
+
+    let stats = pool.stats();
+    assert_eq!(stats.num, 2);
+    assert_eq!(stats.num_loading, 0);
+    assert_eq!(stats.usage, 384);
+    assert_eq!(stats.budget, Some(256));
+
+    // Resources that are not created from file will never be evicted.
+    pool.advance().unwrap();
+    assert_eq!(pool.stats().num_evicted, 0);
+    assert_eq!(pool.resource(h1), Some(&128));
+    assert!(pool.set_cached(h1, true).is_err());
+
+    pool.delete(h2);
+    assert_eq!(pool.stats().num, 1);
+    assert_eq!(pool.stats().usage, 128);
+
+    pool.delete(h1);
+    assert_eq!(pool.stats().usage, 0);
+    assert!(pool.set_cached(h1, false).is_err());
+}
+
+impl_vertex! {
+    Vertex {
+        position => [Position; Float; 2; false],
+    }
+}
+
+fn mesh_bytes() -> Vec<u8> {
+    let verts = [
+        Vertex::new([-1.0, -1.0]),
+        Vertex::new([1.0, -1.0]),
+        Vertex::new([1.0, 1.0]),
+    ];
+
+    let mut params = MeshParams::default();
+    params.num_verts = 3;
+    params.num_idxes = 3;
+    params.layout = Vertex::layout();
+
+    let data = MeshData {
+        vptr: Vertex::encode(&verts[..]).into(),
+        iptr: IndexFormat::encode(&[0u16, 1, 2]).into(),
+    };
+
+    let mut bytes = mesh_loader::MAGIC.to_vec();
+    bincode::serialize_into(&mut bytes, &params).unwrap();
+    bincode::serialize_into(&mut bytes, &data).unwrap();
+    bytes
+}
+
+fn testbed() -> PathBuf {
+    let dir = std::env::temp_dir().join("crayon-res-pool-test");
+    fs::create_dir_all(&dir).unwrap();
+
+    let mut manifest = Manifest::new();
+    for (i, name) in ["a.mesh", "b.mesh"].iter().enumerate() {
+        let uuid = Uuid::from_bytes([i as u8 + 1; 16]);
+        fs::write(dir.join(format!("{:X}", uuid.to_simple())), mesh_bytes()).unwrap();
+
+        manifest.items.push(ManifestItem {
+            filename: manifest.buf.extend_from_str(*name),
+            dependencies: manifest.buf.extend_from_slice::<usize>(&[]),
+            uuid,
+        });
+    }
+
+    let mut bytes = MAGIC.to_vec();
+    bincode::serialize_into(&mut bytes, &manifest).unwrap();
+    fs::write(dir.join(NAME), &bytes).unwrap();
+    dir
+}
+
+fn wait_until<T: Fn() -> bool>(func: T) {
+    for _ in 0..1000 {
+        if func() {
+            return;
+        }
+
+        application::run_frames(1).unwrap();
+        thread::sleep(Duration::from_millis(1));
+    }
+
+    panic!("Timeout.");
+}
+
+#[test]
+fn evict_and_reload() {
+    let dir = testbed();
+    let prefix = format!("file://{}/", dir.display());
+
+    let mut params = Params::default();
+    params.res.dirs.push(prefix.clone());
+    application::setup_headless(params).unwrap();
+
+    let m1 = video::create_mesh_from(format!("{}a.mesh", prefix)).unwrap();
+    let m2 = video::create_mesh_from(format!("{}b.mesh", prefix)).unwrap();
+    wait_until(|| video::mesh_stats().num_loading == 0);
+
+    let size = video::mesh_stats().usage / 2;
+    video::set_mesh_cached(m1, true).unwrap();
+    video::set_mesh_cached(m2, true).unwrap();
+    video::set_mesh_budget(Some(size));
+
+    let surface = video::create_surface(SurfaceParams::default()).unwrap();
+    let mut params = ShaderParams::default();
+    params.attributes = AttributeLayout::build()
+        .with(Attribute::Position, 2)
+        .finish();
+    let shader = video::create_shader(params, String::new(), String::new()).unwrap();
+
+    // The mesh which is drawn every frame should never be evicted.
+    let mut cmds = CommandBuffer::new();
+    for _ in 0..4 {
+        cmds.draw(Draw::new(shader, m1));
+        cmds.submit(surface).unwrap();
+        application::run_frames(1).unwrap();
+    }
+
+    let stats = video::mesh_stats();
+    assert_eq!(stats.num_evicted, 1);
+    assert_eq!(stats.usage, size);
+    assert!(video::mesh(m1).is_some());
+
+    // The evicted mesh is reloaded transparently once its accessed again.
+    assert_eq!(video::mesh_state(m2), ResourceState::NotReady);
+    wait_until(|| video::mesh_state(m2) == ResourceState::Ok);
+
+    fs::remove_dir_all(dir).unwrap();
+}
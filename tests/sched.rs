@@ -0,0 +1,312 @@
+extern crate crayon;
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+use crayon::application;
+use crayon::application::prelude::*;
+use crayon::res::request::Request;
+use crayon::sched;
+use crayon::sched::future::Executor;
+use crayon::sched::prelude::*;
+
+struct Input;
+struct Bodies;
+
+fn handles() {
+    let counter = Arc::new(AtomicUsize::new(0));
+
+    let c1 = counter.clone();
+    let h1 = sched::spawn_with_handle(move || {
+        c1.fetch_add(1, Ordering::SeqCst);
+    });
+
+    let c2 = counter.clone();
+    let h2 = h1.then(move || {
+        assert_eq!(c2.fetch_add(1, Ordering::SeqCst), 1);
+    });
+
+    h2.wait();
+    assert!(h1.is_finished());
+    assert!(h2.is_set());
+    assert_eq!(counter.load(Ordering::SeqCst), 2);
+
+    // Continuations of finished jobs are kicked off immediately.
+    let c3 = counter.clone();
+    h1.then(move || {
+        c3.fetch_add(1, Ordering::SeqCst);
+    })
+    .wait();
+    assert_eq!(counter.load(Ordering::SeqCst), 3);
+}
+
+fn dependencies() {
+    let order = Arc::new(Mutex::new(Vec::new()));
+
+    let deps: Vec<_> = (0..8)
+        .map(|i| {
+            let order = order.clone();
+            sched::spawn_with_handle(move || order.lock().unwrap().push(i))
+        })
+        .collect();
+
+    let o2 = order.clone();
+    let refs: Vec<_> = deps.iter().collect();
+    let last = sched::spawn_after(&refs, move || o2.lock().unwrap().push(8));
+    sched::wait_until(&last);
+
+    let order = order.lock().unwrap();
+    assert_eq!(order.len(), 9);
+    assert_eq!(order[8], 8);
+
+    // Jobs without dependencies are spawned immediately.
+    sched::spawn_after(&[], || {}).wait();
+}
+
+fn wait_in_worker() {
+    let latch = SpinLatch::new();
+
+    sched::scope(|s| {
+        s.spawn(|_| {
+            let inner = sched::spawn_with_handle(|| {});
+            sched::wait_until(&inner);
+            latch.set();
+        });
+    });
+
+    assert!(latch.is_set());
+}
+
+// The main thread helps the default queue while waiting, even if all the workers are busy.
+fn wait_in_main() {
+    let started = Arc::new(AtomicUsize::new(0));
+    let release = Arc::new(LockLatch::new());
+
+    for _ in 0..sched::num_workers() {
+        let started = started.clone();
+        let release = release.clone();
+        sched::spawn(move || {
+            started.fetch_add(1, Ordering::SeqCst);
+            release.wait();
+        });
+    }
+
+    while started.load(Ordering::SeqCst) < sched::num_workers() {
+        std::thread::yield_now();
+    }
+
+    let handle = sched::spawn_with_handle(|| {});
+    sched::wait_until(&handle);
+    release.set();
+}
+
+struct FrameTask {
+    name: &'static str,
+    access: Access,
+    order: Arc<Mutex<Vec<&'static str>>>,
+}
+
+impl LifecycleListener for FrameTask {
+    fn on_update_tasks<'a>(&'a mut self, graph: &mut TaskGraph<'a>) {
+        let name = self.name;
+        let order = &self.order;
+        graph.add(name, self.access.clone(), move || {
+            order.lock().unwrap().push(name);
+        });
+    }
+}
+
+fn frame_tasks() {
+    let order = Arc::new(Mutex::new(Vec::new()));
+
+    let writer = application::attach(FrameTask {
+        name: "physics",
+        access: Access::new().write::<Bodies>(),
+        order: order.clone(),
+    });
+
+    let reader = application::attach(FrameTask {
+        name: "render",
+        access: Access::new().read::<Bodies>(),
+        order: order.clone(),
+    });
+
+    application::run_frames(2).unwrap();
+    application::detach(writer);
+    application::detach(reader);
+
+    // The tasks of listeners are executed once per frame.
+    let order = order.lock().unwrap();
+    assert_eq!(order.len(), 4);
+    for frame in order.chunks(2) {
+        assert!(frame.contains(&"physics") && frame.contains(&"render"));
+    }
+}
+
+fn graph() {
+    let order = Mutex::new(Vec::new());
+    let running = AtomicUsize::new(0);
+
+    {
+        let mut graph = TaskGraph::new();
+        let input = graph.add("input", Access::new().write::<Input>(), || {
+            order.lock().unwrap().push("input");
+        });
+
+        graph.add("physics", Access::new().read::<Input>().write::<Bodies>(), || {
+            running.fetch_add(1, Ordering::SeqCst);
+            order.lock().unwrap().push("physics");
+        });
+
+        graph.add("audio", Access::new().read::<Input>(), || {
+            running.fetch_add(1, Ordering::SeqCst);
+            order.lock().unwrap().push("audio");
+        });
+
+        graph.add("render", Access::new().read::<Bodies>(), || {
+            order.lock().unwrap().push("render");
+        });
+
+        let stats = graph.add("stats", Access::new().after(input), || {
+            order.lock().unwrap().push("stats");
+        });
+
+        assert_eq!(graph.len(), 5);
+        assert_eq!(graph.name(stats), Some("stats"));
+
+        graph.run();
+        graph.run();
+    }
+
+    let order = order.into_inner().unwrap();
+    assert_eq!(order.len(), 10);
+    assert_eq!(running.load(Ordering::SeqCst), 4);
+
+    for frame in order.chunks(5) {
+        let index = |name| frame.iter().position(|v| *v == name).unwrap();
+        assert_eq!(index("input"), 0);
+        assert!(index("physics") < index("render"));
+    }
+}
+
+fn queues() {
+    let name = Arc::new(Mutex::new(String::new()));
+
+    let n2 = name.clone();
+    sched::spawn_on_with_handle("io", move || {
+        *n2.lock().unwrap() = std::thread::current().name().unwrap().to_owned();
+    })
+    .wait();
+
+    // Workers of the default queue never run jobs of low priority queues.
+    assert!(name.lock().unwrap().starts_with("crayon-io-"));
+
+    // Jobs are spawned into the default queue if there is no queue with that name, which
+    // might be helped by the idle workers of low priority queues.
+    let n2 = name.clone();
+    sched::spawn_on_with_handle("unknown", move || {
+        *n2.lock().unwrap() = std::thread::current().name().unwrap().to_owned();
+    })
+    .wait();
+
+    assert!(name.lock().unwrap().starts_with("crayon-"));
+}
+
+fn par() {
+    let mut items: Vec<usize> = vec![0; 10000];
+    sched::par::par_for(&mut items, |i, v| *v = i);
+    assert!(items.iter().enumerate().all(|(i, &v)| i == v));
+
+    let mut chunks = Mutex::new(Vec::new());
+    sched::par::par_chunks(&mut items, 100, |offset, chunk| {
+        assert_eq!(chunk[0], offset);
+        chunks.lock().unwrap().push(chunk.len());
+    });
+
+    let chunks = chunks.get_mut().unwrap();
+    assert_eq!(chunks.len(), 100);
+    assert!(chunks.iter().all(|&v| v == 100));
+
+    let squares = sched::par::par_map(&items, |&v| v * v);
+    assert!(squares.iter().enumerate().all(|(i, &v)| i * i == v));
+
+    // Small slices are processed on the current thread directly.
+    let mut small = vec![0; 3];
+    sched::par::par_for(&mut small, |i, v| *v = i + 1);
+    assert_eq!(small, vec![1, 2, 3]);
+}
+
+fn futures() {
+    let executor = Arc::new(Executor::new());
+    let latch = Arc::new(LockLatch::new());
+    let steps = Arc::new(AtomicUsize::new(0));
+
+    let l2 = latch.clone();
+    let s2 = steps.clone();
+    let handle = executor.spawn(async move {
+        let rsp = Request::ok(vec![1, 2, 3]).await;
+        assert_eq!(&rsp.unwrap()[..], &[1, 2, 3]);
+        s2.fetch_add(1, Ordering::SeqCst);
+
+        (&*l2).await;
+        s2.fetch_add(1, Ordering::SeqCst);
+
+        sched::spawn_with_handle(|| {}).await;
+        sched::delay(std::time::Duration::from_millis(0)).await;
+        s2.fetch_add(1, Ordering::SeqCst);
+    });
+
+    assert_eq!(executor.len(), 1);
+    executor.advance();
+    assert_eq!(steps.load(Ordering::SeqCst), 1);
+
+    // Futures are polled once per advance.
+    executor.advance();
+    assert_eq!(steps.load(Ordering::SeqCst), 1);
+    assert!(!handle.is_finished());
+
+    (*latch).set(());
+    for _ in 0..1000 {
+        if handle.is_finished() {
+            break;
+        }
+
+        executor.advance();
+        std::thread::yield_now();
+    }
+
+    assert!(handle.is_finished());
+    assert!(executor.is_empty());
+    assert_eq!(steps.load(Ordering::SeqCst), 3);
+
+    // Futures could spawn new futures or query the executor while being polled.
+    let e2 = executor.clone();
+    let s2 = steps.clone();
+    executor.spawn(async move {
+        e2.spawn(async move {
+            s2.fetch_add(1, Ordering::SeqCst);
+        });
+
+        assert_eq!(e2.len(), 1);
+    });
+
+    executor.advance();
+    executor.advance();
+    assert!(executor.is_empty());
+    assert_eq!(steps.load(Ordering::SeqCst), 4);
+}
+
+#[test]
+fn sched() {
+    crayon::application::oneshot().unwrap();
+
+    handles();
+    dependencies();
+    wait_in_worker();
+    wait_in_main();
+    graph();
+    frame_tasks();
+    queues();
+    par();
+    futures();
+}
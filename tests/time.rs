@@ -0,0 +1,91 @@
+extern crate crayon;
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crayon::application;
+use crayon::application::prelude::*;
+
+fn scale() {
+    assert_eq!(application::time_scale(), 1.0);
+    application::set_time_scale(0.5);
+    assert_eq!(application::time_scale(), 0.5);
+
+    // Negative scales are clamped.
+    application::set_time_scale(-1.0);
+    assert_eq!(application::time_scale(), 0.0);
+    application::set_time_scale(1.0);
+
+    assert!(!application::paused());
+    application::pause();
+    assert!(application::paused());
+    application::resume();
+    assert!(!application::paused());
+}
+
+fn timers() {
+    let fired = Arc::new(AtomicUsize::new(0));
+    let f2 = fired.clone();
+    application::add_timer("once", Timer::once(Duration::from_millis(50)), move || {
+        f2.fetch_add(1, Ordering::SeqCst);
+    });
+
+    let repeated = Arc::new(AtomicUsize::new(0));
+    let r2 = repeated.clone();
+    let timer = Timer::repeat(Duration::from_millis(20)).with_clock(Clock::Real);
+    application::add_timer("repeat", timer, move || {
+        r2.fetch_add(1, Ordering::SeqCst);
+    });
+
+    application::run_frames(10).unwrap();
+    assert_eq!(application::game_time(), Duration::from_millis(100));
+    assert_eq!(application::real_time(), Duration::from_millis(100));
+    assert_eq!(fired.load(Ordering::SeqCst), 1);
+    assert_eq!(repeated.load(Ordering::SeqCst), 5);
+    assert!(application::timer_remaining("once").is_none());
+
+    // The game time freezes when paused, while the timers with real clock keep going.
+    application::add_timer("countdown", Timer::once(Duration::from_millis(30)), || {});
+    application::pause();
+    application::run_frames(10).unwrap();
+    assert_eq!(application::game_time(), Duration::from_millis(100));
+    assert_eq!(application::real_time(), Duration::from_millis(200));
+    assert_eq!(repeated.load(Ordering::SeqCst), 10);
+    assert_eq!(
+        application::timer_remaining("countdown"),
+        Some(Duration::from_millis(30))
+    );
+
+    application::resume();
+    application::set_time_scale(0.5);
+    application::run_frames(10).unwrap();
+    assert_eq!(application::game_time(), Duration::from_millis(150));
+    assert_eq!(application::real_time(), Duration::from_millis(300));
+    assert!(application::timer_remaining("countdown").is_none());
+    application::set_time_scale(1.0);
+
+    // Timers with the same name are replaced.
+    let timer = Timer::repeat(Duration::from_secs(1)).with_clock(Clock::Real);
+    application::add_timer("repeat", timer, || {});
+    application::run_frames(10).unwrap();
+    assert_eq!(repeated.load(Ordering::SeqCst), 15);
+    assert_eq!(
+        application::timer_remaining("repeat"),
+        Some(Duration::from_millis(900))
+    );
+
+    assert!(application::remove_timer("repeat"));
+    assert!(!application::remove_timer("repeat"));
+    assert!(application::timer_remaining("repeat").is_none());
+}
+
+#[test]
+fn time() {
+    let mut params = Params::default();
+    params.fake_clock = Some(Duration::from_millis(10));
+    application::setup_headless(params).unwrap();
+
+    scale();
+    timers();
+}
@@ -0,0 +1,227 @@
+extern crate crayon;
+
+use std::f32::consts::PI;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crayon::application;
+use crayon::application::prelude::*;
+use crayon::input;
+use crayon::input::prelude::*;
+use crayon::math::prelude::Vector2;
+
+#[derive(Default)]
+struct Gestures {
+    long_press: Option<Vector2<f32>>,
+    swipe: Option<(SwipeDirection, Vector2<f32>)>,
+    pinch: Vec<GesturePinch>,
+    rotate: Vec<GestureRotate>,
+}
+
+// Gestures are cleared at the end of every frame, so we collect them during updates.
+struct Collector(Arc<Mutex<Gestures>>);
+
+impl LifecycleListener for Collector {
+    fn on_update(&mut self) -> crayon::errors::Result<()> {
+        let mut gestures = self.0.lock().unwrap();
+
+        if let GestureTap::Action { position } = input::finger_long_press() {
+            gestures.long_press = Some(position);
+        }
+
+        if let GestureSwipe::Action {
+            direction,
+            velocity,
+            ..
+        } = input::finger_swipe()
+        {
+            gestures.swipe = Some((direction, velocity));
+        }
+
+        match input::finger_pinch() {
+            GesturePinch::None => {}
+            v => gestures.pinch.push(v),
+        }
+
+        match input::finger_rotate() {
+            GestureRotate::None => {}
+            v => gestures.rotate.push(v),
+        }
+
+        Ok(())
+    }
+}
+
+// Plays the touch events, one frame per item.
+fn play(frames: &[&[(u8, TouchState, [f32; 2])]]) {
+    let mut recording = InputRecording::default();
+    recording.frames = frames.len() as u64 + 1;
+
+    for (frame, touches) in frames.iter().enumerate() {
+        for &(id, state, position) in touches.iter() {
+            recording.events.push(RecordedEvent {
+                frame: frame as u64,
+                time: Duration::from_millis(100 * frame as u64),
+                event: InputEvent::Touch {
+                    id,
+                    state,
+                    position: position.into(),
+                },
+            });
+        }
+    }
+
+    input::play_recording(recording);
+    application::run_frames(frames.len() + 1).unwrap();
+}
+
+fn long_press(gestures: &Mutex<Gestures>) {
+    play(&[&[(0, TouchState::Start, [10.0, 10.0])], &[], &[]]);
+    assert_eq!(gestures.lock().unwrap().long_press, None);
+
+    play(&[
+        &[(0, TouchState::Start, [10.0, 10.0])],
+        &[],
+        &[],
+        &[],
+        &[],
+        &[],
+        &[],
+    ]);
+    assert_eq!(
+        gestures.lock().unwrap().long_press,
+        Some(Vector2::new(10.0, 10.0))
+    );
+
+    // Moving too far cancels the long press.
+    gestures.lock().unwrap().long_press = None;
+    play(&[
+        &[(1, TouchState::Start, [10.0, 10.0])],
+        &[(1, TouchState::Move, [40.0, 10.0])],
+        &[],
+        &[],
+        &[],
+        &[],
+        &[],
+        &[],
+    ]);
+
+    assert_eq!(gestures.lock().unwrap().long_press, None);
+}
+
+fn swipe(gestures: &Mutex<Gestures>) {
+    play(&[
+        &[(0, TouchState::Start, [100.0, 100.0])],
+        &[(0, TouchState::Move, [100.0, 150.0])],
+        &[(0, TouchState::End, [110.0, 300.0])],
+    ]);
+
+    let (direction, velocity) = gestures.lock().unwrap().swipe.take().unwrap();
+    assert_eq!(direction, SwipeDirection::Up);
+    assert!((velocity.y - 1000.0).abs() < 1e-2);
+
+    // Too slow to be a swipe.
+    play(&[
+        &[(1, TouchState::Start, [100.0, 100.0])],
+        &[],
+        &[],
+        &[],
+        &[],
+        &[],
+        &[(1, TouchState::End, [0.0, 100.0])],
+    ]);
+
+    assert!(gestures.lock().unwrap().swipe.is_none());
+}
+
+fn pinch(gestures: &Mutex<Gestures>) {
+    play(&[
+        &[
+            (0, TouchState::Start, [0.0, 0.0]),
+            (1, TouchState::Start, [100.0, 0.0]),
+        ],
+        &[(1, TouchState::Move, [105.0, 0.0])],
+        &[(1, TouchState::Move, [150.0, 0.0])],
+        // The delta is measured across all the events in one frame.
+        &[
+            (1, TouchState::Move, [180.0, 0.0]),
+            (1, TouchState::Move, [200.0, 0.0]),
+        ],
+        &[(0, TouchState::End, [0.0, 0.0])],
+    ]);
+
+    let gestures = gestures.lock().unwrap();
+    assert_eq!(gestures.pinch.len(), 3);
+
+    match gestures.pinch[0] {
+        GesturePinch::Start { center } => assert_eq!(center, Vector2::new(75.0, 0.0)),
+        v => panic!("{:?}", v),
+    }
+
+    match gestures.pinch[1] {
+        GesturePinch::Move { scale, delta, .. } => {
+            assert!((scale - 2.0).abs() < 1e-4);
+            assert!((delta - 200.0 / 150.0).abs() < 1e-4);
+        }
+        v => panic!("{:?}", v),
+    }
+
+    match gestures.pinch[2] {
+        GesturePinch::End { scale, .. } => assert!((scale - 2.0).abs() < 1e-4),
+        v => panic!("{:?}", v),
+    }
+
+    // Fingers are not rotated.
+    assert!(gestures.rotate.is_empty());
+}
+
+fn rotate(gestures: &Mutex<Gestures>) {
+    play(&[
+        &[
+            (0, TouchState::Start, [0.0, 0.0]),
+            (1, TouchState::Start, [100.0, 0.0]),
+        ],
+        &[(1, TouchState::Move, [0.0, 100.0])],
+        &[(1, TouchState::Move, [-100.0, 0.0])],
+        &[
+            (1, TouchState::Move, [-70.710_68, -70.710_68]),
+            (1, TouchState::Move, [0.0, -100.0]),
+        ],
+        &[(1, TouchState::End, [0.0, -100.0])],
+    ]);
+
+    let gestures = gestures.lock().unwrap();
+    assert_eq!(gestures.rotate.len(), 4);
+
+    match gestures.rotate[2] {
+        GestureRotate::Move { angle, delta, .. } => {
+            // Keeps accumulating across the boundary of PI.
+            assert!((angle - 1.5 * PI).abs() < 1e-4);
+            assert!((delta - 0.5 * PI).abs() < 1e-4);
+        }
+        v => panic!("{:?}", v),
+    }
+
+    match gestures.rotate[3] {
+        GestureRotate::End { angle, .. } => assert!((angle - 1.5 * PI).abs() < 1e-4),
+        v => panic!("{:?}", v),
+    }
+}
+
+#[test]
+fn touchpad() {
+    let mut params = Params::default();
+    params.fake_clock = Some(Duration::from_millis(100));
+    application::setup_headless(params).unwrap();
+
+    let gestures = Arc::new(Mutex::new(Gestures::default()));
+    application::attach(Collector(gestures.clone()));
+
+    long_press(&gestures);
+    swipe(&gestures);
+    pinch(&gestures);
+
+    gestures.lock().unwrap().pinch.clear();
+    gestures.lock().unwrap().rotate.clear();
+    rotate(&gestures);
+}
@@ -0,0 +1,118 @@
+extern crate crayon;
+
+use std::sync::{Arc, Mutex};
+
+use crayon::prelude::*;
+use crayon::window;
+
+struct Recorder {
+    events: Arc<Mutex<Vec<WindowEvent>>>,
+}
+
+impl EventListener for Recorder {
+    fn on(&mut self, v: &Event) -> CrResult<()> {
+        if let Event::Window(v) = *v {
+            self.events.lock().unwrap().push(v);
+        }
+
+        Ok(())
+    }
+}
+
+fn windows() {
+    let w1 = window::create(WindowParams::default()).unwrap();
+    let w2 = window::create(WindowParams::default()).unwrap();
+    assert!(window::alive(w1));
+    assert!(window::alive(w2));
+    assert!(window::window_dimensions(w1).is_some());
+    assert_eq!(window::window_device_pixel_ratio(w2), Some(1.0));
+
+    let mut params = SurfaceParams::default();
+    params.set_window(w1);
+    let surface = video::create_surface(params).unwrap();
+    crayon::application::run_frames(1).unwrap();
+
+    window::delete(w1);
+    assert!(!window::alive(w1));
+    assert!(window::alive(w2));
+    assert!(window::window_dimensions(w1).is_none());
+    assert!(window::window_position(w1).is_none());
+
+    // Deleting twice is fine.
+    window::delete(w1);
+    window::delete(w2);
+    assert!(!window::alive(w2));
+
+    video::delete_surface(surface);
+}
+
+fn modes() {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let lis = window::attach(Recorder {
+        events: events.clone(),
+    });
+
+    assert_eq!(window::mode(), WindowMode::Windowed);
+    window::set_mode(WindowMode::Fullscreen).unwrap();
+    window::set_mode(WindowMode::Fullscreen).unwrap();
+    assert_eq!(window::mode(), WindowMode::Fullscreen);
+
+    window::set_cursor(CursorIcon::Crosshair);
+    assert_eq!(window::cursor(), CursorIcon::Crosshair);
+    window::set_cursor_visible(false);
+    window::set_cursor_grab(true).unwrap();
+    assert!(!window::cursor_visible());
+    assert!(window::cursor_grab());
+
+    window::set_title("Modes");
+    window::set_size((320, 240));
+    crayon::application::run_frames(1).unwrap();
+
+    {
+        let events = events.lock().unwrap();
+        assert_eq!(events.len(), 3);
+
+        match events[0] {
+            WindowEvent::ModeChanged(WindowMode::Fullscreen) => {}
+            _ => panic!("unexpected event {:?}", events[0]),
+        }
+
+        match events[2] {
+            WindowEvent::CursorGrabChanged(true) => {}
+            _ => panic!("unexpected event {:?}", events[2]),
+        }
+    }
+
+    let mut params = TextureParams::default();
+    params.dimensions = (2, 2).into();
+    let data = TextureData {
+        bytes: vec![vec![255; 16].into_boxed_slice()],
+    };
+
+    window::set_icon(&params, &data).unwrap();
+    params.dimensions = (4, 4).into();
+    assert!(window::set_icon(&params, &data).is_err());
+    params.format = TextureFormat::R8;
+    assert!(window::set_icon(&params, &data).is_err());
+    window::clear_icon().unwrap();
+
+    window::detach(lis);
+}
+
+fn text() {
+    assert!(window::clipboard_text().is_none());
+    window::set_clipboard_text("crayon").unwrap();
+    assert_eq!(window::clipboard_text(), Some("crayon".to_owned()));
+
+    window::set_ime_position((10.0, 20.0));
+    crayon::application::run_frames(1).unwrap();
+    assert!(input::ime_composition().is_none());
+}
+
+#[test]
+fn window() {
+    crayon::application::oneshot().unwrap();
+    windows();
+    modes();
+    text();
+}
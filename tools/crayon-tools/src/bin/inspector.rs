@@ -0,0 +1,94 @@
+//! A reference client of the inspector protocol, see `crayon::application::ins`.
+
+use std::net::TcpStream;
+
+use crayon::application::ins::{self, Request, Response};
+
+const USAGE: &str = "Usage: crayon-inspector [--addr 127.0.0.1:9338] <command>
+
+Commands:
+    list                                Lists the registered inspectables.
+    inspect <name>                      Prints a snapshot of inspectable.
+    watch <name> [interval_ms]          Prints the snapshots of inspectable periodically.
+    command <name> <command> [args]     Executes a command with JSON arguments.";
+
+fn main() {
+    env_logger::init();
+
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    if args.is_empty() || args.iter().any(|v| v == "-h" || v == "--help") {
+        println!("{}", USAGE);
+        return;
+    }
+
+    let mut addr = format!("127.0.0.1:{}", ins::PORT);
+    if args[0] == "--addr" && args.len() > 1 {
+        addr = args.remove(1);
+        args.remove(0);
+    }
+
+    if let Err(err) = exec(&addr, &args) {
+        eprintln!("{}", err);
+        std::process::exit(1);
+    }
+}
+
+fn exec(addr: &str, args: &[String]) -> Result<(), failure::Error> {
+    let arg = |i: usize| -> Result<&str, failure::Error> {
+        args.get(i)
+            .map(|v| v.as_str())
+            .ok_or_else(|| failure::format_err!("{}", USAGE))
+    };
+
+    let (request, watch) = match arg(0)? {
+        "list" => (Request::List, false),
+        "inspect" => (
+            Request::Inspect {
+                name: arg(1)?.to_owned(),
+            },
+            false,
+        ),
+        "watch" => (
+            Request::Subscribe {
+                name: arg(1)?.to_owned(),
+                interval_ms: arg(2).unwrap_or("1000").parse()?,
+            },
+            true,
+        ),
+        "command" => (
+            Request::Command {
+                name: arg(1)?.to_owned(),
+                command: arg(2)?.to_owned(),
+                args: serde_json::from_str(arg(3).unwrap_or("null"))?,
+            },
+            false,
+        ),
+        _ => failure::bail!("{}", USAGE),
+    };
+
+    let mut stream = TcpStream::connect(addr)?;
+    ins::write_message(&mut stream, &request)?;
+
+    loop {
+        match ins::read_message(&mut stream)? {
+            Response::List { inspectables } => {
+                for v in inspectables {
+                    println!("{}\t{}", v.name, v.commands.join(", "));
+                }
+            }
+            Response::Snapshot { value, .. } => {
+                println!("{}", serde_json::to_string_pretty(&value)?);
+            }
+            Response::Unsubscribed { .. } => {}
+            Response::CommandResult { result, .. } => match result {
+                Ok(value) => println!("{}", serde_json::to_string_pretty(&value)?),
+                Err(err) => failure::bail!("{}", err),
+            },
+            Response::Error { message } => failure::bail!("{}", message),
+        }
+
+        if !watch {
+            return Ok(());
+        }
+    }
+}
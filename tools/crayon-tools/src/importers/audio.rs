@@ -0,0 +1,44 @@
+//! Imports OGG/WAV files into `CSFX` audio clips.
+//!
+//! The payloads are stored as is, they will be decoded by `AudioClipLoader` at runtime.
+
+use std::fs;
+use std::path::Path;
+
+use crayon::errors::Result;
+use crayon_audio::assets::clip_loader::MAGIC;
+use crayon_audio::assets::wav;
+
+use super::super::meta::Meta;
+use super::ImportedResource;
+
+pub fn import(path: &Path, meta: &mut Meta) -> Result<Vec<ImportedResource>> {
+    let bytes = fs::read(path)?;
+
+    match super::extension(path).as_str() {
+        "ogg" => {
+            if !bytes.starts_with(b"OggS") {
+                bail!("{} is not a valid OGG file.", path.display());
+            }
+        }
+        "wav" => {
+            // Decodes it once to make sure the encoding is supported.
+            wav::decode(&bytes)?;
+        }
+        ext => bail!("Audio with extension '{}' is not supported.", ext),
+    }
+
+    Ok(vec![ImportedResource {
+        uuid: meta.uuid,
+        name: None,
+        bytes: encode(&bytes),
+        dependencies: Vec::new(),
+    }])
+}
+
+/// Encodes the OGG or WAV bytes into `CSFX` format.
+pub fn encode(payload: &[u8]) -> Vec<u8> {
+    let mut bytes = MAGIC.to_vec();
+    bytes.extend_from_slice(payload);
+    bytes
+}
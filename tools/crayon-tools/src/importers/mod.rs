@@ -0,0 +1,69 @@
+//! Importers that compile assets into runtime resources.
+
+pub mod audio;
+pub mod model;
+pub mod texture;
+
+use std::path::Path;
+
+use crayon::errors::Result;
+use uuid::Uuid;
+
+use super::meta::Meta;
+
+/// The kinds of importers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ImporterType {
+    /// Imports PNG/JPEG/BMP/TGA/DDS/KTX images into texture.
+    Texture,
+    /// Imports OBJ/glTF models into prefab, meshes and textures.
+    Transmission,
+    /// Imports OGG/WAV files into audio clip.
+    Audio,
+}
+
+impl ImporterType {
+    /// Returns the default extensions that could be handled by this importer.
+    pub fn extensions(self) -> &'static [&'static str] {
+        match self {
+            ImporterType::Texture => &[".png", ".jpg", ".jpeg", ".bmp", ".tga", ".dds", ".ktx"],
+            ImporterType::Transmission => &[".obj", ".gltf", ".glb"],
+            ImporterType::Audio => &[".ogg", ".wav"],
+        }
+    }
+}
+
+/// A resource produced by importing an asset.
+#[derive(Debug, Clone)]
+pub struct ImportedResource {
+    /// The UUID of this resource.
+    pub uuid: Uuid,
+    /// The name of sub-resource, its `None` for the main resource of asset.
+    pub name: Option<String>,
+    /// The serialized bytes in runtime format.
+    pub bytes: Vec<u8>,
+    /// The UUIDs of resources that this resource depends on.
+    pub dependencies: Vec<Uuid>,
+}
+
+/// Imports the asset at `path` with specified importer.
+pub fn import<P: AsRef<Path>>(
+    importer: ImporterType,
+    path: P,
+    meta: &mut Meta,
+) -> Result<Vec<ImportedResource>> {
+    let path = path.as_ref();
+    match importer {
+        ImporterType::Texture => texture::import(path, meta),
+        ImporterType::Transmission => model::import(path, meta),
+        ImporterType::Audio => audio::import(path, meta),
+    }
+}
+
+/// Returns the lowercase extension of `path` (without dot).
+pub(crate) fn extension(path: &Path) -> String {
+    path.extension()
+        .and_then(|v| v.to_str())
+        .map(|v| v.to_lowercase())
+        .unwrap_or_default()
+}
@@ -0,0 +1,252 @@
+//! Imports OBJ and glTF models into a `PREB` prefab, `VMSH` meshes and `VTEX` textures.
+//!
+//! Every model in the OBJ file is converted into a mesh, and the prefab is made of a root node
+//! with one child node for each mesh. glTF scenes are converted with
+//! `crayon_world::assets::gltf`, and the images used by materials are imported as textures.
+
+use std::fs;
+use std::path::Path;
+
+use crayon::bincode;
+use crayon::errors::Result;
+use crayon::math::prelude::Aabb3;
+use crayon::video::assets::mesh_loader::MAGIC as MESH_MAGIC;
+use crayon::video::prelude::{
+    Attribute, IndexFormat, MeshData, MeshParams, VertexFormat, VertexLayout,
+};
+use crayon_world::assets::gltf;
+use crayon_world::assets::prefab::{Prefab, PrefabNode};
+use crayon_world::assets::prefab_loader::MAGIC as PREFAB_MAGIC;
+use crayon_world::spatial::prelude::Transform;
+
+use super::super::meta::Meta;
+use super::texture;
+use super::ImportedResource;
+
+pub fn import(path: &Path, meta: &mut Meta) -> Result<Vec<ImportedResource>> {
+    match super::extension(path).as_str() {
+        "obj" => import_obj(path, meta),
+        "gltf" | "glb" => import_gltf(path, meta),
+        _ => bail!("Model {} is not supported.", path.display()),
+    }
+}
+
+fn import_obj(path: &Path, meta: &mut Meta) -> Result<Vec<ImportedResource>> {
+    let (models, _) = tobj::load_obj(path)?;
+
+    let name = path
+        .file_stem()
+        .and_then(|v| v.to_str())
+        .unwrap_or("")
+        .to_owned();
+
+    let mut prefab = Prefab::default();
+
+    prefab.nodes.push(PrefabNode {
+        name,
+        local_transform: Transform::default(),
+        first_child: if models.is_empty() { None } else { Some(1) },
+        next_sib: None,
+        mesh_renderer: None,
+        material: None,
+    });
+
+    let mut resources = Vec::new();
+    for (i, model) in models.iter().enumerate() {
+        let (params, data) = decode_obj_mesh(&model.mesh)?;
+        let sub = format!("meshes/{}", i);
+        let uuid = meta.sub(&sub);
+
+        resources.push(ImportedResource {
+            uuid,
+            name: Some(sub),
+            bytes: encode_mesh(&params, &data)?,
+            dependencies: Vec::new(),
+        });
+
+        prefab.universe_meshes.push(uuid);
+        prefab.nodes.push(PrefabNode {
+            name: model.name.clone(),
+            local_transform: Transform::default(),
+            first_child: None,
+            next_sib: if i + 1 < models.len() { Some(i + 2) } else { None },
+            mesh_renderer: Some(i),
+            material: None,
+        });
+    }
+
+    resources.push(ImportedResource {
+        uuid: meta.uuid,
+        name: None,
+        bytes: encode_prefab(&prefab)?,
+        dependencies: prefab.universe_meshes.clone(),
+    });
+
+    Ok(resources)
+}
+
+fn import_gltf(path: &Path, meta: &mut Meta) -> Result<Vec<ImportedResource>> {
+    let dir = path.parent().unwrap_or_else(|| Path::new(""));
+    let bytes = fs::read(path)?;
+
+    let scene = gltf::import(&bytes, |uri| {
+        let file = dir.join(uri);
+        fs::read(&file).map_err(|err| format_err!("Failed to read {}. {}", file.display(), err))
+    })?;
+
+    let mut prefab = scene.prefab;
+    let mut resources = Vec::new();
+
+    for (i, (params, data)) in scene.meshes.iter().enumerate() {
+        let sub = format!("meshes/{}", i);
+        let uuid = meta.sub(&sub);
+
+        resources.push(ImportedResource {
+            uuid,
+            name: Some(sub),
+            bytes: encode_mesh(params, data)?,
+            dependencies: Vec::new(),
+        });
+
+        prefab.universe_meshes.push(uuid);
+    }
+
+    // Materials index textures by its position in `universe_textures`, so images that could
+    // not be decoded are detached from materials.
+    let mut slots = Vec::with_capacity(scene.images.len());
+    for (i, image) in scene.images.iter().enumerate() {
+        let decoded = image.as_ref().map(|v| texture::decode_image(v));
+
+        match decoded {
+            Some(Ok((params, data))) => {
+                let sub = format!("textures/{}", i);
+                let uuid = meta.sub(&sub);
+
+                resources.push(ImportedResource {
+                    uuid,
+                    name: Some(sub),
+                    bytes: texture::encode(&params, &data)?,
+                    dependencies: Vec::new(),
+                });
+
+                slots.push(Some(prefab.universe_textures.len()));
+                prefab.universe_textures.push(uuid);
+            }
+            Some(Err(err)) => {
+                warn!("Failed to decode image {} of {}. {}", i, path.display(), err);
+                slots.push(None);
+            }
+            None => slots.push(None),
+        }
+    }
+
+    for v in &mut prefab.materials {
+        v.base_color_texture = v.base_color_texture.and_then(|i| slots[i]);
+    }
+
+    let mut dependencies = prefab.universe_meshes.clone();
+    dependencies.extend_from_slice(&prefab.universe_textures);
+
+    resources.push(ImportedResource {
+        uuid: meta.uuid,
+        name: None,
+        bytes: encode_prefab(&prefab)?,
+        dependencies,
+    });
+
+    Ok(resources)
+}
+
+/// Encodes the mesh into `VMSH` format.
+pub fn encode_mesh(params: &MeshParams, data: &MeshData) -> Result<Vec<u8>> {
+    let mut bytes = MESH_MAGIC.to_vec();
+    bincode::serialize_into(&mut bytes, params)?;
+    bincode::serialize_into(&mut bytes, data)?;
+    Ok(bytes)
+}
+
+/// Encodes the prefab into `PREB` format.
+pub fn encode_prefab(prefab: &Prefab) -> Result<Vec<u8>> {
+    let mut bytes = PREFAB_MAGIC.to_vec();
+    bincode::serialize_into(&mut bytes, prefab)?;
+    Ok(bytes)
+}
+
+/// Converts the OBJ mesh into interleaved vertices with positions, and optional normals and
+/// texture coordinates.
+pub fn decode_obj_mesh(mesh: &tobj::Mesh) -> Result<(MeshParams, MeshData)> {
+    let num_verts = mesh.positions.len() / 3;
+    let has_normals = mesh.normals.len() == num_verts * 3 && num_verts > 0;
+    let has_texcoords = mesh.texcoords.len() == num_verts * 2 && num_verts > 0;
+
+    let mut layout = VertexLayout::build().with(Attribute::Position, VertexFormat::Float, 3, false);
+    if has_normals {
+        layout = layout.with(Attribute::Normal, VertexFormat::Float, 3, false);
+    }
+
+    if has_texcoords {
+        layout = layout.with(Attribute::Texcoord0, VertexFormat::Float, 2, false);
+    }
+
+    let mut params = MeshParams::default();
+    params.layout = layout.finish();
+    params.num_verts = num_verts;
+    params.num_idxes = mesh.indices.len();
+    params.aabb = aabb(&mesh.positions);
+
+    let mut verts = Vec::with_capacity(params.vertex_buffer_len() / 4);
+    for i in 0..num_verts {
+        verts.extend_from_slice(&mesh.positions[i * 3..i * 3 + 3]);
+
+        if has_normals {
+            verts.extend_from_slice(&mesh.normals[i * 3..i * 3 + 3]);
+        }
+
+        if has_texcoords {
+            verts.extend_from_slice(&mesh.texcoords[i * 2..i * 2 + 2]);
+        }
+    }
+
+    if mesh.indices.iter().any(|&v| v as usize >= num_verts) {
+        bail!("Index out of bounds.");
+    }
+
+    let iptr: Box<[u8]> = if num_verts <= std::u16::MAX as usize {
+        params.index_format = IndexFormat::U16;
+        let idxes: Vec<u16> = mesh.indices.iter().map(|&v| v as u16).collect();
+        IndexFormat::encode(&idxes).into()
+    } else {
+        params.index_format = IndexFormat::U32;
+        IndexFormat::encode(&mesh.indices).into()
+    };
+
+    let data = MeshData {
+        vptr: IndexFormat::encode(&verts).into(),
+        iptr,
+    };
+
+    params.validate(Some(&data))?;
+    Ok((params, data))
+}
+
+fn aabb(positions: &[f32]) -> Aabb3<f32> {
+    let mut aabb = Aabb3::zero();
+
+    for (i, v) in positions.chunks(3).filter(|v| v.len() == 3).enumerate() {
+        if i == 0 {
+            aabb.min.x = v[0];
+            aabb.min.y = v[1];
+            aabb.min.z = v[2];
+            aabb.max = aabb.min;
+        } else {
+            aabb.min.x = aabb.min.x.min(v[0]);
+            aabb.min.y = aabb.min.y.min(v[1]);
+            aabb.min.z = aabb.min.z.min(v[2]);
+            aabb.max.x = aabb.max.x.max(v[0]);
+            aabb.max.y = aabb.max.y.max(v[1]);
+            aabb.max.z = aabb.max.z.max(v[2]);
+        }
+    }
+
+    aabb
+}
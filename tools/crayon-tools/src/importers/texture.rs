@@ -0,0 +1,197 @@
+//! Imports images into `VTEX` textures.
+//!
+//! Common image formats (PNG/JPEG/BMP/TGA) are decoded into `RGBA8`. DDS (DXT1/DXT5 and
+//! uncompressed 32-bits) and KTX (ETC2, PVRTC, S3TC and uncompressed 8-bits) containers are
+//! copied with all their mipmaps without decompression.
+
+use std::fs;
+use std::path::Path;
+
+use crayon::bincode;
+use crayon::errors::Result;
+use crayon::math::prelude::Vector2;
+use crayon::video::assets::texture_loader::MAGIC;
+use crayon::video::prelude::{TextureData, TextureFormat, TextureParams};
+
+use super::super::meta::Meta;
+use super::ImportedResource;
+
+pub fn import(path: &Path, meta: &mut Meta) -> Result<Vec<ImportedResource>> {
+    let bytes = fs::read(path)?;
+
+    let (params, data) = match super::extension(path).as_str() {
+        "dds" => decode_dds(&bytes)?,
+        "ktx" => decode_ktx(&bytes)?,
+        _ => decode_image(&bytes)?,
+    };
+
+    Ok(vec![ImportedResource {
+        uuid: meta.uuid,
+        name: None,
+        bytes: encode(&params, &data)?,
+        dependencies: Vec::new(),
+    }])
+}
+
+/// Encodes the texture into `VTEX` format.
+pub fn encode(params: &TextureParams, data: &TextureData) -> Result<Vec<u8>> {
+    let mut bytes = MAGIC.to_vec();
+    bincode::serialize_into(&mut bytes, params)?;
+    bincode::serialize_into(&mut bytes, data)?;
+    Ok(bytes)
+}
+
+/// Decodes PNG/JPEG/BMP/TGA image into `RGBA8` texture.
+pub fn decode_image(bytes: &[u8]) -> Result<(TextureParams, TextureData)> {
+    let image = image::load_from_memory(bytes)?.to_rgba();
+
+    let mut params = TextureParams::default();
+    params.format = TextureFormat::RGBA8;
+    params.dimensions = Vector2::new(image.width(), image.height());
+
+    let data = TextureData {
+        bytes: vec![image.into_raw().into_boxed_slice()],
+    };
+
+    Ok((params, data))
+}
+
+/// Decodes DDS container.
+pub fn decode_dds(bytes: &[u8]) -> Result<(TextureParams, TextureData)> {
+    const HEADER: usize = 128;
+    const DDPF_FOURCC: u32 = 0x4;
+    const DDPF_RGB: u32 = 0x40;
+
+    if bytes.len() < HEADER || &bytes[0..4] != b"DDS " {
+        bail!("[DDS] MAGIC number not match.");
+    }
+
+    let height = read_u32(bytes, 12, true);
+    let width = read_u32(bytes, 16, true);
+    let levels = read_u32(bytes, 28, true).max(1);
+    let pf_flags = read_u32(bytes, 80, true);
+    let four_cc = &bytes[84..88];
+    let bits = read_u32(bytes, 88, true);
+    let r_mask = read_u32(bytes, 92, true);
+
+    let (format, block) = if pf_flags & DDPF_FOURCC != 0 {
+        match four_cc {
+            b"DXT1" => (TextureFormat::S3tcDxt1RGB4BPP, Some(8)),
+            b"DXT5" => (TextureFormat::S3tcDxt5RGBA8BPP, Some(16)),
+            _ => bail!("[DDS] FourCC {:?} is not supported.", four_cc),
+        }
+    } else if pf_flags & DDPF_RGB != 0 && bits == 32 {
+        (TextureFormat::RGBA8, None)
+    } else {
+        bail!("[DDS] Pixel format is not supported.");
+    };
+
+    let mut data = TextureData { bytes: Vec::new() };
+    let mut iter = HEADER;
+    let (mut w, mut h) = (width, height);
+
+    for _ in 0..levels {
+        let len = match block {
+            Some(block) => ((w + 3) / 4).max(1) * ((h + 3) / 4).max(1) * block,
+            None => w * h * 4,
+        } as usize;
+
+        if iter + len > bytes.len() {
+            bail!("[DDS] Unexpected end of file.");
+        }
+
+        let mut level = bytes[iter..iter + len].to_vec();
+
+        // Converts BGRA into RGBA.
+        if block.is_none() && r_mask == 0x00ff_0000 {
+            for v in level.chunks_mut(4) {
+                v.swap(0, 2);
+            }
+        }
+
+        data.bytes.push(level.into_boxed_slice());
+        iter += len;
+        w = (w / 2).max(1);
+        h = (h / 2).max(1);
+    }
+
+    let mut params = TextureParams::default();
+    params.format = format;
+    params.dimensions = Vector2::new(width, height);
+    Ok((params, data))
+}
+
+/// Decodes KTX (version 1.1) container.
+pub fn decode_ktx(bytes: &[u8]) -> Result<(TextureParams, TextureData)> {
+    const IDENTIFIER: [u8; 12] = [
+        0xAB, 0x4B, 0x54, 0x58, 0x20, 0x31, 0x31, 0xBB, 0x0D, 0x0A, 0x1A, 0x0A,
+    ];
+    const HEADER: usize = 64;
+
+    if bytes.len() < HEADER || bytes[0..12] != IDENTIFIER[..] {
+        bail!("[KTX] MAGIC number not match.");
+    }
+
+    let le = read_u32(bytes, 12, true) == 0x0403_0201;
+    let internal_format = read_u32(bytes, 28, le);
+    let width = read_u32(bytes, 36, le);
+    let height = read_u32(bytes, 40, le).max(1);
+    let faces = read_u32(bytes, 52, le);
+    let levels = read_u32(bytes, 56, le).max(1);
+    let kvs = read_u32(bytes, 60, le) as usize;
+
+    if faces != 1 {
+        bail!("[KTX] Cubemaps are not supported.");
+    }
+
+    let format = match internal_format {
+        0x83F0 | 0x83F1 => TextureFormat::S3tcDxt1RGB4BPP,
+        0x83F3 => TextureFormat::S3tcDxt5RGBA8BPP,
+        0x8D64 | 0x9274 => TextureFormat::Etc2RGB4BPP,
+        0x9278 => TextureFormat::Etc2RGBA8BPP,
+        0x8C00 => TextureFormat::PvrtcRGB4BPP,
+        0x8C01 => TextureFormat::PvrtcRGB2BPP,
+        0x8C02 => TextureFormat::PvrtcRGBA4BPP,
+        0x8C03 => TextureFormat::PvrtcRGBA2BPP,
+        0x8058 | 0x1908 => TextureFormat::RGBA8,
+        0x8051 | 0x1907 => TextureFormat::RGB8,
+        0x8229 | 0x1903 => TextureFormat::R8,
+        v => bail!("[KTX] Internal format 0x{:X} is not supported.", v),
+    };
+
+    let mut data = TextureData { bytes: Vec::new() };
+    let mut iter = HEADER + kvs;
+
+    for _ in 0..levels {
+        if iter + 4 > bytes.len() {
+            bail!("[KTX] Unexpected end of file.");
+        }
+
+        let len = read_u32(bytes, iter, le) as usize;
+        iter += 4;
+
+        if iter + len > bytes.len() {
+            bail!("[KTX] Unexpected end of file.");
+        }
+
+        data.bytes.push(bytes[iter..iter + len].to_vec().into_boxed_slice());
+
+        // Mipmaps are padded to 4 bytes.
+        iter += (len + 3) & !3;
+    }
+
+    let mut params = TextureParams::default();
+    params.format = format;
+    params.dimensions = Vector2::new(width, height);
+    Ok((params, data))
+}
+
+#[inline]
+fn read_u32(bytes: &[u8], offset: usize, le: bool) -> u32 {
+    let v = &bytes[offset..offset + 4];
+    if le {
+        u32::from(v[0]) | (u32::from(v[1]) << 8) | (u32::from(v[2]) << 16) | (u32::from(v[3]) << 24)
+    } else {
+        u32::from(v[3]) | (u32::from(v[2]) << 8) | (u32::from(v[1]) << 16) | (u32::from(v[0]) << 24)
+    }
+}
@@ -0,0 +1,55 @@
+//! # crayon-tools
+//!
+//! The asset import pipeline of crayon. Assets are files stored in formats that authoring tools
+//! produce, such like PNG images, OBJ/glTF models and OGG clips. The `crayon-tools` compiles them into
+//! the binary resources (`VTEX`, `VMSH`, `PREB` and `CSFX`) that could be loaded at runtime
+//! without any further processing, and writes a `MANIFEST` file which could be attached to the
+//! `ResourceSystem` directly.
+//!
+//! # Workspace
+//!
+//! A workspace is described by a `workspace.toml` file, which specifies where the assets are
+//! placed and where the resources should be written to. Importers are picked by the extension
+//! of assets:
+//!
+//! ```toml
+//! [assets]
+//! source = 'assets'
+//! destination = 'resources'
+//!
+//! [[assets.importers]]
+//! type = 'Texture'
+//! extensions = ['.png', '.tga', '.dds', '.ktx']
+//! ```
+//!
+//! The default importers are used if there is no `assets.importers` in the workspace.
+//!
+//! # Meta
+//!
+//! For every asset, a `.meta.toml` file is generated besides it when its imported for the first
+//! time. It records the UUIDs of all the resources this asset produces, so the references between
+//! resources keep valid across builds. You should always submit .meta files into version control
+//! with assets.
+//!
+//! # Command Line
+//!
+//! ```sh
+//! crayon-tools path/to/workspace.toml
+//! ```
+
+#[macro_use]
+extern crate failure;
+#[macro_use]
+extern crate log;
+#[macro_use]
+extern crate serde;
+
+pub mod importers;
+pub mod meta;
+pub mod workspace;
+
+pub mod prelude {
+    pub use super::importers::{ImportedResource, ImporterType};
+    pub use super::meta::Meta;
+    pub use super::workspace::{Workspace, WorkspaceParams};
+}
@@ -0,0 +1,26 @@
+use crayon_tools::prelude::*;
+
+const USAGE: &str = "Usage: crayon-tools [path/to/workspace.toml]";
+
+fn main() {
+    env_logger::init();
+
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.iter().any(|v| v == "-h" || v == "--help") || args.len() > 1 {
+        println!("{}", USAGE);
+        return;
+    }
+
+    let path = args
+        .get(0)
+        .map(|v| v.as_str())
+        .unwrap_or("workspace.toml");
+
+    match Workspace::load(path).and_then(|v| v.build()) {
+        Ok(num) => println!("Built {} resources from {}.", num, path),
+        Err(err) => {
+            eprintln!("{}", err);
+            std::process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,68 @@
+//! The `.meta.toml` file which records UUIDs of resources produced by an asset.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crayon::errors::Result;
+use uuid::Uuid;
+
+use super::importers::ImporterType;
+
+/// The suffix of meta files.
+pub const SUFFIX: &str = ".meta.toml";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Meta {
+    /// The UUID of the main resource.
+    pub uuid: Uuid,
+    /// The importer of this asset.
+    pub importer: ImporterType,
+    /// The UUIDs of sub-resources, indexed by their names.
+    #[serde(default)]
+    pub subs: BTreeMap<String, Uuid>,
+}
+
+impl Meta {
+    /// Creates a new `Meta` with random UUID.
+    pub fn new(importer: ImporterType) -> Self {
+        Meta {
+            uuid: Uuid::new_v4(),
+            importer,
+            subs: BTreeMap::new(),
+        }
+    }
+
+    /// Returns the path of meta file of asset at `path`.
+    pub fn path<P: AsRef<Path>>(path: P) -> PathBuf {
+        let mut name = path.as_ref().as_os_str().to_owned();
+        name.push(SUFFIX);
+        name.into()
+    }
+
+    /// Loads the meta file of asset at `path` if exists.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Option<Self>> {
+        let path = Meta::path(path);
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let contents = fs::read_to_string(&path)?;
+        Ok(Some(toml::from_str(&contents)?))
+    }
+
+    /// Saves this meta besides the asset at `path`.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let contents = toml::to_string(self)?;
+        fs::write(Meta::path(path), contents)?;
+        Ok(())
+    }
+
+    /// Gets the UUID of sub-resource with `name`, a new one will be allocated if not exists.
+    pub fn sub<T: AsRef<str>>(&mut self, name: T) -> Uuid {
+        *self
+            .subs
+            .entry(name.as_ref().to_owned())
+            .or_insert_with(Uuid::new_v4)
+    }
+}
@@ -0,0 +1,212 @@
+//! Workspace which compiles all the assets under source directory into resources.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crayon::bincode;
+use crayon::errors::Result;
+use crayon::res::manifest::{Manifest, ManifestItem, MAGIC, NAME};
+use crayon::utils::prelude::FastHashMap;
+use uuid::Uuid;
+
+use super::importers::{self, ImportedResource, ImporterType};
+use super::meta::{self, Meta};
+
+/// The setup parameters of workspace, which are usually loaded from `workspace.toml`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkspaceParams {
+    pub assets: AssetsParams,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssetsParams {
+    /// The directory of assets, relative to the workspace.
+    pub source: PathBuf,
+    /// The directory to write resources into, relative to the workspace.
+    pub destination: PathBuf,
+    /// The importers and the asset extensions they handle. The default importers are
+    /// used if its empty.
+    #[serde(default)]
+    pub importers: Vec<ImporterParams>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImporterParams {
+    #[serde(rename = "type")]
+    pub importer: ImporterType,
+    pub extensions: Vec<String>,
+}
+
+pub struct Workspace {
+    root: PathBuf,
+    params: WorkspaceParams,
+}
+
+impl Workspace {
+    /// Loads the workspace from `workspace.toml` file. Paths in the workspace are relative to
+    /// the directory of this file.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let contents = fs::read_to_string(path)
+            .map_err(|err| format_err!("Failed to read {}. {}", path.display(), err))?;
+
+        let params = toml::from_str(&contents)?;
+        let root = path.parent().map(|v| v.to_owned()).unwrap_or_default();
+        Ok(Workspace::new(root, params))
+    }
+
+    /// Creates a workspace at `root` directory.
+    pub fn new<P: Into<PathBuf>>(root: P, params: WorkspaceParams) -> Self {
+        Workspace {
+            root: root.into(),
+            params,
+        }
+    }
+
+    /// Gets the directory of assets.
+    #[inline]
+    pub fn source(&self) -> PathBuf {
+        self.root.join(&self.params.assets.source)
+    }
+
+    /// Gets the directory of resources.
+    #[inline]
+    pub fn destination(&self) -> PathBuf {
+        self.root.join(&self.params.assets.destination)
+    }
+
+    /// Finds the importer of asset at `path` by its extension.
+    pub fn importer<P: AsRef<Path>>(&self, path: P) -> Option<ImporterType> {
+        let ext = format!(".{}", importers::extension(path.as_ref()));
+
+        if self.params.assets.importers.is_empty() {
+            let defaults = [
+                ImporterType::Texture,
+                ImporterType::Transmission,
+                ImporterType::Audio,
+            ];
+
+            return defaults
+                .iter()
+                .find(|v| v.extensions().contains(&ext.as_str()))
+                .cloned();
+        }
+
+        self.params
+            .assets
+            .importers
+            .iter()
+            .find(|v| v.extensions.iter().any(|e| e.to_lowercase() == ext))
+            .map(|v| v.importer)
+    }
+
+    /// Imports all the assets, writes the resources and `MANIFEST` into destination. Returns
+    /// the number of resources.
+    pub fn build(&self) -> Result<usize> {
+        let source = self.source();
+        let destination = self.destination();
+        fs::create_dir_all(&destination)?;
+
+        let mut assets = Vec::new();
+        collect(&source, &mut assets)?;
+        assets.sort();
+
+        let mut resources: Vec<(String, ImportedResource)> = Vec::new();
+        for path in assets {
+            let importer = match self.importer(&path) {
+                Some(importer) => importer,
+                None => continue,
+            };
+
+            let name = path
+                .strip_prefix(&source)?
+                .components()
+                .map(|v| v.as_os_str().to_string_lossy().into_owned())
+                .collect::<Vec<_>>()
+                .join("/");
+
+            let (mut meta, dirty) = match Meta::load(&path)? {
+                Some(meta) if meta.importer == importer => (meta, false),
+                Some(mut meta) => {
+                    meta.importer = importer;
+                    (meta, true)
+                }
+                None => (Meta::new(importer), true),
+            };
+
+            let snapshot = meta.clone();
+            let imported = importers::import(importer, &path, &mut meta)
+                .map_err(|err| format_err!("Failed to import {}. {}", path.display(), err))?;
+
+            if dirty || meta != snapshot {
+                meta.save(&path)?;
+            }
+
+            info!("[Workspace] imported {} ({:?}).", name, importer);
+
+            for v in imported {
+                let filename = match v.name.as_ref() {
+                    Some(sub) => format!("{}#{}", name, sub),
+                    None => name.clone(),
+                };
+
+                fs::write(destination.join(format!("{:X}", v.uuid.to_simple())), &v.bytes)?;
+                resources.push((filename, v));
+            }
+        }
+
+        let manifest = build_manifest(&resources)?;
+        let mut bytes = MAGIC.to_vec();
+        bincode::serialize_into(&mut bytes, &manifest)?;
+        fs::write(destination.join(NAME), &bytes)?;
+
+        Ok(resources.len())
+    }
+}
+
+fn build_manifest(resources: &[(String, ImportedResource)]) -> Result<Manifest> {
+    let mut indices = FastHashMap::default();
+    for (i, (filename, v)) in resources.iter().enumerate() {
+        if indices.insert(v.uuid, i).is_some() {
+            bail!("Duplicated UUID {} of {}.", v.uuid, filename);
+        }
+    }
+
+    let mut manifest = Manifest::new();
+    for (filename, v) in resources {
+        let dependencies = v
+            .dependencies
+            .iter()
+            .map(|uuid: &Uuid| {
+                indices
+                    .get(uuid)
+                    .cloned()
+                    .ok_or_else(|| format_err!("Dependency {} of {} is missing.", uuid, filename))
+            })
+            .collect::<Result<Vec<usize>>>()?;
+
+        let filename = manifest.buf.extend_from_str(filename.as_str());
+        let dependencies = manifest.buf.extend_from_slice(&dependencies);
+        manifest.items.push(ManifestItem {
+            filename,
+            dependencies,
+            uuid: v.uuid,
+        });
+    }
+
+    Ok(manifest)
+}
+
+fn collect(dir: &Path, assets: &mut Vec<PathBuf>) -> Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+
+        if path.is_dir() {
+            collect(&path, assets)?;
+        } else if !path.to_string_lossy().ends_with(meta::SUFFIX) {
+            assets.push(path);
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,136 @@
+extern crate crayon;
+extern crate crayon_tools;
+
+use std::fs;
+use std::path::PathBuf;
+
+use crayon::res::manifest::ManfiestResolver;
+use crayon_tools::prelude::*;
+
+const OBJ: &str = "
+o triangle
+v 0.0 0.0 0.0
+v 1.0 0.0 0.0
+v 0.0 1.0 0.0
+f 1 2 3
+";
+
+fn wav() -> Vec<u8> {
+    let samples: [i16; 4] = [0, 1024, -1024, 0];
+
+    let mut bytes = Vec::new();
+    bytes.extend_from_slice(b"RIFF");
+    bytes.extend_from_slice(&(36 + 8u32).to_le_bytes());
+    bytes.extend_from_slice(b"WAVEfmt ");
+    bytes.extend_from_slice(&16u32.to_le_bytes());
+    bytes.extend_from_slice(&1u16.to_le_bytes());
+    bytes.extend_from_slice(&1u16.to_le_bytes());
+    bytes.extend_from_slice(&44100u32.to_le_bytes());
+    bytes.extend_from_slice(&(44100u32 * 2).to_le_bytes());
+    bytes.extend_from_slice(&2u16.to_le_bytes());
+    bytes.extend_from_slice(&16u16.to_le_bytes());
+    bytes.extend_from_slice(b"data");
+    bytes.extend_from_slice(&8u32.to_le_bytes());
+    for v in &samples {
+        bytes.extend_from_slice(&v.to_le_bytes());
+    }
+
+    bytes
+}
+
+fn dds() -> Vec<u8> {
+    let mut bytes = vec![0; 128];
+    bytes[0..4].copy_from_slice(b"DDS ");
+    bytes[12..16].copy_from_slice(&4u32.to_le_bytes());
+    bytes[16..20].copy_from_slice(&4u32.to_le_bytes());
+    bytes[28..32].copy_from_slice(&1u32.to_le_bytes());
+    bytes[80..84].copy_from_slice(&4u32.to_le_bytes());
+    bytes[84..88].copy_from_slice(b"DXT1");
+    bytes.extend_from_slice(&[0; 8]);
+    bytes
+}
+
+fn setup(name: &str) -> PathBuf {
+    let root = std::env::temp_dir().join(format!("crayon-tools-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&root);
+
+    fs::create_dir_all(root.join("assets/models")).unwrap();
+    fs::write(root.join("assets/models/triangle.obj"), OBJ).unwrap();
+    fs::write(root.join("assets/sfx.wav"), wav()).unwrap();
+    fs::write(root.join("assets/tex.dds"), dds()).unwrap();
+    fs::write(root.join("assets/readme.txt"), "ignored").unwrap();
+    fs::write(
+        root.join("workspace.toml"),
+        "[assets]\nsource = 'assets'\ndestination = 'resources'\n",
+    )
+    .unwrap();
+
+    root
+}
+
+#[test]
+fn build() {
+    let root = setup("build");
+    let workspace = Workspace::load(root.join("workspace.toml")).unwrap();
+    assert_eq!(workspace.importer("a.PNG"), Some(ImporterType::Texture));
+    assert_eq!(workspace.importer("a.txt"), None);
+
+    // Prefab and mesh, audio clip and texture.
+    assert_eq!(workspace.build().unwrap(), 4);
+
+    let mut resolver = ManfiestResolver::new();
+    let mut file = fs::File::open(root.join("resources/MANIFEST")).unwrap();
+    resolver.add("res:", &mut file).unwrap();
+
+    for name in &[
+        "res:/models/triangle.obj",
+        "res:/models/triangle.obj#meshes/0",
+        "res:/sfx.wav",
+        "res:/tex.dds",
+    ] {
+        let uuid = resolver.find(name).unwrap();
+        let path = root.join(format!("resources/{:X}", uuid.to_simple()));
+        assert!(path.exists(), "{} is missing.", name);
+    }
+
+    let prefab = resolver.find("res:/models/triangle.obj").unwrap();
+    let mesh = resolver.find("res:/models/triangle.obj#meshes/0").unwrap();
+    assert_eq!(resolver.dependencies(prefab).unwrap().count(), 1);
+
+    // UUIDs are stable across builds since they are recorded in .meta files.
+    let meta = Meta::load(root.join("assets/models/triangle.obj"))
+        .unwrap()
+        .unwrap();
+    assert_eq!(meta.uuid, prefab);
+    assert_eq!(meta.subs["meshes/0"], mesh);
+
+    workspace.build().unwrap();
+    let mut resolver = ManfiestResolver::new();
+    let mut file = fs::File::open(root.join("resources/MANIFEST")).unwrap();
+    resolver.add("res:", &mut file).unwrap();
+    assert_eq!(resolver.find("res:/models/triangle.obj"), Some(prefab));
+
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn invalid() {
+    let root = setup("invalid");
+    fs::write(root.join("assets/broken.ktx"), b"not a ktx").unwrap();
+
+    let workspace = Workspace::load(root.join("workspace.toml")).unwrap();
+    assert!(workspace.build().is_err());
+
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn truncated_wav() {
+    let root = setup("truncated_wav");
+    fs::write(root.join("assets/broken.wav"), &wav()[0..30]).unwrap();
+
+    let workspace = Workspace::load(root.join("workspace.toml")).unwrap();
+    assert!(workspace.build().is_err());
+
+    let _ = fs::remove_dir_all(&root);
+}